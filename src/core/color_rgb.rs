@@ -4,6 +4,55 @@ use minifb::clamp;
 use rand::{rngs::{ThreadRng, mock::StepRng}, Rng};
 use rand_chacha::ChaChaRng;
 
+// Piecewise gaussian fit of the CIE 1931 color matching functions by Wyman, Sloan and Shirley
+#[inline]
+fn piecewise_gaussian(wavelength: f64, mean: f64, sigma_below: f64, sigma_above: f64) -> f64 {
+    let t = (wavelength - mean) / if wavelength < mean { sigma_below } else { sigma_above };
+    (-0.5 * t * t).exp()
+}
+
+#[inline]
+fn cie_xyz(wavelength: f64) -> (f64, f64, f64) {
+    let x = 1.056 * piecewise_gaussian(wavelength, 599.8, 37.9, 31.0) + 0.362 * piecewise_gaussian(wavelength, 442.0, 16.0, 26.7) - 0.065 * piecewise_gaussian(wavelength, 501.1, 20.4, 26.2);
+    let y = 0.821 * piecewise_gaussian(wavelength, 568.8, 46.9, 40.5) + 0.286 * piecewise_gaussian(wavelength, 530.9, 16.3, 31.1);
+    let z = 1.217 * piecewise_gaussian(wavelength, 437.0, 11.8, 36.0) + 0.681 * piecewise_gaussian(wavelength, 459.0, 26.0, 13.8);
+
+    (x, y, z)
+}
+
+// Spectral radiance of a blackbody in W / (sr m^2 nm) by Planck's law
+#[inline]
+fn planck(wavelength_nm: f64, temperature: f64) -> f64 {
+    const PLANCK: f64 = 6.626_070_15e-34;
+    const LIGHT_SPEED: f64 = 299_792_458.0;
+    const BOLTZMANN: f64 = 1.380_649e-23;
+
+    let wavelength = wavelength_nm * 1.0e-9;
+    let exponent = PLANCK * LIGHT_SPEED / (wavelength * BOLTZMANN * temperature);
+    2.0 * PLANCK * LIGHT_SPEED * LIGHT_SPEED / (wavelength.powi(5) * exponent.exp_m1()) * 1.0e-9
+}
+
+// CIE XYZ of a blackbody, integrated over the visible range
+fn blackbody_xyz(temperature: f32) -> (f64, f64, f64) {
+    let (mut x, mut y, mut z) = (0.0, 0.0, 0.0);
+    if temperature <= 0.0 {
+        return (x, y, z);
+    }
+
+    const STEP: f64 = 5.0;
+    let mut wavelength: f64 = 380.0;
+    while wavelength <= 780.0 {
+        let radiance = planck(wavelength, temperature as f64) * STEP;
+        let (x_bar, y_bar, z_bar) = cie_xyz(wavelength);
+        x += radiance * x_bar;
+        y += radiance * y_bar;
+        z += radiance * z_bar;
+        wavelength += STEP;
+    }
+
+    (x, y, z)
+}
+
 #[derive(Default, Debug, Copy, Clone, PartialEq)]
 pub struct ColorRGB {
     pub r: f32,
@@ -50,6 +99,33 @@ impl ColorRGB {
         self.is_black() && self.is_nan() 
     }
 
+    // Relative luminance of linear sRGB primaries
+    #[inline]
+    pub fn luminance(&self) -> f32 {
+        0.2126 * self.r + 0.7152 * self.g + 0.0722 * self.b
+    }
+
+    // Linear sRGB color of a blackbody at a temperature in Kelvin, scaled to a luminance of 1.
+    // Colors outside of the sRGB gamut, like the deep red of cool bodies, are clamped.
+    pub fn blackbody(temperature: f32) -> Self {
+        let (x, y, z) = blackbody_xyz(temperature);
+        if y <= 0.0 {
+            return ColorRGB::black();
+        }
+
+        let (x, y, z) = (x / y, 1.0, z / y);
+        ColorRGB {
+            r: ( 3.2406 * x - 1.5372 * y - 0.4986 * z).max(0.0) as f32,
+            g: (-0.9689 * x + 1.8758 * y + 0.0415 * z).max(0.0) as f32,
+            b: ( 0.0557 * x - 0.2040 * y + 1.0570 * z).max(0.0) as f32,
+        }
+    }
+
+    // Luminance of a blackbody at a temperature in Kelvin, in candela per square meter
+    pub fn blackbody_luminance(temperature: f32) -> f32 {
+        (683.0 * blackbody_xyz(temperature).1) as f32
+    }
+
     #[inline]
     pub fn random(rng: &mut ThreadRng) -> Self {
         ColorRGB { r: rng.gen::<f32>(), g: rng.gen::<f32>(), b: rng.gen::<f32>() }
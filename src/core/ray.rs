@@ -5,6 +5,8 @@ pub struct Ray {
     pub origin : Vec3,
     pub direction: Vec3,
     pub time: f32,
+    pub cone_width: f32, // Width of the pixel footprint at the origin, for texture filtering
+    pub cone_spread: f32, // Angle the footprint grows by per unit of distance, 0 for rays not traced from the camera
 }
 
 impl Ray {
@@ -12,21 +14,34 @@ impl Ray {
     #[allow(dead_code)]
     #[inline]
     pub fn new (origin: Vec3, direction: Vec3, time: f32) -> Self {
-        Ray { origin, direction, time }
+        Ray { origin, direction, time, cone_width: 0.0, cone_spread: 0.0 }
     }
 
     #[inline]
     pub fn new_normalized (origin: Vec3, direction: Vec3, time: f32) -> Self {
-        let mut ray: Ray = Ray { origin, direction, time };
+        let mut ray: Ray = Ray { origin, direction, time, cone_width: 0.0, cone_spread: 0.0 };
         
         ray.direction.normalize();
 
         ray
     }
 
+    #[inline]
+    pub fn with_cone(mut self, cone_width: f32, cone_spread: f32) -> Self {
+        self.cone_width = cone_width;
+        self.cone_spread = cone_spread;
+        self
+    }
+
     #[inline]
     pub fn at(&self, t:f32) -> Vec3 {
         self.origin + self.direction * t
     }
 
+    // Width of the pixel footprint after travelling to t, the direction doesn't have to be normalized
+    #[inline]
+    pub fn cone_width_at(&self, t: f32) -> f32 {
+        self.cone_width + self.cone_spread * t * self.direction.mag()
+    }
+
 }
\ No newline at end of file
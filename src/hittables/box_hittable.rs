@@ -2,7 +2,7 @@ use rand::rngs::ThreadRng;
 use rand_chacha::ChaCha20Rng;
 use ultraviolet::Vec3;
 
-use crate::{services::hittable_service::HittableService, core::ray::Ray, geometry::aabb::AABB};
+use crate::{services::{hittable_service::HittableService, material_service::MaterialService, texture_service::TextureService}, core::ray::Ray, geometry::aabb::AABB};
 
 use super::{xy_rect::XYRect, xz_rect::XZRect, yz_rect::YZRect, bvh_node::BVHNode, hittable::Hittable, hit_record::HitRecord, hittable_enum::HittableEnum};
 
@@ -62,4 +62,8 @@ impl Hittable for BoxHittable {
         true
     }
 
+    fn emitted_power(&self, hittable_service: &HittableService, material_service: &MaterialService, texture_service: &TextureService, flipped: bool) -> f32 {
+        hittable_service.emitted_power(self.sides_index, material_service, texture_service, flipped)
+    }
+
 }
\ No newline at end of file
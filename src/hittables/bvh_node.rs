@@ -3,7 +3,7 @@ use std::{cmp::Ordering};
 use rand::{Rng, rngs::ThreadRng};
 use rand_chacha::ChaCha20Rng;
 
-use crate::{services::hittable_service::HittableService, geometry::aabb::AABB, core::ray::Ray};
+use crate::{services::{hittable_service::HittableService, material_service::MaterialService, texture_service::TextureService}, geometry::aabb::AABB, core::ray::Ray};
 
 use super::{hittable::{Hittable}, hit_record::HitRecord, hittable_enum::HittableEnum};
 
@@ -142,4 +142,14 @@ impl Hittable for BVHNode{
         true
     }
 
+    fn emitted_power(&self, hittable_service: &HittableService, material_service: &MaterialService, texture_service: &TextureService, flipped: bool) -> f32 {
+        let left_power = hittable_service.emitted_power(self.left_index, material_service, texture_service, flipped);
+        // Single element leaves point both children at the same hittable
+        if self.left_index == self.right_index {
+            return left_power;
+        }
+
+        left_power + hittable_service.emitted_power(self.right_index, material_service, texture_service, flipped)
+    }
+
 }
\ No newline at end of file
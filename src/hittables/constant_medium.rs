@@ -19,6 +19,10 @@ impl ConstantMedium {
             negative_inverse_density: -1.0 / density 
         }
     }
+
+    pub fn from_mean_free_path(model_index: usize, phase_function: usize, mean_free_path: f32) -> ConstantMedium {
+        ConstantMedium::new(model_index, phase_function, 1.0 / mean_free_path)
+    }
 }
 
 impl Hittable for ConstantMedium {
@@ -57,6 +61,9 @@ impl Hittable for ConstantMedium {
         hit_out.v = 0.0;
         hit_out.position = ray.at(t);
         hit_out.normal = Vec3::new(1.0, 0.0, 0.0 );
+        hit_out.geometric_normal = hit_out.normal;
+        hit_out.set_tangents(&Vec3::zero(), &Vec3::zero());
+        hit_out.uv_scale = (0.0, 0.0);
         hit_out.is_front_face = true;
         hit_out.material = self.phase_function;
 
@@ -0,0 +1,101 @@
+use ultraviolet::Vec3;
+use rand::{rngs::ThreadRng, Rng};
+
+use crate::{services::hittable_service::HittableService, core::ray::Ray, geometry::aabb::AABB, math::distribution::Distribution2D, scene::environment_map::{EnvironmentMap, EquirectangularMapping, clamp_to_poles}};
+
+use super::{hittable::Hittable, hit_record::HitRecord};
+
+// Samples directions towards the bright parts of an environment map.
+// It only goes in the lights list, rays never hit it, they escape to the background instead.
+pub struct EnvironmentLight {
+    distribution: Distribution2D,
+    mapping: EquirectangularMapping,
+}
+
+impl EnvironmentLight {
+    pub fn new(environment_map: &EnvironmentMap) -> EnvironmentLight {
+        EnvironmentLight { distribution: environment_map.luminance_distribution(), mapping: environment_map.mapping() }
+    }
+
+    // Directions at the very poles would have a pdf of 0, they are nudged off them
+    fn sample_direction(&self, u0: f32, u1: f32) -> Vec3 {
+        let (u, v, _pdf) = self.distribution.sample(u0, u1);
+        self.mapping.direction(u, clamp_to_poles(v))
+    }
+}
+
+impl Hittable for EnvironmentLight {
+    fn hit(&self, _rng: &mut ThreadRng, _hittable_service: &HittableService, _ray: &Ray, _t_min: f32, _t_max: f32, _hit_out: &mut HitRecord) -> bool {
+        false
+    }
+
+    fn bounding_box(&self, _hittable_service: &HittableService, _time_0: f32, _time_1: f32, _box_out: &mut AABB) -> bool {
+        false
+    }
+
+    fn pdf_value(&self, _rng: &mut ThreadRng, _hittable_service: &HittableService, _origin: &Vec3, v: &Vec3) -> f32 {
+        let (u, v) = self.mapping.uv(v);
+        self.mapping.solid_angle_pdf(self.distribution.pdf(u, v), v)
+    }
+
+    fn random(&self, rng: &mut ThreadRng, _hittable_service: &HittableService, _origin: &Vec3) -> Vec3 {
+        self.sample_direction(rng.gen::<f32>(), rng.gen::<f32>())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f32::consts::PI;
+
+    use super::*;
+
+    fn pdf(light: &EnvironmentLight, direction: &Vec3) -> f32 {
+        light.pdf_value(&mut rand::thread_rng(), &HittableService::new(), &Vec3::zero(), direction)
+    }
+
+    #[test]
+    fn pdf_integrates_to_one_over_the_sphere() {
+        for rotation in [0.0, 30.0] {
+            let light = EnvironmentLight::new(&EnvironmentMap::new("sunset.pfm").with_rotation(rotation));
+
+            let (theta_steps, phi_steps) = (512, 1024);
+            let (theta_step, phi_step) = (PI / theta_steps as f32, 2.0 * PI / phi_steps as f32);
+            let mut integral: f64 = 0.0;
+            for theta_index in 0..theta_steps {
+                let theta = (theta_index as f32 + 0.5) * theta_step;
+                for phi_index in 0..phi_steps {
+                    let phi = (phi_index as f32 + 0.5) * phi_step;
+                    let direction = Vec3::new(theta.sin() * phi.cos(), theta.cos(), theta.sin() * phi.sin());
+                    integral += (pdf(&light, &direction) * theta.sin() * theta_step * phi_step) as f64;
+                }
+            }
+
+            assert!((integral - 1.0).abs() < 0.01, "pdf integrates to {} at rotation {}", integral, rotation);
+        }
+    }
+
+    #[test]
+    fn sampled_directions_have_a_positive_pdf() {
+        for rotation in [0.0, 30.0] {
+            let light = EnvironmentLight::new(&EnvironmentMap::new("sunset.pfm").with_rotation(rotation));
+
+            // Including the corners of the sample space, which land on the poles and the seam
+            let extremes = [0.0, 0.5, 1.0 - f32::EPSILON];
+            for u0 in extremes {
+                for u1 in extremes {
+                    let direction = light.sample_direction(u0, u1);
+                    let value = pdf(&light, &direction);
+                    assert!(0.0 < value && value.is_finite(), "pdf {} for sample {} {} at rotation {}", value, u0, u1, rotation);
+                }
+            }
+
+            let mut rng = rand::thread_rng();
+            let hittable_service = HittableService::new();
+            for _ in 0..100_000 {
+                let direction = light.random(&mut rng, &hittable_service, &Vec3::zero());
+                let value = pdf(&light, &direction);
+                assert!(0.0 < value && value.is_finite(), "pdf {} for {:?} at rotation {}", value, direction, rotation);
+            }
+        }
+    }
+}
@@ -1,6 +1,6 @@
 use rand::rngs::ThreadRng;
 
-use crate::{services::hittable_service::HittableService, core::ray::Ray, geometry::aabb::AABB};
+use crate::{services::{hittable_service::HittableService, material_service::MaterialService, texture_service::TextureService}, core::ray::Ray, geometry::aabb::AABB};
 
 use super::{hittable::Hittable, hit_record::HitRecord};
 
@@ -32,4 +32,33 @@ impl Hittable for FlipFace {
     fn bounding_box(&self, hittable_service: &HittableService, time_0: f32, time_1: f32, box_out: &mut AABB) -> bool {
         hittable_service.bounding_box(self.model_index, time_0, time_1, box_out)
     }
-}
\ No newline at end of file
+
+    fn emitted_power(&self, hittable_service: &HittableService, material_service: &MaterialService, texture_service: &TextureService, flipped: bool) -> f32 {
+        hittable_service.emitted_power(self.model_index, material_service, texture_service, !flipped)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{hittables::{hittable_enum::HittableEnum, xz_rect::XZRect}, materials::{diffuse_light::DiffuseLight, material_enum::MaterialEnum}, textures::{solid_color::SolidColor, texture_enum::TextureEnum}, core::color_rgb::ColorRGB};
+
+    use super::*;
+
+    #[test]
+    fn flipped_lights_get_the_same_weight() {
+        let mut texture_service = TextureService::new();
+        let emission_index = texture_service.add_texture(TextureEnum::SolidColor(SolidColor::from_color(&ColorRGB::new(4.0, 4.0, 4.0))));
+        let mut material_service = MaterialService::new();
+        let light_material_index = material_service.add_material(MaterialEnum::DiffuseLight(DiffuseLight::new(emission_index)));
+
+        let mut hittable_service = HittableService::new();
+        let rect_index = hittable_service.add_hittable(HittableEnum::XZRect(XZRect::new(-1.0, 1.0, -1.0, 1.0, 2.0, light_material_index)));
+        let flipped_index = hittable_service.add_hittable(HittableEnum::FlipFace(FlipFace::new(rect_index)));
+
+        let power = hittable_service.emitted_power(rect_index, &material_service, &texture_service, false);
+        let flipped_power = hittable_service.emitted_power(flipped_index, &material_service, &texture_service, false);
+
+        assert!(0.0 < power);
+        assert_eq!(power, flipped_power);
+    }
+}
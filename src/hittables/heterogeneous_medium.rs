@@ -0,0 +1,83 @@
+use ultraviolet::Vec3;
+use rand::{rngs::ThreadRng, Rng};
+
+use crate::{services::hittable_service::HittableService, core::ray::Ray, geometry::aabb::AABB, textures::volume_grid::VolumeGrid};
+
+use super::{hittable::Hittable, hit_record::HitRecord};
+
+// A medium whose density varies according to a volume grid, scaled by density_scale.
+// The grid lives in the same space as the boundary. Distances are sampled with delta tracking,
+// which proposes collisions against the maximum density and accepts them with
+// probability density / maximum density, the rest are null collisions the ray just passes through.
+pub struct HeterogeneousMedium {
+    boundary_index: usize,
+    phase_function: usize,
+    density: VolumeGrid,
+    density_scale: f32,
+    max_density: f32,
+}
+
+impl HeterogeneousMedium {
+    pub fn new(boundary_index: usize, phase_function: usize, density: VolumeGrid, density_scale: f32) -> HeterogeneousMedium {
+        let max_density = density.max_value() * density_scale;
+        HeterogeneousMedium { boundary_index, phase_function, density, density_scale, max_density }
+    }
+}
+
+impl Hittable for HeterogeneousMedium {
+    fn hit(&self, rng: &mut ThreadRng, hittable_service: &HittableService, ray: &Ray, t_min: f32, t_max: f32, hit_out: &mut HitRecord) -> bool {
+        if self.max_density <= 0.0 {
+            return false;
+        }
+
+        let mut hit_1 = HitRecord::default();
+        if !hittable_service.hit(self.boundary_index, rng, ray, f32::NEG_INFINITY, f32::INFINITY, &mut hit_1) {
+            return false;
+        }
+
+        let mut hit_2 = HitRecord::default();
+        if !hittable_service.hit(self.boundary_index, rng, ray, hit_1.t+0.0001, f32::INFINITY, &mut hit_2) {
+            return false;
+        }
+
+        if hit_1.t < t_min { hit_1.t = t_min; };
+        if t_max < hit_2.t { hit_2.t = t_max; };
+
+        if hit_2.t <= hit_1.t { return false; }
+
+        if hit_1.t < 0.0 { hit_1.t = 0.0; }
+
+        let ray_length = ray.direction.mag();
+        let mut t = hit_1.t;
+        loop {
+            t -= (1.0 - rng.gen::<f32>()).ln() / (self.max_density * ray_length);
+            if hit_2.t <= t {
+                return false;
+            }
+
+            let position = ray.at(t);
+            if rng.gen::<f32>() * self.max_density < self.density.lookup(&position) * self.density_scale {
+                hit_out.t = t;
+                hit_out.u = 0.0;
+                hit_out.v = 0.0;
+                hit_out.position = position;
+                hit_out.normal = Vec3::new(1.0, 0.0, 0.0 );
+                hit_out.geometric_normal = hit_out.normal;
+                hit_out.set_tangents(&Vec3::zero(), &Vec3::zero());
+                hit_out.uv_scale = (0.0, 0.0);
+                hit_out.is_front_face = true;
+                hit_out.material = self.phase_function;
+
+                return true;
+            }
+        }
+    }
+
+    fn bounding_box(&self, hittable_service: &HittableService, time_0: f32, time_1: f32, box_out: &mut AABB) -> bool {
+        hittable_service.bounding_box(self.boundary_index, time_0, time_1, box_out)
+    }
+
+    fn pdf_value(&self, _rng: &mut ThreadRng, _hittable_service: &HittableService, _origin: &Vec3, _v: &Vec3) -> f32 { 0.0 }
+
+    fn random(&self, _rng: &mut ThreadRng, _hittable_service: &HittableService, _origin: &Vec3) -> Vec3 { Vec3::new(1.0, 0.0, 0.0) }
+}
\ No newline at end of file
@@ -1,20 +1,41 @@
 use ultraviolet::Vec3;
 
-use crate::{core::ray::Ray};
+use crate::{core::ray::Ray, math::ortho_normal_base::OrthoNormalBase};
 
+const MIN_FOOTPRINT_COSINE: f32 = 0.05;
+
+#[derive(Clone, Copy)]
 pub struct HitRecord {
     pub t: f32,
     pub u: f32,
     pub v: f32,
     pub position: Vec3,
-    pub normal: Vec3,
+    pub normal: Vec3, // Shading normal, can be perturbed by materials
+    pub geometric_normal: Vec3,
+    pub tangent: Vec3, // Direction of increasing u, zero if the hittable has no uv parameterization
+    pub bitangent: Vec3, // Direction of increasing v
+    pub uv_scale: (f32, f32), // World distance covered by one unit of u and of v, zero if the hittable has no uv parameterization
+    pub footprint: (f32, f32), // Extent of the pixel footprint along the tangent and the bitangent, set by the integrator for texture filtering
     pub is_front_face: bool,
     pub material: usize,
 }
 
 impl HitRecord{
     pub fn default() -> Self {
-        HitRecord { t: 0.0, u: 0.0, v: 0.0, position: Vec3::zero(), normal: Vec3::zero(), is_front_face: false, material: 0 }
+        HitRecord { 
+            t: 0.0, 
+            u: 0.0, 
+            v: 0.0, 
+            position: Vec3::zero(), 
+            normal: Vec3::zero(), 
+            geometric_normal: Vec3::zero(), 
+            tangent: Vec3::zero(), 
+            bitangent: Vec3::zero(), 
+            uv_scale: (0.0, 0.0),
+            footprint: (0.0, 0.0),
+            is_front_face: false, 
+            material: 0 
+        }
     }
 
     pub fn new(
@@ -26,7 +47,7 @@ impl HitRecord{
         normal: &Vec3,
         material: usize
     ) -> Self {
-        let mut result = HitRecord{ t, u, v, position: *position, normal: *normal, is_front_face: false, material };
+        let mut result = HitRecord{ t, u, v, position: *position, normal: *normal, geometric_normal: *normal, tangent: Vec3::zero(), bitangent: Vec3::zero(), uv_scale: (0.0, 0.0), footprint: (0.0, 0.0), is_front_face: false, material };
         result.set_face_normal(ray, normal);
         result
     }
@@ -44,5 +65,84 @@ impl HitRecord{
             self.normal.y = -outward_normal.y;
             self.normal.z = -outward_normal.z;
         }
+
+        self.geometric_normal = self.normal;
+    }
+
+    #[inline]
+    pub fn set_tangents(&mut self, tangent: &Vec3, bitangent: &Vec3) {
+        self.tangent = *tangent;
+        self.bitangent = *bitangent;
+    }
+
+    // The ray cone widens with distance and lands on the surface as an ellipse,
+    // stretched by 1 / cos along the direction the ray travels over the surface
+    pub fn set_footprint(&mut self, ray: &Ray) {
+        let width = ray.cone_width_at(self.t);
+        let direction = ray.direction.normalized();
+        let cos_theta = direction.dot(self.geometric_normal).abs().max(MIN_FOOTPRINT_COSINE);
+
+        let along_surface = direction - self.geometric_normal * direction.dot(self.geometric_normal);
+        if self.tangent.mag_sq() < 1.0e-8 || along_surface.mag_sq() < 1.0e-8 {
+            self.footprint = (width / cos_theta, width / cos_theta);
+            return;
+        }
+        let along_surface = along_surface.normalized();
+
+        let extent = |axis: Vec3| {
+            let alignment = along_surface.dot(axis.normalized()).clamp(-1.0, 1.0);
+            width * ((alignment / cos_theta).powi(2) + 1.0 - alignment * alignment).sqrt()
+        };
+        self.footprint = (extent(self.tangent), extent(self.bitangent));
+    }
+
+    // Orthonormal tangent and bitangent around the shading normal.
+    // Falls back to an arbitrary frame if the hittable did not supply tangents.
+    pub fn tangent_frame(&self) -> (Vec3, Vec3) {
+        let tangent = self.tangent - self.normal * self.normal.dot(self.tangent);
+        if tangent.mag_sq() < 1.0e-8 {
+            let uvw = OrthoNormalBase::build_from_w(&self.normal);
+            return (uvw.u, uvw.v);
+        }
+        let tangent = tangent.normalized();
+
+        let bitangent = self.bitangent - self.normal * self.normal.dot(self.bitangent) - tangent * tangent.dot(self.bitangent);
+        if bitangent.mag_sq() < 1.0e-8 {
+            return (tangent, self.normal.cross(tangent));
+        }
+
+        (tangent, bitangent.normalized())
+    }
+
+    // Replaces the shading normal. A normal facing away from the viewer would turn the surface black,
+    // so it gets bent back until it is just barely visible.
+    pub fn set_shading_normal(&mut self, ray: &Ray, normal: &Vec3) {
+        let outgoing = -ray.direction.normalized();
+        let mut normal = normal.normalized();
+
+        let visibility = normal.dot(outgoing);
+        if visibility < 0.01 {
+            normal = (normal + outgoing * (0.01 - visibility)).normalized();
+        }
+
+        self.normal = normal;
+    }
+
+    // A direction has to be on the same side of the shading and the geometric normal,
+    // otherwise perturbed normals leak light through the surface
+    #[inline]
+    pub fn is_consistent(&self, direction: &Vec3) -> bool {
+        0.0 <= direction.dot(self.normal) * direction.dot(self.geometric_normal)
+    }
+
+    // Mirrors an inconsistent direction across the geometric surface,
+    // for specular rays which have no other way of dropping out
+    #[inline]
+    pub fn make_consistent(&self, direction: &Vec3) -> Vec3 {
+        if self.is_consistent(direction) {
+            return *direction;
+        }
+
+        *direction - self.geometric_normal * (2.0 * direction.dot(self.geometric_normal))
     }
 }
\ No newline at end of file
@@ -3,7 +3,10 @@ use rand::rngs::ThreadRng;
 
 use crate::geometry::aabb::AABB;
 use crate::core::ray::Ray;
+use crate::core::color_rgb::ColorRGB;
 use crate::services::hittable_service::{HittableService};
+use crate::services::material_service::MaterialService;
+use crate::services::texture_service::TextureService;
 
 use super::hit_record::HitRecord;
 
@@ -13,4 +16,22 @@ pub trait Hittable: Sync + Send {
     fn bounding_box(&self, _hittable_service: &HittableService, time_0: f32, time_1: f32, box_out: &mut AABB) -> bool;
     fn pdf_value(&self, _rng: &mut ThreadRng, _hittable_service: &HittableService, _origin: &Vec3,_vv: &Vec3) -> f32 { 0.0 }
     fn random(&self, _rng: &mut ThreadRng, _hittable_service: &HittableService, _origin: &Vec3) -> Vec3 { Vec3::new(1.0, 0.0, 0.0) }
+    // Rough estimate of the emitted power, only used to decide how often a light gets sampled. 0 if unknown.
+    // flipped is set under a FlipFace, where the back of the surface is the side lit up.
+    fn emitted_power(&self, _hittable_service: &HittableService, _material_service: &MaterialService, _texture_service: &TextureService, _flipped: bool) -> f32 { 0.0 }
+}
+
+// Power of a Lambertian emitter of the given area, using the radiance seen head on from the front
+// at one representative point, sampled in the middle of the uv domain. When flipped the probe comes
+// from behind the outward normal and gets its facing swapped, just like FlipFace does to real hits
+pub fn diffuse_emitted_power(material_service: &MaterialService, texture_service: &TextureService, position: &Vec3, normal: &Vec3, flipped: bool, material: usize, area: f32) -> f32 {
+    let side = if flipped { -*normal } else { *normal };
+    let ray = Ray::new(*position + side, -side, 0.0);
+    let mut hit = HitRecord::new(&ray, 1.0, 0.5, 0.5, position, normal, material);
+    hit.is_front_face ^= flipped;
+
+    let mut emitted = ColorRGB::black();
+    material_service.emitted(texture_service, &ray, &hit, &mut emitted);
+
+    std::f32::consts::PI * area * emitted.luminance().max(0.0)
 }
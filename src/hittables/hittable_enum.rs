@@ -1,9 +1,9 @@
 use ultraviolet::Vec3;
 use rand::rngs::ThreadRng;
 
-use crate::{services::hittable_service::HittableService, core::ray::Ray, geometry::aabb::AABB};
+use crate::{services::{hittable_service::HittableService, material_service::MaterialService, texture_service::TextureService}, core::ray::Ray, geometry::aabb::AABB};
 
-use super::{default_hittable::DefaultHittable, bvh_node::BVHNode, sphere::Sphere, moving_sphere::MovingSphere, hittable_list::HittableList, xy_rect::XYRect, xz_rect::XZRect, yz_rect::YZRect, box_hittable::BoxHittable, rotate_y::RotateY, translate::Translate, constant_medium::ConstantMedium, flip_face::FlipFace, hittable::Hittable, hit_record::HitRecord};
+use super::{default_hittable::DefaultHittable, bvh_node::BVHNode, sphere::Sphere, moving_sphere::MovingSphere, hittable_list::HittableList, xy_rect::XYRect, xz_rect::XZRect, yz_rect::YZRect, box_hittable::BoxHittable, rotate_y::RotateY, translate::Translate, constant_medium::ConstantMedium, heterogeneous_medium::HeterogeneousMedium, environment_light::EnvironmentLight, flip_face::FlipFace, hittable::Hittable, hit_record::HitRecord};
 
 pub enum HittableEnum {
     DefaultHittable(DefaultHittable),
@@ -18,6 +18,8 @@ pub enum HittableEnum {
     RotateY(RotateY),
     Translate(Translate),
     ConstantMedium(ConstantMedium),
+    HeterogeneousMedium(HeterogeneousMedium),
+    EnvironmentLight(EnvironmentLight),
     FlipFace(FlipFace),
 }
 
@@ -38,6 +40,8 @@ impl HittableEnum {
             HittableEnum::RotateY(rotate_y) => rotate_y.hit(rng, hittable_service, ray, t_min, t_max, hit_out),
             HittableEnum::Translate(translate) => translate.hit(rng, hittable_service, ray, t_min, t_max, hit_out),
             HittableEnum::ConstantMedium(constant_medium) => constant_medium.hit(rng, hittable_service, ray, t_min, t_max, hit_out),
+            HittableEnum::HeterogeneousMedium(heterogeneous_medium) => heterogeneous_medium.hit(rng, hittable_service, ray, t_min, t_max, hit_out),
+            HittableEnum::EnvironmentLight(environment_light) => environment_light.hit(rng, hittable_service, ray, t_min, t_max, hit_out),
             HittableEnum::FlipFace(flip_face) => flip_face.hit(rng, hittable_service, ray, t_min, t_max, hit_out),
         }
     }
@@ -57,6 +61,8 @@ impl HittableEnum {
             HittableEnum::RotateY(rotate_y) => rotate_y.bounding_box(hittable_service, time_0, time_1, box_out),
             HittableEnum::Translate(translate) => translate.bounding_box(hittable_service, time_0, time_1, box_out),
             HittableEnum::ConstantMedium(constant_medium) => constant_medium.bounding_box(hittable_service, time_0, time_1, box_out),
+            HittableEnum::HeterogeneousMedium(heterogeneous_medium) => heterogeneous_medium.bounding_box(hittable_service, time_0, time_1, box_out),
+            HittableEnum::EnvironmentLight(environment_light) => environment_light.bounding_box(hittable_service, time_0, time_1, box_out),
             HittableEnum::FlipFace(flip_face) => flip_face.bounding_box(hittable_service, time_0, time_1, box_out),
         }
     }
@@ -76,6 +82,8 @@ impl HittableEnum {
             HittableEnum::RotateY(rotate_y) => rotate_y.pdf_value(rng, hittable_service, origin, vv),
             HittableEnum::Translate(translate) => translate.pdf_value(rng, hittable_service, origin, vv),
             HittableEnum::ConstantMedium(constant_medium) => constant_medium.pdf_value(rng, hittable_service, origin, vv),
+            HittableEnum::HeterogeneousMedium(heterogeneous_medium) => heterogeneous_medium.pdf_value(rng, hittable_service, origin, vv),
+            HittableEnum::EnvironmentLight(environment_light) => environment_light.pdf_value(rng, hittable_service, origin, vv),
             HittableEnum::FlipFace(flip_face) => flip_face.pdf_value(rng, hittable_service, origin, vv),
         }
     }
@@ -95,8 +103,30 @@ impl HittableEnum {
             HittableEnum::RotateY(rotate_y) => rotate_y.random(rng, hittable_service, origin),
             HittableEnum::Translate(translate) => translate.random(rng, hittable_service, origin),
             HittableEnum::ConstantMedium(constant_medium) => constant_medium.random(rng, hittable_service, origin),
+            HittableEnum::HeterogeneousMedium(heterogeneous_medium) => heterogeneous_medium.random(rng, hittable_service, origin),
+            HittableEnum::EnvironmentLight(environment_light) => environment_light.random(rng, hittable_service, origin),
             HittableEnum::FlipFace(flip_face) => flip_face.random(rng, hittable_service, origin),
         }
     }
 
+    pub fn emitted_power(&self, hittable_service: &HittableService, material_service: &MaterialService, texture_service: &TextureService, flipped: bool) -> f32 {
+        match &self {
+            HittableEnum::DefaultHittable(default) => default.emitted_power(hittable_service, material_service, texture_service, flipped),
+            HittableEnum::BVHNode(bvh_node) => bvh_node.emitted_power(hittable_service, material_service, texture_service, flipped),
+            HittableEnum::Sphere(sphere) => sphere.emitted_power(hittable_service, material_service, texture_service, flipped),
+            HittableEnum::MovingSphere(moving_sphere) => moving_sphere.emitted_power(hittable_service, material_service, texture_service, flipped),
+            HittableEnum::HittableList(hittable_list) => hittable_list.emitted_power(hittable_service, material_service, texture_service, flipped),
+            HittableEnum::XYRect(xy_rect) => xy_rect.emitted_power(hittable_service, material_service, texture_service, flipped),
+            HittableEnum::XZRect(xz_rect) => xz_rect.emitted_power(hittable_service, material_service, texture_service, flipped),
+            HittableEnum::YZRect(yz_rect) => yz_rect.emitted_power(hittable_service, material_service, texture_service, flipped),
+            HittableEnum::BoxHittable(box_hittable) => box_hittable.emitted_power(hittable_service, material_service, texture_service, flipped),
+            HittableEnum::RotateY(rotate_y) => rotate_y.emitted_power(hittable_service, material_service, texture_service, flipped),
+            HittableEnum::Translate(translate) => translate.emitted_power(hittable_service, material_service, texture_service, flipped),
+            HittableEnum::ConstantMedium(constant_medium) => constant_medium.emitted_power(hittable_service, material_service, texture_service, flipped),
+            HittableEnum::HeterogeneousMedium(heterogeneous_medium) => heterogeneous_medium.emitted_power(hittable_service, material_service, texture_service, flipped),
+            HittableEnum::EnvironmentLight(environment_light) => environment_light.emitted_power(hittable_service, material_service, texture_service, flipped),
+            HittableEnum::FlipFace(flip_face) => flip_face.emitted_power(hittable_service, material_service, texture_service, flipped),
+        }
+    }
+
 }
\ No newline at end of file
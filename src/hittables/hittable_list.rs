@@ -1,7 +1,7 @@
 use ultraviolet::Vec3;
 use rand::{rngs::ThreadRng, Rng};
 
-use crate::{services::hittable_service::HittableService, core::ray::Ray, geometry::aabb::AABB};
+use crate::{services::{hittable_service::HittableService, material_service::MaterialService, texture_service::TextureService}, core::ray::Ray, geometry::aabb::AABB, math::alias_table::AliasTable};
 
 use super::{hittable::Hittable, hit_record::HitRecord};
 
@@ -9,11 +9,29 @@ use super::{hittable::Hittable, hit_record::HitRecord};
 #[derive(Default)]
 pub struct HittableList {
     hittable_indices: Vec<usize>,
+    selection: Option<AliasTable>, // When sampling, pick members proportionally to these weights instead of uniformly
 }
 
 impl HittableList {
     pub fn from_list(hittable_indices: Vec<usize>) -> HittableList {
-        HittableList { hittable_indices }
+        HittableList { hittable_indices, selection: None }
+    }
+
+    // Used for the list of lights, so a few bright lights aren't drowned out by many dim ones.
+    // There should be one weight, such as the emitted power, per member.
+    pub fn with_selection_weights(mut self, weights: &[f32]) -> HittableList {
+        assert_eq!(weights.len(), self.hittable_indices.len());
+        self.selection = Some(AliasTable::new(weights));
+        self
+    }
+
+    // Probability of the member at the given position being picked by random()
+    #[inline]
+    pub fn selection_pmf(&self, member: usize) -> f32 {
+        match &self.selection {
+            Some(selection) => selection.pmf(member),
+            None => 1.0 / self.hittable_indices.len() as f32,
+        }
     }
 }
 
@@ -32,6 +50,10 @@ impl Hittable for HittableList {
                 hit_out.v = temp_record.v;
                 hit_out.position = temp_record.position;
                 hit_out.normal = temp_record.normal;
+                hit_out.geometric_normal = temp_record.geometric_normal;
+                hit_out.tangent = temp_record.tangent;
+                hit_out.bitangent = temp_record.bitangent;
+                hit_out.uv_scale = temp_record.uv_scale;
                 hit_out.is_front_face = temp_record.is_front_face;
                 hit_out.material = temp_record.material;
             }
@@ -71,16 +93,26 @@ impl Hittable for HittableList {
     fn pdf_value(&self, rng: &mut ThreadRng, hittable_service: &HittableService, origin: &Vec3, v: &Vec3) -> f32 {
         let mut sum = 0.0;
 
-        for object_index in 0..self.hittable_indices.len(){
-            sum += hittable_service.pdf_value(self.hittable_indices[object_index], rng, origin, v);
+        for (member, hittable_index) in self.hittable_indices.iter().enumerate() {
+            let pmf = self.selection_pmf(member);
+            if 0.0 < pmf {
+                sum += pmf * hittable_service.pdf_value(*hittable_index, rng, origin, v);
+            }
         }
 
-        sum / self.hittable_indices.len() as f32
+        sum
     }
 
     fn random(&self, rng: &mut ThreadRng, hittable_service: &HittableService, origin: &Vec3) -> Vec3 {
-        let random_object_index = rng.gen_range(0..self.hittable_indices.len());
+        let random_object_index = match &self.selection {
+            Some(selection) => selection.sample(rng.gen::<f32>()),
+            None => rng.gen_range(0..self.hittable_indices.len()),
+        };
         hittable_service.random(self.hittable_indices[random_object_index], rng, origin) / self.hittable_indices.len() as f32
     }
 
+    fn emitted_power(&self, hittable_service: &HittableService, material_service: &MaterialService, texture_service: &TextureService, flipped: bool) -> f32 {
+        self.hittable_indices.iter().map(|hittable_index| hittable_service.emitted_power(*hittable_index, material_service, texture_service, flipped)).sum()
+    }
+
 }
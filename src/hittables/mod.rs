@@ -12,5 +12,7 @@ pub mod box_hittable;
 pub mod translate;
 pub mod rotate_y;
 pub mod constant_medium;
+pub mod heterogeneous_medium;
+pub mod environment_light;
 pub mod flip_face;
 pub mod hittable_enum;
\ No newline at end of file
@@ -3,9 +3,9 @@ use std::f32::consts::PI;
 use ultraviolet::Vec3;
 use rand::rngs::ThreadRng;
 
-use crate::{core::ray::Ray, services::hittable_service::HittableService, geometry::aabb::AABB};
+use crate::{core::ray::Ray, services::{hittable_service::HittableService, material_service::MaterialService, texture_service::TextureService}, geometry::aabb::AABB};
 
-use super::{hittable::{Hittable}, hit_record::HitRecord};
+use super::{hittable::{Hittable, diffuse_emitted_power}, hit_record::HitRecord, sphere::{sphere_tangent, sphere_uv_scale}};
 
 pub struct MovingSphere {
     pub radius: f32,
@@ -73,6 +73,12 @@ impl Hittable for MovingSphere{
         hit_out.v = v;
         hit_out.position = position;
         hit_out.set_face_normal(ray, &normal);
+        let tangent = sphere_tangent(&normal);
+        let bitangent = normal.cross(tangent);
+        // Hitting the inside flips the normal, flip the tangent too to keep the frame right handed
+        let tangent = if hit_out.is_front_face { tangent } else { -tangent };
+        hit_out.set_tangents(&tangent, &bitangent);
+        hit_out.uv_scale = sphere_uv_scale(self.radius);
         hit_out.material = self.material;
 
         true
@@ -105,4 +111,12 @@ impl Hittable for MovingSphere{
     fn random(&self, _rng: &mut ThreadRng, _hittable_service: &HittableService, _origin: &Vec3) -> Vec3 {
         Vec3::new(1.0, 0.0, 0.0)
     }
+
+    fn emitted_power(&self, _hittable_service: &HittableService, material_service: &MaterialService, texture_service: &TextureService, flipped: bool) -> f32 {
+        let normal = Vec3::new(0.0, 1.0, 0.0);
+        let area = 4.0 * PI * self.radius * self.radius;
+        let center = self.center(0.5 * (self.time_0 + self.time_1));
+
+        diffuse_emitted_power(material_service, texture_service, &(center + self.radius * normal), &normal, flipped, self.material, area)
+    }
 }
\ No newline at end of file
@@ -1,7 +1,7 @@
 use ultraviolet::Vec3;
 use rand::rngs::ThreadRng;
 
-use crate::{geometry::aabb::AABB, services::hittable_service::HittableService, core::ray::Ray};
+use crate::{geometry::aabb::AABB, services::{hittable_service::HittableService, material_service::MaterialService, texture_service::TextureService}, core::ray::Ray};
 
 use super::{hittable::Hittable, hit_record::HitRecord};
 
@@ -69,7 +69,7 @@ impl Hittable for RotateY {
         direction[0] = self.cos_theta * ray.direction[0] - self.sin_theta * ray.direction[2];
         direction[2] = self.sin_theta * ray.direction[0] + self.cos_theta * ray.direction[2];
 
-        let rotated_ray = Ray{ origin, direction, time: ray.time };
+        let rotated_ray = Ray{ origin, direction, ..*ray };
 
         if !hittable_service.hit(self.model_index, rng, &rotated_ray, t_min, t_max, hit_out) {
             return false;
@@ -90,6 +90,17 @@ impl Hittable for RotateY {
         // impossible the immutable borrow for outward_normal
         hit_out.set_face_normal(&rotated_ray, &normal);
 
+        let mut tangent = hit_out.tangent;
+        let mut bitangent = hit_out.bitangent;
+
+        tangent[0] = self.cos_theta * hit_out.tangent[0] + self.sin_theta * hit_out.tangent[2];
+        tangent[2] = -self.sin_theta * hit_out.tangent[0] + self.cos_theta * hit_out.tangent[2];
+
+        bitangent[0] = self.cos_theta * hit_out.bitangent[0] + self.sin_theta * hit_out.bitangent[2];
+        bitangent[2] = -self.sin_theta * hit_out.bitangent[0] + self.cos_theta * hit_out.bitangent[2];
+
+        hit_out.set_tangents(&tangent, &bitangent);
+
 
         true
     }
@@ -108,4 +119,8 @@ impl Hittable for RotateY {
         true
     }
 
+    fn emitted_power(&self, hittable_service: &HittableService, material_service: &MaterialService, texture_service: &TextureService, flipped: bool) -> f32 {
+        hittable_service.emitted_power(self.model_index, material_service, texture_service, flipped)
+    }
+
 }
@@ -8,9 +8,11 @@ use crate::math::ortho_normal_base::OrthoNormalBase;
 use crate::{geometry::aabb::AABB};
 use crate::core::ray::Ray;
 use crate::services::hittable_service::HittableService;
+use crate::services::material_service::MaterialService;
+use crate::services::texture_service::TextureService;
 
 use super::hit_record::HitRecord;
-use super::hittable::{Hittable};
+use super::hittable::{Hittable, diffuse_emitted_power};
 
 pub struct Sphere {
     pub radius: f32,
@@ -61,6 +63,12 @@ impl Hittable for Sphere{
         hit_out.v = v;
         hit_out.position = position;
         hit_out.set_face_normal(ray, &normal);
+        let tangent = sphere_tangent(&normal);
+        let bitangent = normal.cross(tangent);
+        // Hitting the inside flips the normal, flip the tangent too to keep the frame right handed
+        let tangent = if hit_out.is_front_face { tangent } else { -tangent };
+        hit_out.set_tangents(&tangent, &bitangent);
+        hit_out.uv_scale = sphere_uv_scale(self.radius);
         hit_out.material = self.material;
 
         true
@@ -98,7 +106,31 @@ impl Hittable for Sphere{
         uvw.local_vector(&random_to_sphere(rng, self.radius, distance_squared))
     }
 
+    fn emitted_power(&self, _hittable_service: &HittableService, material_service: &MaterialService, texture_service: &TextureService, flipped: bool) -> f32 {
+        let normal = Vec3::new(0.0, 1.0, 0.0);
+        let area = 4.0 * PI * self.radius * self.radius;
 
+        diffuse_emitted_power(material_service, texture_service, &(self.center + self.radius * normal), &normal, flipped, self.material, area)
+    }
+
+
+}
+
+// u runs around the equator and v over a meridian
+#[inline]
+pub fn sphere_uv_scale(radius: f32) -> (f32, f32) {
+    (2.0 * PI * radius, PI * radius)
+}
+
+// Direction of increasing u around the y axis. It is undefined at the poles, where any horizontal direction will do.
+#[inline]
+pub fn sphere_tangent(outward_normal: &Vec3) -> Vec3 {
+    let tangent = Vec3::new(outward_normal.z, 0.0, -outward_normal.x);
+    if tangent.mag_sq() < 1.0e-12 {
+        return Vec3::new(1.0, 0.0, 0.0);
+    }
+
+    tangent.normalized()
 }
 
 #[inline]
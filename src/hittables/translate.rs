@@ -1,7 +1,7 @@
 use ultraviolet::Vec3;
 use rand::rngs::ThreadRng;
 
-use crate::{services::hittable_service::HittableService, core::ray::Ray, geometry::aabb::AABB};
+use crate::{services::{hittable_service::HittableService, material_service::MaterialService, texture_service::TextureService}, core::ray::Ray, geometry::aabb::AABB};
 
 use super::{hittable::Hittable, hit_record::HitRecord};
 
@@ -20,7 +20,7 @@ impl Translate {
 impl Hittable for Translate {
     #[inline]
     fn hit(&self, rng: &mut ThreadRng, hittable_service: &HittableService, ray: &Ray, t_min: f32, t_max: f32, hit_out: &mut HitRecord) -> bool {
-        let moved_ray = Ray{ origin: ray.origin - self.offset, direction: ray.direction, time: ray.time };
+        let moved_ray = Ray{ origin: ray.origin - self.offset, ..*ray };
         if !hittable_service.hit(self.model_index, rng, &moved_ray, t_min, t_max, hit_out) {
             return false;
         }
@@ -46,4 +46,8 @@ impl Hittable for Translate {
         true
     }
 
+    fn emitted_power(&self, hittable_service: &HittableService, material_service: &MaterialService, texture_service: &TextureService, flipped: bool) -> f32 {
+        hittable_service.emitted_power(self.model_index, material_service, texture_service, flipped)
+    }
+
 }
\ No newline at end of file
@@ -1,9 +1,9 @@
 use ultraviolet::Vec3;
 use rand::rngs::ThreadRng;
 
-use crate::{services::hittable_service::HittableService, core::ray::Ray, geometry::aabb::AABB};
+use crate::{services::{hittable_service::HittableService, material_service::MaterialService, texture_service::TextureService}, core::ray::Ray, geometry::aabb::AABB};
 
-use super::{hittable::Hittable, hit_record::HitRecord};
+use super::{hittable::{Hittable, diffuse_emitted_power}, hit_record::HitRecord};
 
 pub struct YZRect {
     material: usize,
@@ -45,6 +45,8 @@ impl Hittable for YZRect {
         hit_out.v = v;
         hit_out.position = ray.at(t);
         hit_out.set_face_normal(ray, &outward_normal);
+        hit_out.set_tangents(&Vec3::new(0.0, 1.0, 0.0), &Vec3::new(0.0, 0.0, 1.0));
+        hit_out.uv_scale = (self.y1 - self.y0, self.z1 - self.z0);
         hit_out.material = self.material;
 
         true
@@ -62,4 +64,11 @@ impl Hittable for YZRect {
         true
     }
 
+    fn emitted_power(&self, _hittable_service: &HittableService, material_service: &MaterialService, texture_service: &TextureService, flipped: bool) -> f32 {
+        let area = (self.y1 - self.y0) * (self.z1 - self.z0);
+        let center = Vec3::new(self.k, 0.5 * (self.y0 + self.y1), 0.5 * (self.z0 + self.z1));
+
+        diffuse_emitted_power(material_service, texture_service, &center, &Vec3::new(1.0, 0.0, 0.0), flipped, self.material, area)
+    }
+
 }
\ No newline at end of file
@@ -12,6 +12,7 @@ use crate::utility::render_config::RenderConfig;
 
 mod geometry;
 mod hittables;
+mod lights;
 mod materials;
 mod math;
 mod noise;
@@ -0,0 +1,46 @@
+use std::f32::consts::PI;
+
+use ultraviolet::Vec3;
+use rand::{rngs::ThreadRng, Rng};
+
+use crate::{core::color_rgb::ColorRGB, math::ortho_normal_base::OrthoNormalBase};
+
+use super::light::{Light, LightSample};
+
+// Light from infinitely far away travelling in direction, like the sun, giving irradiance
+// on a surface facing it. An angular diameter in degrees turns it into a disk in the sky,
+// sampled uniformly, which gives soft shadows. The sun is about half a degree across.
+pub struct DirectionalLight {
+    pub to_light: OrthoNormalBase,
+    pub irradiance: ColorRGB,
+    pub cos_half_angle: f32,
+}
+
+impl DirectionalLight {
+    pub fn new(direction: Vec3, irradiance: ColorRGB, angular_diameter: f32) -> DirectionalLight {
+        let half_angle = 0.5 * angular_diameter.clamp(0.0, 180.0);
+        DirectionalLight { 
+            to_light: OrthoNormalBase::build_from_w(&-direction.normalized()), 
+            irradiance, 
+            cos_half_angle: half_angle.to_radians().cos() 
+        }
+    }
+}
+
+impl Light for DirectionalLight {
+    fn sample(&self, rng: &mut ThreadRng, _point: &Vec3, sample_out: &mut LightSample) -> bool {
+        sample_out.direction = if self.cos_half_angle < 1.0 {
+            let cos_theta = 1.0 - rng.gen::<f32>() * (1.0 - self.cos_half_angle);
+            let sin_theta = (1.0 - cos_theta * cos_theta).max(0.0).sqrt();
+            let phi = 2.0 * PI * rng.gen::<f32>();
+            self.to_light.local_vector(&Vec3::new(sin_theta * phi.cos(), sin_theta * phi.sin(), cos_theta))
+        } else {
+            self.to_light.w
+        };
+
+        sample_out.distance = f32::INFINITY;
+        sample_out.radiance = self.irradiance;
+
+        true
+    }
+}
\ No newline at end of file
@@ -0,0 +1,301 @@
+use ultraviolet::Vec3;
+
+use crate::math::ortho_normal_base::OrthoNormalBase;
+
+// Angular intensity distribution of a light fixture, read from an IESNA LM-63 photometric file.
+// Only type C photometry is supported, which is what nearly all architectural fixtures use.
+// Vertical angles are measured from the nadir, the direction the fixture points in, horizontal angles
+// go around it, starting from the length of the fixture.
+#[derive(Clone)]
+pub struct IesProfile {
+    vertical_angles: Vec<f32>,
+    horizontal_angles: Vec<f32>,
+    candela: Vec<f32>, // One row of vertical angles per horizontal angle
+    max_candela: f32,
+}
+
+impl IesProfile {
+    pub fn from_file(path: &str) -> Result<Self, String> {
+        let text = std::fs::read_to_string(path).map_err(|error| format!("could not read IES file {}: {}", path, error))?;
+        Self::parse(&text, path)
+    }
+
+    pub fn parse(text: &str, path: &str) -> Result<Self, String> {
+        // Everything up to the TILT line is free form keywords
+        let mut lines = text.lines();
+        let tilt = lines
+            .by_ref()
+            .map(|line| line.trim())
+            .find(|line| line.starts_with("TILT="))
+            .ok_or_else(|| format!("{} has no TILT line, is it an IES file?", path))?;
+        if tilt != "TILT=NONE" && tilt != "TILT=INCLUDE" {
+            return Err(format!("{} references a separate tilt file, which isn't supported", path));
+        }
+
+        let mut tokens = lines
+            .flat_map(|line| line.split(|character: char| character.is_whitespace() || character == ','))
+            .filter(|token| !token.is_empty());
+        let mut next = || -> Result<f32, String> {
+            let token = tokens.next().ok_or_else(|| format!("{} ended early", path))?;
+            token.parse::<f32>().map_err(|_| format!("{} has a malformed number '{}'", path, token))
+        };
+
+        // Tilt data only matters for lamps which change output when tilted, skip it
+        if tilt == "TILT=INCLUDE" {
+            next()?;
+            let tilt_angle_count = next()? as usize;
+            for _ in 0..2 * tilt_angle_count {
+                next()?;
+            }
+        }
+
+        let _lamp_count = next()?;
+        let _lumens_per_lamp = next()?;
+        let candela_multiplier = next()?;
+        let vertical_count = next()? as usize;
+        let horizontal_count = next()? as usize;
+        let photometric_type = next()? as i32;
+        let _units_type = next()?;
+        let (_width, _length, _height) = (next()?, next()?, next()?);
+        let ballast_factor = next()?;
+        let ballast_lamp_factor = next()?;
+        let _input_watts = next()?;
+
+        if photometric_type != 1 {
+            return Err(format!("{} uses type {} photometry, only type C is supported", path, photometric_type));
+        }
+        if vertical_count == 0 || horizontal_count == 0 {
+            return Err(format!("{} has no angles", path));
+        }
+
+        let vertical_angles: Vec<f32> = (0..vertical_count).map(|_| next()).collect::<Result<_, _>>()?;
+        let horizontal_angles: Vec<f32> = (0..horizontal_count).map(|_| next()).collect::<Result<_, _>>()?;
+        let scale = candela_multiplier * ballast_factor * ballast_lamp_factor;
+        let candela: Vec<f32> = (0..vertical_count * horizontal_count).map(|_| next().map(|value| (value * scale).max(0.0))).collect::<Result<_, _>>()?;
+        let max_candela = candela.iter().copied().fold(0.0, f32::max);
+
+        Ok(IesProfile { vertical_angles, horizontal_angles, candela, max_candela })
+    }
+
+    #[inline]
+    pub fn max_candela(&self) -> f32 {
+        self.max_candela
+    }
+
+    // Intensity in candela at angles in degrees, interpolated bilinearly
+    pub fn candela(&self, vertical_angle: f32, horizontal_angle: f32) -> f32 {
+        let vertical_first = self.vertical_angles[0];
+        let vertical_last = self.vertical_angles[self.vertical_angles.len() - 1];
+        if vertical_angle < vertical_first || vertical_last < vertical_angle {
+            return 0.0;
+        }
+
+        let (vertical_index, vertical_t) = bracket(&self.vertical_angles, vertical_angle);
+        let row = |horizontal_index: usize| {
+            let offset = horizontal_index * self.vertical_angles.len();
+            let lower = self.candela[offset + vertical_index];
+            let upper = self.candela[offset + (vertical_index + 1).min(self.vertical_angles.len() - 1)];
+            lower + (upper - lower) * vertical_t
+        };
+
+        if self.horizontal_angles.len() == 1 {
+            return row(0);
+        }
+
+        // Fold the angle into the range covered by the file, using the symmetry its range implies
+        let horizontal_first = self.horizontal_angles[0];
+        let horizontal_last = self.horizontal_angles[self.horizontal_angles.len() - 1];
+        let mut horizontal_angle = horizontal_angle.rem_euclid(360.0);
+        if horizontal_first == 90.0 && horizontal_last == 270.0 {
+            if !(90.0..=270.0).contains(&horizontal_angle) {
+                horizontal_angle = (180.0 - horizontal_angle).rem_euclid(360.0);
+            }
+        } else if horizontal_last <= 90.0 {
+            if 180.0 < horizontal_angle { horizontal_angle = 360.0 - horizontal_angle; }
+            if 90.0 < horizontal_angle { horizontal_angle = 180.0 - horizontal_angle; }
+        } else if horizontal_last <= 180.0 && 180.0 < horizontal_angle {
+            horizontal_angle = 360.0 - horizontal_angle;
+        }
+
+        // Full circles which don't repeat the first angle at 360 wrap around from the last to the first
+        if horizontal_last < horizontal_angle {
+            let span = horizontal_first + 360.0 - horizontal_last;
+            let t = if 0.0 < span { (horizontal_angle - horizontal_last) / span } else { 0.0 };
+            let (lower, upper) = (row(self.horizontal_angles.len() - 1), row(0));
+            return lower + (upper - lower) * t.clamp(0.0, 1.0);
+        }
+
+        let (horizontal_index, horizontal_t) = bracket(&self.horizontal_angles, horizontal_angle);
+        let lower = row(horizontal_index);
+        let upper = row((horizontal_index + 1).min(self.horizontal_angles.len() - 1));
+        lower + (upper - lower) * horizontal_t
+    }
+
+    // Intensity in candela in a direction leaving the fixture, given as a frame whose w axis is
+    // the nadir of the fixture and whose u axis is the horizontal angle 0
+    pub fn candela_in_direction(&self, frame: &OrthoNormalBase, direction: &Vec3) -> f32 {
+        let direction = direction.normalized();
+        let vertical_angle = direction.dot(frame.w).clamp(-1.0, 1.0).acos().to_degrees();
+        let horizontal_angle = f32::atan2(direction.dot(frame.v), direction.dot(frame.u)).to_degrees();
+
+        self.candela(vertical_angle, horizontal_angle)
+    }
+
+    // Intensity relative to the brightest direction, for scaling lights given a peak intensity
+    #[inline]
+    pub fn relative_intensity(&self, frame: &OrthoNormalBase, direction: &Vec3) -> f32 {
+        if self.max_candela <= 0.0 {
+            return 0.0;
+        }
+
+        self.candela_in_direction(frame, direction) / self.max_candela
+    }
+}
+
+// Index of the last angle at or below the given one, and how far it is towards the next
+#[inline]
+fn bracket(angles: &[f32], angle: f32) -> (usize, f32) {
+    let index = angles.partition_point(|value| *value <= angle).clamp(1, angles.len()) - 1;
+    if angles.len() <= index + 1 {
+        return (index, 0.0);
+    }
+
+    let width = angles[index + 1] - angles[index];
+    let t = if 0.0 < width { (angle - angles[index]) / width } else { 0.0 };
+    (index, t.clamp(0.0, 1.0))
+}
+
+// Frame for looking up a profile, for a fixture pointing along nadir, with its length along the given direction.
+// The length only matters for profiles which aren't symmetric around the nadir.
+pub fn fixture_frame(nadir: &Vec3, length: &Vec3) -> OrthoNormalBase {
+    let w = nadir.normalized();
+    let u = *length - w * length.dot(w);
+    if u.mag_sq() <= 1e-8 {
+        return OrthoNormalBase::build_from_w(&w);
+    }
+
+    let u = u.normalized();
+    OrthoNormalBase { u, v: w.cross(u), w }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(actual: f32, expected: f32) {
+        assert!((actual - expected).abs() < 1.0e-3 * expected.abs().max(1.0), "got {}, expected {}", actual, expected);
+    }
+
+    // A made up profile with a single vertical angle pair per horizontal angle, candela equal to the horizontal angle + 1
+    fn profile_with_horizontal_angles(horizontal_angles: &[f32], tilt: &str) -> IesProfile {
+        let mut text = format!("IESNA:LM-63-2002\n[TEST] symmetry\n{}\n", tilt);
+        text += &format!("1 -1 1.0 2 {} 1 2 0.1 0.1 0.0\n1.0 1.0 10\n0 90\n", horizontal_angles.len());
+        text += &horizontal_angles.iter().map(|angle| angle.to_string()).collect::<Vec<String>>().join(" ");
+        text += "\n";
+        for angle in horizontal_angles {
+            text += &format!("{} {}\n", angle + 1.0, angle + 1.0);
+        }
+
+        IesProfile::parse(&text, "test").unwrap()
+    }
+
+    #[test]
+    fn parses_the_bundled_downlight() {
+        let profile = IesProfile::from_file("downlight.ies").unwrap();
+
+        assert_eq!(profile.vertical_angles.len(), 19);
+        assert_eq!(profile.horizontal_angles, vec![0.0]);
+        assert_close(profile.max_candela(), 2460.0);
+
+        // Rotationally symmetric, the horizontal angle doesn't matter
+        assert_close(profile.candela(0.0, 0.0), 2460.0);
+        for horizontal_angle in [0.0, 45.0, 90.0, 180.0, 300.0, -30.0] {
+            assert_close(profile.candela(30.0, horizontal_angle), profile.candela(30.0, 0.0));
+        }
+
+        // Nothing above the horizon
+        assert_eq!(profile.candela(90.0, 0.0), 0.0);
+        assert_eq!(profile.candela(120.0, 0.0), 0.0);
+    }
+
+    #[test]
+    fn folds_the_bundled_wall_washer_around_the_0_180_plane() {
+        let profile = IesProfile::from_file("wallwash.ies").unwrap();
+
+        assert_eq!(profile.horizontal_angles, vec![0.0, 30.0, 60.0, 90.0, 120.0, 150.0, 180.0]);
+
+        // Tabulated values, 150 cos(v) + 900 sin(h) exp(-((v - 45) / 15)^2) rounded to tenths
+        assert_close(profile.candela(45.0, 90.0), 1006.1);
+        assert_close(profile.candela(45.0, 0.0), 106.1);
+        assert_close(profile.max_candela(), 1006.1);
+
+        // Mirrored across the 0-180 plane, so 270 is the same as 90, and 200 the same as 160
+        assert_close(profile.candela(45.0, 270.0), profile.candela(45.0, 90.0));
+        assert_close(profile.candela(40.0, 200.0), profile.candela(40.0, 160.0));
+        assert_close(profile.candela(40.0, -20.0), profile.candela(40.0, 20.0));
+
+        // Halfway between 30 and 60 degrees horizontally
+        assert_close(profile.candela(45.0, 45.0), 0.5 * (profile.candela(45.0, 30.0) + profile.candela(45.0, 60.0)));
+    }
+
+    #[test]
+    fn folds_quadrant_symmetric_profiles() {
+        let profile = profile_with_horizontal_angles(&[0.0, 45.0, 90.0], "TILT=NONE");
+
+        assert_close(profile.candela(0.0, 45.0), 46.0);
+        assert_close(profile.candela(0.0, 135.0), 46.0);
+        assert_close(profile.candela(0.0, 225.0), 46.0);
+        assert_close(profile.candela(0.0, 315.0), 46.0);
+        assert_close(profile.candela(0.0, 180.0), 1.0);
+        assert_close(profile.candela(0.0, 270.0), 91.0);
+    }
+
+    #[test]
+    fn folds_profiles_around_the_90_270_plane() {
+        let profile = profile_with_horizontal_angles(&[90.0, 180.0, 270.0], "TILT=NONE");
+
+        assert_close(profile.candela(0.0, 180.0), 181.0);
+        assert_close(profile.candela(0.0, 0.0), 181.0);
+        assert_close(profile.candela(0.0, 45.0), profile.candela(0.0, 135.0));
+        assert_close(profile.candela(0.0, 315.0), profile.candela(0.0, 225.0));
+    }
+
+    #[test]
+    fn wraps_full_circles_without_a_closing_angle() {
+        let profile = profile_with_horizontal_angles(&[0.0, 90.0, 180.0, 270.0], "TILT=NONE");
+
+        // Between 270 and 360, which is the 0 row again
+        assert_close(profile.candela(0.0, 315.0), 0.5 * (271.0 + 1.0));
+        assert_close(profile.candela(0.0, 135.0), 0.5 * (91.0 + 181.0));
+    }
+
+    #[test]
+    fn skips_included_tilt_data() {
+        let tilt = "TILT=INCLUDE\n1\n3\n0 45 90\n1.0 0.9 0.8";
+        let profile = profile_with_horizontal_angles(&[0.0], tilt);
+
+        assert_eq!(profile.vertical_angles, vec![0.0, 90.0]);
+        assert_close(profile.candela(0.0, 0.0), 1.0);
+    }
+
+    #[test]
+    fn rejects_files_without_a_tilt_line() {
+        let result = IesProfile::parse("IESNA:LM-63-2002\n1 -1 1.0 1 1 1 2 0 0 0\n", "test");
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn relative_intensity_follows_the_fixture_frame() {
+        let profile = IesProfile::from_file("wallwash.ies").unwrap();
+
+        // Pointing down with its length along x, so 90 degrees horizontally is +z
+        let frame = fixture_frame(&Vec3::new(0.0, -1.0, 0.0), &Vec3::new(1.0, 0.0, 0.0));
+        let towards = Vec3::new(0.0, -1.0, 1.0);
+        let away = Vec3::new(0.0, -1.0, -1.0);
+
+        assert_close(profile.relative_intensity(&frame, &towards), 1.0);
+        assert_close(profile.relative_intensity(&frame, &away), 1.0);
+        assert_close(profile.relative_intensity(&frame, &Vec3::new(0.0, 1.0, 0.0)), 0.0);
+    }
+}
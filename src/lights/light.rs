@@ -0,0 +1,16 @@
+use ultraviolet::Vec3;
+use rand::rngs::ThreadRng;
+
+use crate::core::color_rgb::ColorRGB;
+
+#[derive(Default, Clone, Copy)]
+pub struct LightSample {
+    pub direction: Vec3, // Normalized, from the lit point towards the light
+    pub distance: f32, // Infinite for lights without a position
+    pub radiance: ColorRGB, // Arriving at the lit point, already divided by the pdf of the sample
+}
+
+// Lights which aren't geometry and can't be hit by rays, they are only found by sampling them
+pub trait Light: Sync + Send {
+    fn sample(&self, rng: &mut ThreadRng, point: &Vec3, sample_out: &mut LightSample) -> bool;
+}
\ No newline at end of file
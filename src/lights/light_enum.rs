@@ -0,0 +1,21 @@
+use ultraviolet::Vec3;
+use rand::rngs::ThreadRng;
+
+use super::{point_light::PointLight, spot_light::SpotLight, directional_light::DirectionalLight, light::{Light, LightSample}};
+
+pub enum LightEnum {
+    Point(PointLight),
+    Spot(SpotLight),
+    Directional(DirectionalLight),
+}
+
+impl Light for LightEnum {
+    #[inline]
+    fn sample(&self, rng: &mut ThreadRng, point: &Vec3, sample_out: &mut LightSample) -> bool {
+        match self {
+            LightEnum::Point(point_light) => point_light.sample(rng, point, sample_out),
+            LightEnum::Spot(spot_light) => spot_light.sample(rng, point, sample_out),
+            LightEnum::Directional(directional_light) => directional_light.sample(rng, point, sample_out),
+        }
+    }
+}
\ No newline at end of file
@@ -0,0 +1,7 @@
+pub mod light;
+pub mod point_light;
+pub mod spot_light;
+pub mod directional_light;
+pub mod photometry;
+pub mod ies_profile;
+pub mod light_enum;
\ No newline at end of file
@@ -0,0 +1,107 @@
+use std::f32::consts::PI;
+
+use crate::core::color_rgb::ColorRGB;
+
+// Luminous efficacy in lumens per watt of some common kinds of lamps, for use with LuminousFlux::Watts
+pub const HALOGEN_EFFICACY: f32 = 20.0;
+pub const LED_EFFICACY: f32 = 100.0;
+
+// Total visible light given off by an emitter, as found on the packaging of a light bulb.
+// Watts are the electrical power drawn, the light given off depends on how efficient the lamp is.
+#[derive(Clone, Copy)]
+pub enum LuminousFlux {
+    Lumens(f32),
+    Watts { watts: f32, luminous_efficacy: f32 },
+}
+
+impl LuminousFlux {
+    #[inline]
+    pub fn lumens(&self) -> f32 {
+        match self {
+            LuminousFlux::Lumens(lumens) => *lumens,
+            LuminousFlux::Watts { watts, luminous_efficacy } => watts * luminous_efficacy,
+        }
+    }
+}
+
+// Converts real world emitter specifications to the unitless radiance used by the materials and lights.
+// meters_per_unit is the size of one scene unit, white_luminance is the luminance, in candela per square meter,
+// which ends up as a radiance of 1, in other words the exposure of the camera.
+// Colors are only used for their chromaticity, they are scaled to a luminance of 1 first. 
+pub struct PhotometricUnits {
+    meters_per_unit: f32,
+    white_luminance: f32,
+}
+
+impl PhotometricUnits {
+    pub fn new(meters_per_unit: f32, white_luminance: f32) -> PhotometricUnits {
+        PhotometricUnits { meters_per_unit, white_luminance }
+    }
+
+    // Radiance of a surface with a given luminance in candela per square meter, also known as nits
+    pub fn radiance(&self, color: &ColorRGB, luminance: f32) -> ColorRGB {
+        normalized_color(color) * (luminance / self.white_luminance)
+    }
+
+    // Radiance of a surface glowing only because it is hot, like molten metal or the sun
+    pub fn blackbody_radiance(&self, temperature: f32) -> ColorRGB {
+        self.radiance(&ColorRGB::blackbody(temperature), ColorRGB::blackbody_luminance(temperature))
+    }
+
+    // Radiance for a DiffuseLight covering a surface of the given area in scene units squared.
+    // Diffuse lights only emit from their front faces, so all of the flux leaves through one side,
+    // which for spheres is all of the outside.
+    pub fn area_radiance(&self, color: &ColorRGB, flux: LuminousFlux, area: f32) -> ColorRGB {
+        let area_in_meters = area * self.meters_per_unit * self.meters_per_unit;
+        if area_in_meters <= 0.0 {
+            return ColorRGB::black();
+        }
+
+        self.radiance(color, flux.lumens() / (PI * area_in_meters))
+    }
+
+    // Scale for a ProfiledLight covering a surface of the given area in scene units squared,
+    // turning the candela of its profile into radiance
+    pub fn area_radiance_per_candela(&self, color: &ColorRGB, area: f32) -> ColorRGB {
+        let area_in_meters = area * self.meters_per_unit * self.meters_per_unit;
+        if area_in_meters <= 0.0 {
+            return ColorRGB::black();
+        }
+
+        self.radiance(color, 1.0 / area_in_meters)
+    }
+
+    // Intensity for a PointLight radiating the flux evenly in all directions
+    pub fn point_intensity(&self, color: &ColorRGB, flux: LuminousFlux) -> ColorRGB {
+        self.intensity(color, flux.lumens() / (4.0 * PI))
+    }
+
+    // Intensity for a SpotLight with the same angles in degrees, so the flux ends up inside its cone.
+    // The smooth falloff between the two angles is approximated as being linear in cosine.
+    pub fn spot_intensity(&self, color: &ColorRGB, flux: LuminousFlux, cone_angle: f32, falloff_start: f32) -> ColorRGB {
+        let cone_angle = cone_angle.clamp(0.0, 180.0);
+        let falloff_start = falloff_start.clamp(0.0, cone_angle);
+        let solid_angle = 2.0 * PI * (1.0 - 0.5 * (cone_angle.to_radians().cos() + falloff_start.to_radians().cos()));
+        if solid_angle <= 0.0 {
+            return ColorRGB::black();
+        }
+
+        self.intensity(color, flux.lumens() / solid_angle)
+    }
+
+    // Intensity in candela, for point and spot lights. Scene distances are converted to meters
+    // before applying the inverse square law.
+    pub fn intensity(&self, color: &ColorRGB, candela: f32) -> ColorRGB {
+        normalized_color(color) * (candela / (self.meters_per_unit * self.meters_per_unit * self.white_luminance))
+    }
+}
+
+#[inline]
+fn normalized_color(color: &ColorRGB) -> ColorRGB {
+    let luminance = color.luminance();
+    if luminance <= 0.0 {
+        return ColorRGB::black();
+    }
+
+    *color / luminance
+}
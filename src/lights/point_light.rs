@@ -0,0 +1,48 @@
+use ultraviolet::Vec3;
+use rand::rngs::ThreadRng;
+
+use crate::core::color_rgb::ColorRGB;
+
+use super::{light::{Light, LightSample}, ies_profile::{IesProfile, fixture_frame}};
+use crate::math::ortho_normal_base::OrthoNormalBase;
+
+// Emits intensity equally in all directions from a single point,
+// unless given a photometric profile, in which case intensity is the peak of the profile
+pub struct PointLight {
+    pub position: Vec3,
+    pub intensity: ColorRGB,
+    pub profile: Option<(IesProfile, OrthoNormalBase)>,
+}
+
+impl PointLight {
+    pub fn new(position: Vec3, intensity: ColorRGB) -> PointLight {
+        PointLight { position, intensity, profile: None }
+    }
+
+    // Points the fixture towards target, with its length along the given direction
+    pub fn with_profile(mut self, profile: IesProfile, target: Vec3, length: Vec3) -> PointLight {
+        self.profile = Some((profile, fixture_frame(&(target - self.position), &length)));
+        self
+    }
+}
+
+impl Light for PointLight {
+    fn sample(&self, _rng: &mut ThreadRng, point: &Vec3, sample_out: &mut LightSample) -> bool {
+        let to_light = self.position - *point;
+        let distance_squared = to_light.mag_sq();
+        if distance_squared <= 0.0 {
+            return false;
+        }
+
+        sample_out.distance = distance_squared.sqrt();
+        sample_out.direction = to_light / sample_out.distance;
+        let relative_intensity = self.profile.as_ref().map_or(1.0, |(profile, frame)| profile.relative_intensity(frame, &-sample_out.direction));
+        if relative_intensity <= 0.0 {
+            return false;
+        }
+
+        sample_out.radiance = self.intensity * (relative_intensity / distance_squared);
+
+        true
+    }
+}
\ No newline at end of file
@@ -0,0 +1,78 @@
+use ultraviolet::Vec3;
+use rand::rngs::ThreadRng;
+
+use crate::core::color_rgb::ColorRGB;
+
+use super::{light::{Light, LightSample}, ies_profile::{IesProfile, fixture_frame}};
+use crate::math::ortho_normal_base::OrthoNormalBase;
+
+// A point light restricted to a cone around the direction from position to target.
+// The intensity is full inside falloff_start degrees and fades smoothly to zero at cone_angle degrees.
+// With a photometric profile, the profile is pointed along the spot and intensity is its peak.
+pub struct SpotLight {
+    pub position: Vec3,
+    pub direction: Vec3,
+    pub intensity: ColorRGB,
+    pub cos_cone_angle: f32,
+    pub cos_falloff_start: f32,
+    pub profile: Option<(IesProfile, OrthoNormalBase)>,
+}
+
+impl SpotLight {
+    pub fn new(position: Vec3, target: Vec3, intensity: ColorRGB, cone_angle: f32, falloff_start: f32) -> SpotLight {
+        let cone_angle = cone_angle.clamp(0.0, 180.0);
+        let falloff_start = falloff_start.clamp(0.0, cone_angle);
+
+        SpotLight { 
+            position, 
+            direction: (target - position).normalized(), 
+            intensity, 
+            cos_cone_angle: cone_angle.to_radians().cos(), 
+            cos_falloff_start: falloff_start.to_radians().cos(),
+            profile: None,
+        }
+    }
+
+    // The length of the fixture is along the given direction
+    pub fn with_profile(mut self, profile: IesProfile, length: Vec3) -> SpotLight {
+        self.profile = Some((profile, fixture_frame(&self.direction, &length)));
+        self
+    }
+
+    #[inline]
+    fn falloff(&self, cos_theta: f32) -> f32 {
+        if cos_theta <= self.cos_cone_angle {
+            return 0.0;
+        }
+
+        if self.cos_falloff_start <= cos_theta || self.cos_falloff_start <= self.cos_cone_angle {
+            return 1.0;
+        }
+
+        let t = (cos_theta - self.cos_cone_angle) / (self.cos_falloff_start - self.cos_cone_angle);
+        t * t * (3.0 - 2.0 * t)
+    }
+}
+
+impl Light for SpotLight {
+    fn sample(&self, _rng: &mut ThreadRng, point: &Vec3, sample_out: &mut LightSample) -> bool {
+        let to_light = self.position - *point;
+        let distance_squared = to_light.mag_sq();
+        if distance_squared <= 0.0 {
+            return false;
+        }
+
+        sample_out.distance = distance_squared.sqrt();
+        sample_out.direction = to_light / sample_out.distance;
+
+        let falloff = self.falloff(-sample_out.direction.dot(self.direction)) 
+            * self.profile.as_ref().map_or(1.0, |(profile, frame)| profile.relative_intensity(frame, &-sample_out.direction));
+        if falloff <= 0.0 {
+            return false;
+        }
+
+        sample_out.radiance = self.intensity * (falloff / distance_squared);
+
+        true
+    }
+}
\ No newline at end of file
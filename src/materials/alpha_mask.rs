@@ -0,0 +1,44 @@
+use rand::{rngs::ThreadRng, Rng};
+
+use crate::{services::{material_service::MaterialService, texture_service::TextureService}, core::{ray::Ray, color_rgb::ColorRGB}, hittables::hit_record::HitRecord, pdfs::pdf_enum::PDFEnum};
+
+use super::{material::Material, scatter_record::ScatterRecord};
+
+// Cutout for leaves, fences and the like. Where the alpha of the mask texture is zero
+// rays continue as if nothing was hit, partial alpha lets rays through stochastically.
+pub struct AlphaMask {
+    pub material: usize,
+    pub alpha_texture_index: usize,
+}
+
+impl AlphaMask {
+    pub fn new(material: usize, alpha_texture_index: usize) -> AlphaMask {
+        AlphaMask { material, alpha_texture_index }
+    }
+}
+
+impl Material for AlphaMask {
+    fn emitted(&self, material_service: &MaterialService, texture_service: &TextureService, ray:&Ray, hit: &HitRecord, emitted_out: &mut ColorRGB) {
+        material_service.emitted_by_index(self.material, texture_service, ray, hit, emitted_out);
+
+        *emitted_out *= texture_service.alpha(self.alpha_texture_index, hit.u, hit.v, &hit.position);
+    }
+
+    fn scatter(&self, rng: &mut ThreadRng, material_service: &MaterialService, texture_service: &TextureService, ray:&Ray, hit: &HitRecord, scatter_out: &mut ScatterRecord) -> bool {
+        let alpha = texture_service.alpha(self.alpha_texture_index, hit.u, hit.v, &hit.position);
+        if alpha <= rng.gen::<f32>() {
+            scatter_out.is_specular = true;
+            scatter_out.pdf = PDFEnum::None();
+            scatter_out.attenuation = ColorRGB::white();
+            scatter_out.specular_ray = Ray::new(hit.position, ray.direction, ray.time);
+
+            return true;
+        }
+
+        material_service.scatter_by_index(self.material, rng, texture_service, ray, hit, scatter_out)
+    }
+
+    fn perturb_normal(&self, material_service: &MaterialService, texture_service: &TextureService, ray: &Ray, hit: &mut HitRecord) {
+        material_service.perturb_normal_by_index(self.material, texture_service, ray, hit);
+    }
+}
@@ -0,0 +1,61 @@
+use rand::{rngs::ThreadRng, Rng};
+
+use crate::{services::{material_service::MaterialService, texture_service::TextureService}, core::{ray::Ray, color_rgb::ColorRGB}, hittables::hit_record::HitRecord};
+
+use super::{material::Material, scatter_record::ScatterRecord};
+
+// Mixes two materials by the luminance of the mask texture, 0 gives material_a and 1 gives material_b,
+// the same convention as the Mix texture. Use a solid color as mask for a constant blend.
+// Every scattering event picks one of the two materials with the blend weight as probability,
+// which keeps the estimate unbiased without evaluating both lobes.
+pub struct Blend {
+    pub material_a: usize,
+    pub material_b: usize,
+    pub mask_texture_index: usize,
+}
+
+impl Blend {
+    pub fn new(material_a: usize, material_b: usize, mask_texture_index: usize) -> Blend {
+        Blend { material_a, material_b, mask_texture_index }
+    }
+
+    #[inline]
+    fn weight(&self, texture_service: &TextureService, hit: &HitRecord) -> f32 {
+        let mut mask: ColorRGB = ColorRGB::black();
+        texture_service.value_at_hit(self.mask_texture_index, hit, &mut mask);
+
+        mask.luminance().clamp(0.0, 1.0)
+    }
+}
+
+impl Material for Blend {
+    fn emitted(&self, material_service: &MaterialService, texture_service: &TextureService, ray:&Ray, hit: &HitRecord, emitted_out: &mut ColorRGB) {
+        let weight = self.weight(texture_service, hit);
+
+        let mut emitted_b: ColorRGB = ColorRGB::black();
+        material_service.emitted_by_index(self.material_a, texture_service, ray, hit, emitted_out);
+        material_service.emitted_by_index(self.material_b, texture_service, ray, hit, &mut emitted_b);
+
+        *emitted_out = *emitted_out * (1.0 - weight) + emitted_b * weight;
+    }
+
+    fn scatter(&self, rng: &mut ThreadRng, material_service: &MaterialService, texture_service: &TextureService, ray:&Ray, hit: &HitRecord, scatter_out: &mut ScatterRecord) -> bool {
+        let chosen_material = if rng.gen::<f32>() < self.weight(texture_service, hit) { self.material_b } else { self.material_a };
+
+        material_service.scatter_by_index(chosen_material, rng, texture_service, ray, hit, scatter_out)
+    }
+
+    // Both materials get to perturb the normal, the results are blended by the mask
+    fn perturb_normal(&self, material_service: &MaterialService, texture_service: &TextureService, ray: &Ray, hit: &mut HitRecord) {
+        let weight = self.weight(texture_service, hit);
+
+        let mut hit_b: HitRecord = *hit;
+        material_service.perturb_normal_by_index(self.material_a, texture_service, ray, hit);
+        material_service.perturb_normal_by_index(self.material_b, texture_service, ray, &mut hit_b);
+
+        let normal = hit.normal * (1.0 - weight) + hit_b.normal * weight;
+        if 0.0 < normal.mag_sq() {
+            hit.set_shading_normal(ray, &normal);
+        }
+    }
+}
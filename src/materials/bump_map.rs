@@ -0,0 +1,81 @@
+use ultraviolet::Vec3;
+use rand::rngs::ThreadRng;
+
+use crate::{services::{material_service::MaterialService, texture_service::TextureService}, core::{ray::Ray, color_rgb::ColorRGB}, hittables::hit_record::HitRecord};
+
+use super::{material::Material, scatter_record::ScatterRecord};
+
+const UV_DELTA: f32 = 1.0 / 1024.0;
+const POSITION_DELTA: f32 = 0.05;
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum HeightSpace {
+    UV,    // Image and other uv mapped textures, the slope is taken by stepping u and v
+    Solid, // Noise and other solid textures, the slope is taken by stepping the position along the tangents
+}
+
+// Bump map on top of any material, the red channel of the height texture displaces the surface
+// along the normal. The slope is found with finite differences in the height space of the texture.
+// Strength scales the resulting slope, which is height per unit of uv for UV
+// and height per unit of distance for Solid.
+pub struct BumpMap {
+    pub material: usize,
+    pub height_texture_index: usize,
+    pub strength: f32,
+    pub height_space: HeightSpace,
+}
+
+impl BumpMap {
+    pub fn new(material: usize, height_texture_index: usize, strength: f32, height_space: HeightSpace) -> BumpMap {
+        BumpMap { material, height_texture_index, strength, height_space }
+    }
+}
+
+impl Material for BumpMap {
+    fn emitted(&self, material_service: &MaterialService, texture_service: &TextureService, ray:&Ray, hit: &HitRecord, emitted_out: &mut ColorRGB) {
+        material_service.emitted_by_index(self.material, texture_service, ray, hit, emitted_out);
+    }
+
+    fn scatter(&self, rng: &mut ThreadRng, material_service: &MaterialService, texture_service: &TextureService, ray:&Ray, hit: &HitRecord, scatter_out: &mut ScatterRecord) -> bool {
+        if !material_service.scatter_by_index(self.material, rng, texture_service, ray, hit, scatter_out) {
+            return false;
+        }
+
+        if scatter_out.is_specular {
+            scatter_out.specular_ray.direction = hit.make_consistent(&scatter_out.specular_ray.direction);
+        }
+
+        true
+    }
+
+    fn perturb_normal(&self, material_service: &MaterialService, texture_service: &TextureService, ray: &Ray, hit: &mut HitRecord) {
+        let (tangent, bitangent) = hit.tangent_frame();
+
+        let probe: HitRecord = *hit;
+        let height = |u: f32, v: f32, position: Vec3| {
+            let probe = HitRecord { u, v, position, ..probe };
+            let mut value: ColorRGB = ColorRGB::black();
+            texture_service.value_at_hit(self.height_texture_index, &probe, &mut value);
+            value.r
+        };
+
+        let center = height(hit.u, hit.v, hit.position);
+        let (slope_u, slope_v) = match self.height_space {
+            HeightSpace::UV => (
+                (height(hit.u + UV_DELTA, hit.v, hit.position) - center) / UV_DELTA,
+                (height(hit.u, hit.v + UV_DELTA, hit.position) - center) / UV_DELTA,
+            ),
+            HeightSpace::Solid => (
+                (height(hit.u, hit.v, hit.position + tangent * POSITION_DELTA) - center) / POSITION_DELTA,
+                (height(hit.u, hit.v, hit.position + bitangent * POSITION_DELTA) - center) / POSITION_DELTA,
+            ),
+        };
+
+        let (slope_u, slope_v) = (self.strength * slope_u, self.strength * slope_v);
+
+        let normal = hit.normal - tangent * slope_u - bitangent * slope_v;
+        hit.set_shading_normal(ray, &normal);
+
+        material_service.perturb_normal_by_index(self.material, texture_service, ray, hit);
+    }
+}
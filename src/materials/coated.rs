@@ -0,0 +1,76 @@
+use ultraviolet::Vec3;
+use rand::{rngs::ThreadRng, Rng};
+
+use crate::{services::{material_service::MaterialService, texture_service::TextureService}, core::{ray::Ray, color_rgb::ColorRGB}, hittables::hit_record::HitRecord, pdfs::pdf_enum::PDFEnum, math::utility::{reflect, random_in_unit_sphere}};
+
+use super::{material::Material, scatter_record::ScatterRecord, dielectric::Dielectric};
+
+// A dielectric clearcoat on top of any other material, like car paint or varnished wood.
+// The coat reflects with the Fresnel probability, everything else is transmitted to the base
+// material and tinted by the coat texture on the way.
+pub struct Coated {
+    pub base_material: usize,
+    pub coat_texture_index: usize,
+    pub inverse_index_of_refraction: f32,
+    pub roughness: f32, // should be saturated to 1
+}
+
+impl Coated {
+    pub fn new(base_material: usize, coat_texture_index: usize, index_of_refraction: f32, roughness: f32) -> Coated {
+        Coated { 
+            base_material, 
+            coat_texture_index, 
+            inverse_index_of_refraction: 1.0 / index_of_refraction, 
+            roughness: roughness.clamp(0.0, 1.0),
+        }
+    }
+}
+
+impl Material for Coated {
+    fn emitted(&self, material_service: &MaterialService, texture_service: &TextureService, ray:&Ray, hit: &HitRecord, emitted_out: &mut ColorRGB) {
+        material_service.emitted_by_index(self.base_material, texture_service, ray, hit, emitted_out);
+    }
+
+    fn scatter(&self, rng: &mut ThreadRng, material_service: &MaterialService, texture_service: &TextureService, ray:&Ray, hit: &HitRecord, scatter_out: &mut ScatterRecord) -> bool {
+        // The coat is only on the outside
+        if !hit.is_front_face {
+            return material_service.scatter_by_index(self.base_material, rng, texture_service, ray, hit, scatter_out);
+        }
+
+        let unit_direction: Vec3 = ray.direction.normalized();
+        let cos_theta = (-unit_direction).dot(hit.normal).min(1.0);
+
+        if rng.gen::<f32>() < Dielectric::reflectance(cos_theta, self.inverse_index_of_refraction) {
+            let mut reflected: Vec3 = Vec3::zero();
+            reflect(&unit_direction, &hit.normal, &mut reflected);
+            reflected += random_in_unit_sphere(rng) * self.roughness;
+
+            // A roughened reflection ending up below the surface is absorbed,
+            // handing it to the base would give the base more than its transmitted share
+            if reflected.dot(hit.normal) <= 0.0 {
+                return false;
+            }
+
+            scatter_out.specular_ray = Ray::new_normalized(hit.position, reflected, ray.time);
+            scatter_out.attenuation = ColorRGB::white();
+            scatter_out.is_specular = true;
+            scatter_out.pdf = PDFEnum::None();
+
+            return true;
+        }
+
+        if !material_service.scatter_by_index(self.base_material, rng, texture_service, ray, hit, scatter_out) {
+            return false;
+        }
+
+        let mut coat_color: ColorRGB = ColorRGB::white();
+        texture_service.value_at_hit(self.coat_texture_index, hit, &mut coat_color);
+        scatter_out.attenuation *= coat_color;
+
+        true
+    }
+
+    fn perturb_normal(&self, material_service: &MaterialService, texture_service: &TextureService, ray: &Ray, hit: &mut HitRecord) {
+        material_service.perturb_normal_by_index(self.base_material, texture_service, ray, hit);
+    }
+}
@@ -0,0 +1,66 @@
+use ultraviolet::Vec3;
+use rand::rngs::ThreadRng;
+
+use crate::{services::{material_service::MaterialService, texture_service::TextureService}, core::{ray::Ray, color_rgb::ColorRGB}, hittables::hit_record::HitRecord, pdfs::pdf_enum::PDFEnum, math::{utility::{reflect, random_in_unit_sphere}, fresnel::conductor_reflectance}};
+
+use super::{material::Material, scatter_record::ScatterRecord};
+
+// Complex index of refraction of common metals, per color channel.
+// RGB fits of the measured spectra (Johnson and Christy for gold, copper and silver, Rakic for aluminium).
+const PRESETS: [(&str, [f32; 3], [f32; 3]); 4] = [
+    ("gold", [0.143, 0.375, 1.442], [3.983, 2.386, 1.603]),
+    ("copper", [0.200, 0.924, 1.102], [3.912, 2.452, 2.142]),
+    ("aluminium", [1.657, 0.880, 0.521], [9.224, 6.270, 4.837]),
+    ("silver", [0.155, 0.117, 0.138], [4.828, 3.122, 2.147]),
+];
+
+// Metal with full conductor Fresnel, the color comes from the complex index of refraction
+// and shifts towards white at grazing angles. Roughness fuzzes the reflection just like Metal.
+pub struct Conductor {
+    pub eta: ColorRGB,
+    pub k: ColorRGB,
+    pub roughness: f32, // should be saturated to 1
+}
+
+impl Conductor {
+    pub fn new(eta: ColorRGB, k: ColorRGB, roughness: f32) -> Conductor {
+        Conductor { eta, k, roughness: roughness.min(1.0) }
+    }
+
+    // Looks up one of gold, copper, aluminium and silver (aluminum works too)
+    pub fn preset(name: &str, roughness: f32) -> Option<Conductor> {
+        let name = name.to_lowercase();
+        let name = if name == "aluminum" { "aluminium" } else { name.as_str() };
+
+        PRESETS
+            .iter()
+            .find(|(preset_name, _, _)| *preset_name == name)
+            .map(|(_, eta, k)| Conductor::new(ColorRGB::new(eta[0], eta[1], eta[2]), ColorRGB::new(k[0], k[1], k[2]), roughness))
+    }
+}
+
+impl Material for Conductor {
+    fn scatter(&self, rng: &mut ThreadRng, _material_service: &MaterialService, _texture_service: &TextureService, ray:&Ray, hit: &HitRecord, scatter_out: &mut ScatterRecord) -> bool {
+        let unit_direction: Vec3 = ray.direction.normalized();
+        let cos_theta = (-unit_direction).dot(hit.normal).min(1.0);
+
+        let mut reflected: Vec3 = Vec3::default(); 
+        reflect(&unit_direction, &hit.normal, &mut reflected);
+        reflected += random_in_unit_sphere(rng) * self.roughness;
+
+        // A roughened reflection ending up below the surface is absorbed
+        if reflected.dot(hit.normal) <= 0.0 {
+            return false;
+        }
+
+        scatter_out.specular_ray = Ray::new_normalized(hit.position, reflected, ray.time);
+        scatter_out.attenuation = ColorRGB::new(
+            conductor_reflectance(cos_theta, self.eta.r, self.k.r),
+            conductor_reflectance(cos_theta, self.eta.g, self.k.g),
+            conductor_reflectance(cos_theta, self.eta.b, self.k.b),
+        );
+        scatter_out.is_specular = true;
+        scatter_out.pdf = PDFEnum::None();
+        true
+    }
+}
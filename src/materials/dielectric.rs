@@ -1,7 +1,7 @@
 use ultraviolet::Vec3;
 use rand::{rngs::ThreadRng, Rng};
 
-use crate::{services::texture_service::TextureService, core::{ray::Ray, color_rgb::ColorRGB}, hittables::hit_record::HitRecord, pdfs::pdf_enum::PDFEnum, math::utility::{reflect, refract}};
+use crate::{services::{material_service::MaterialService, texture_service::TextureService}, core::{ray::Ray, color_rgb::ColorRGB}, hittables::hit_record::HitRecord, pdfs::pdf_enum::PDFEnum, math::utility::{reflect, refract}};
 
 use super::{material::Material, scatter_record::ScatterRecord};
 
@@ -12,7 +12,7 @@ pub struct Dielectric {
 }
 
 impl Material for Dielectric {
-    fn scatter(&self, rng: &mut ThreadRng, _texture_service: &TextureService, ray:&Ray, hit: &HitRecord, scatter_out: &mut ScatterRecord) -> bool {
+    fn scatter(&self, rng: &mut ThreadRng, _material_service: &MaterialService, _texture_service: &TextureService, ray:&Ray, hit: &HitRecord, scatter_out: &mut ScatterRecord) -> bool {
         scatter_out.is_specular = true;
         scatter_out.pdf = PDFEnum::None();
         scatter_out.attenuation = ColorRGB::new(1.0, 1.0, 1.0);
@@ -38,7 +38,7 @@ impl Material for Dielectric {
 }
 
 impl Dielectric {
-    fn reflectance(cosine: f32, index_of_refraction: f32) -> f32 {
+    pub fn reflectance(cosine: f32, index_of_refraction: f32) -> f32 {
         let r0 = (1.0 - index_of_refraction) / (1.0 + index_of_refraction);
         let r0_squared = r0 * r0;
         let inverse_cosine = 1.0 - cosine;
@@ -1,4 +1,4 @@
-use crate::{core::{ray::Ray, color_rgb::ColorRGB}, hittables::hit_record::HitRecord, services::texture_service::TextureService};
+use crate::{core::{ray::Ray, color_rgb::ColorRGB}, hittables::hit_record::HitRecord, services::{material_service::MaterialService, texture_service::TextureService}};
 
 use super::material::Material;
 
@@ -13,9 +13,9 @@ impl DiffuseLight {
 }
 
 impl Material for DiffuseLight {
-    fn emitted(&self, texture_service: &TextureService, _ray:&Ray, hit: &HitRecord, emitted_out: &mut ColorRGB) {
+    fn emitted(&self, _material_service: &MaterialService, texture_service: &TextureService, _ray:&Ray, hit: &HitRecord, emitted_out: &mut ColorRGB) {
         if hit.is_front_face {
-            texture_service.value(self.emission_texture_index, hit.u, hit.v, &hit.position, emitted_out);
+            texture_service.value_at_hit(self.emission_texture_index, hit, emitted_out);
             return;
         }
 
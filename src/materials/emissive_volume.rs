@@ -0,0 +1,77 @@
+use rand::rngs::ThreadRng;
+
+use crate::{services::{material_service::MaterialService, texture_service::TextureService}, core::{ray::Ray, color_rgb::ColorRGB}, hittables::hit_record::HitRecord, textures::volume_grid::VolumeGrid};
+
+use super::{material::Material, scatter_record::ScatterRecord};
+
+const BLACKBODY_TABLE_SIZE: usize = 256;
+
+pub enum VolumeEmission {
+    Constant(ColorRGB),
+    Texture(usize), // Looked up at the collision position, like a solid texture
+    Blackbody(BlackbodyEmission),
+}
+
+// Temperatures in Kelvin from a grid. Hotter gas is brighter and shifts from red to white,
+// the hottest temperature in the grid gets a luminance of intensity.
+pub struct BlackbodyEmission {
+    temperature: VolumeGrid,
+    max_temperature: f32,
+    table: Vec<ColorRGB>, // Emission from 0 Kelvin to max_temperature
+}
+
+impl BlackbodyEmission {
+    pub fn new(temperature: VolumeGrid, intensity: f32) -> BlackbodyEmission {
+        let max_temperature = temperature.max_value();
+        let max_luminance = ColorRGB::blackbody_luminance(max_temperature);
+
+        let mut table: Vec<ColorRGB> = Vec::with_capacity(BLACKBODY_TABLE_SIZE);
+        for index in 0..BLACKBODY_TABLE_SIZE {
+            let entry_temperature = max_temperature * index as f32 / (BLACKBODY_TABLE_SIZE - 1) as f32;
+            let relative_luminance = if 0.0 < max_luminance { ColorRGB::blackbody_luminance(entry_temperature) / max_luminance } else { 0.0 };
+            table.push(ColorRGB::blackbody(entry_temperature) * (intensity * relative_luminance));
+        }
+
+        BlackbodyEmission { temperature, max_temperature, table }
+    }
+
+    fn value(&self, hit: &HitRecord) -> ColorRGB {
+        if self.max_temperature <= 0.0 {
+            return ColorRGB::black();
+        }
+
+        let position = (self.temperature.lookup(&hit.position) / self.max_temperature).clamp(0.0, 1.0) * (BLACKBODY_TABLE_SIZE - 1) as f32;
+        let index = (position as usize).min(BLACKBODY_TABLE_SIZE - 2);
+        let weight = position - index as f32;
+
+        self.table[index] * (1.0 - weight) + self.table[index + 1] * weight
+    }
+}
+
+// Glowing media like fire and hot gas. Goes in a medium in place of its phase function,
+// which still does the scattering. Every collision with the medium picks up the emission,
+// so it is the radiance emitted per unit of extinction.
+pub struct EmissiveVolume {
+    pub phase_function: usize,
+    pub emission: VolumeEmission,
+}
+
+impl EmissiveVolume {
+    pub fn new(phase_function: usize, emission: VolumeEmission) -> EmissiveVolume {
+        EmissiveVolume { phase_function, emission }
+    }
+}
+
+impl Material for EmissiveVolume {
+    fn emitted(&self, _material_service: &MaterialService, texture_service: &TextureService, _ray:&Ray, hit: &HitRecord, emitted_out: &mut ColorRGB) {
+        match &self.emission {
+            VolumeEmission::Constant(color) => *emitted_out = *color,
+            VolumeEmission::Texture(texture_index) => { texture_service.value_at_hit(*texture_index, hit, emitted_out); },
+            VolumeEmission::Blackbody(blackbody) => *emitted_out = blackbody.value(hit),
+        }
+    }
+
+    fn scatter(&self, rng: &mut ThreadRng, material_service: &MaterialService, texture_service: &TextureService, ray:&Ray, hit: &HitRecord, scatter_out: &mut ScatterRecord) -> bool {
+        material_service.scatter_by_index(self.phase_function, rng, texture_service, ray, hit, scatter_out)
+    }
+}
\ No newline at end of file
@@ -0,0 +1,41 @@
+use rand::rngs::ThreadRng;
+
+use crate::{services::{material_service::MaterialService, texture_service::TextureService}, core::ray::Ray, hittables::hit_record::HitRecord, math::phase_function::PhaseFunction, pdfs::{phase_pdf::PhasePDF, pdf_enum::PDFEnum}};
+use super::{material::Material, scatter_record::ScatterRecord};
+
+// Phase function for media, like Isotropic, but with an asymmetry g in (-1, 1).
+// Positive g scatters forward, like fog and clouds, negative g scatters back towards the light.
+pub struct HenyeyGreenstein {
+    pub albedo_texture_index: usize,
+    pub phase_function: PhaseFunction,
+}
+
+impl HenyeyGreenstein {
+    pub fn new(albedo_index: usize, g: f32) -> Self {
+        HenyeyGreenstein { albedo_texture_index: albedo_index, phase_function: PhaseFunction::HenyeyGreenstein { g: g.clamp(-0.99, 0.99) } }
+    }
+
+    // Blend of a forward and a backward lobe, forward_weight is the fraction of light in the forward lobe
+    pub fn double_lobe(albedo_index: usize, g_forward: f32, g_backward: f32, forward_weight: f32) -> Self {
+        HenyeyGreenstein { 
+            albedo_texture_index: albedo_index, 
+            phase_function: PhaseFunction::DoubleHenyeyGreenstein { 
+                g_forward: g_forward.clamp(-0.99, 0.99), 
+                g_backward: g_backward.clamp(-0.99, 0.99), 
+                forward_weight: forward_weight.clamp(0.0, 1.0) 
+            } 
+        }
+    }
+}
+
+impl Material for HenyeyGreenstein {
+    fn scatter(&self, _rng: &mut ThreadRng, _material_service: &MaterialService, texture_service: &TextureService, ray:&Ray, hit: &HitRecord, scatter_out: &mut ScatterRecord) -> bool {
+        scatter_out.is_specular = false;
+        scatter_out.pdf = PDFEnum::PhasePDF(PhasePDF::new(&ray.direction, self.phase_function));
+        texture_service.value_at_hit(self.albedo_texture_index, hit, &mut scatter_out.attenuation)
+    }
+
+    fn scattering_pdf(&self, _rng: &mut ThreadRng, _material_service: &MaterialService, _texture_service: &TextureService, ray: &Ray, _hit: &HitRecord, scattered_ray:&Ray) -> f32 {
+        self.phase_function.evaluate(ray.direction.normalized().dot(scattered_ray.direction.normalized()))
+    }
+}
\ No newline at end of file
@@ -1,6 +1,6 @@
 use rand::rngs::ThreadRng;
 
-use crate::{core::ray::Ray, services::texture_service::TextureService, hittables::hit_record::HitRecord, math::utility::random_in_unit_sphere};
+use crate::{core::ray::Ray, services::{material_service::MaterialService, texture_service::TextureService}, hittables::hit_record::HitRecord, math::utility::random_in_unit_sphere};
 
 use super::{material::Material, scatter_record::ScatterRecord};
 
@@ -15,9 +15,9 @@ impl Isotropic {
 }
 
 impl Material for Isotropic {
-    fn scatter(&self, rng: &mut ThreadRng, texture_service: &TextureService, ray:&Ray, hit: &HitRecord, scatter_out: &mut ScatterRecord) -> bool{
+    fn scatter(&self, rng: &mut ThreadRng, _material_service: &MaterialService, texture_service: &TextureService, ray:&Ray, hit: &HitRecord, scatter_out: &mut ScatterRecord) -> bool{
         scatter_out.is_specular = true;
-        scatter_out.specular_ray = Ray{ origin: hit.position, direction: random_in_unit_sphere(rng), time: ray.time };
-        texture_service.value(self.albedo_texture_index, hit.u, hit.v, &hit.position, &mut scatter_out.attenuation)
+        scatter_out.specular_ray = Ray::new(hit.position, random_in_unit_sphere(rng), ray.time);
+        texture_service.value_at_hit(self.albedo_texture_index, hit, &mut scatter_out.attenuation)
     }
 }
\ No newline at end of file
@@ -1,7 +1,7 @@
 use std::f32::consts::PI;
 use rand::rngs::ThreadRng;
 
-use crate::{services::texture_service::TextureService, core::ray::Ray, hittables::hit_record::HitRecord, pdfs::{cosine_pdf::CosinePDF, pdf_enum::PDFEnum}};
+use crate::{services::{material_service::MaterialService, texture_service::TextureService}, core::ray::Ray, hittables::hit_record::HitRecord, pdfs::{cosine_pdf::CosinePDF, pdf_enum::PDFEnum}};
 use super::{material::Material, scatter_record::ScatterRecord};
 
 pub struct Lambertian {
@@ -15,15 +15,15 @@ impl Lambertian {
 }
 
 impl Material for Lambertian {
-    fn scatter(&self, _rng: &mut ThreadRng, texture_service: &TextureService, _ray:&Ray, hit: &HitRecord, scatter_out: &mut ScatterRecord) -> bool {
+    fn scatter(&self, _rng: &mut ThreadRng, _material_service: &MaterialService, texture_service: &TextureService, _ray:&Ray, hit: &HitRecord, scatter_out: &mut ScatterRecord) -> bool {
         scatter_out.is_specular = false;
-        texture_service.value(self.albedo_texture_index, hit.u, hit.v, &hit.position, &mut scatter_out.attenuation);
+        texture_service.value_at_hit(self.albedo_texture_index, hit, &mut scatter_out.attenuation);
         scatter_out.pdf = PDFEnum::CosinePDF(CosinePDF::new(&hit.normal));
 
         true
     }
 
-    fn scattering_pdf(&self, _rng: &mut ThreadRng, _ray: &Ray, hit: &HitRecord, scattered_ray:&Ray) -> f32 {
+    fn scattering_pdf(&self, _rng: &mut ThreadRng, _material_service: &MaterialService, _texture_service: &TextureService, _ray: &Ray, hit: &HitRecord, scattered_ray:&Ray) -> f32 {
         let cosine = hit.normal.dot(scattered_ray.direction.normalized());
 
         if cosine < 0.0 { 0.0 } else { cosine / PI }
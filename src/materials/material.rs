@@ -3,6 +3,7 @@ use rand::{rngs::ThreadRng};
 use crate::core::color_rgb::ColorRGB;
 use crate::hittables::hit_record::HitRecord;
 use crate::core::ray::Ray;
+use crate::services::material_service::MaterialService;
 use crate::services::texture_service::TextureService;
 
 use super::scatter_record::ScatterRecord;
@@ -10,18 +11,29 @@ use super::scatter_record::ScatterRecord;
 
 
 pub trait Material : Sync + Send {
-    fn emitted(&self, _texture_service: &TextureService, _ray:&Ray, _hit: &HitRecord, emitted_out: &mut ColorRGB) {
+    fn emitted(&self, _material_service: &MaterialService, _texture_service: &TextureService, _ray:&Ray, _hit: &HitRecord, emitted_out: &mut ColorRGB) {
         emitted_out.r = 0.0;
         emitted_out.g = 0.0;
         emitted_out.b = 0.0;
     }
 
-    fn scatter(&self, _rng: &mut ThreadRng, _texture_service: &TextureService, _ray:&Ray, _hit: &HitRecord, _scatter_out: &mut ScatterRecord) -> bool {
+    fn scatter(&self, _rng: &mut ThreadRng, _material_service: &MaterialService, _texture_service: &TextureService, _ray:&Ray, _hit: &HitRecord, _scatter_out: &mut ScatterRecord) -> bool {
         false
     }
 
-    fn scattering_pdf(&self, _rng: &mut ThreadRng, _ray: &Ray, _hit: &HitRecord, _scattered_ray: &Ray) -> f32 {
+    fn scattering_pdf(&self, _rng: &mut ThreadRng, _material_service: &MaterialService, _texture_service: &TextureService, _ray: &Ray, _hit: &HitRecord, _scattered_ray: &Ray) -> f32 {
         0.0
     }
+
+    // Direction dependent color of the scattered light, multiplied onto the attenuation from scatter.
+    // Only needed by materials whose color changes with the directions, like measured BRDFs.
+    fn scattering_tint(&self, _rng: &mut ThreadRng, _material_service: &MaterialService, _texture_service: &TextureService, _ray: &Ray, _hit: &HitRecord, _scattered_ray: &Ray) -> ColorRGB {
+        ColorRGB::white()
+    }
+
+    // Called right after the hit, before emitted and scatter, to replace the shading normal
+    fn perturb_normal(&self, _material_service: &MaterialService, _texture_service: &TextureService, _ray: &Ray, _hit: &mut HitRecord) {
+
+    }
 }
 
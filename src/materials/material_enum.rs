@@ -1,8 +1,8 @@
 use rand::rngs::ThreadRng;
 
-use crate::{services::texture_service::TextureService, core::{ray::Ray, color_rgb::ColorRGB}, hittables::hit_record::HitRecord};
+use crate::{services::{material_service::MaterialService, texture_service::TextureService}, core::{ray::Ray, color_rgb::ColorRGB}, hittables::hit_record::HitRecord};
 
-use super::{default_material::DefaultMaterial, lambertian::Lambertian, metal::Metal, dielectric::Dielectric, diffuse_light::DiffuseLight, isotropic::Isotropic, material::Material, scatter_record::ScatterRecord};
+use super::{default_material::DefaultMaterial, lambertian::Lambertian, metal::Metal, dielectric::Dielectric, diffuse_light::DiffuseLight, isotropic::Isotropic, oren_nayar::OrenNayar, blend::Blend, alpha_mask::AlphaMask, coated::Coated, thin_film::ThinFilm, subsurface::Subsurface, normal_map::NormalMap, bump_map::BumpMap, measured_brdf::MeasuredBRDF, sheen::Sheen, conductor::Conductor, henyey_greenstein::HenyeyGreenstein, rayleigh::Rayleigh, emissive_volume::EmissiveVolume, profiled_light::ProfiledLight, material::Material, scatter_record::ScatterRecord};
 
 pub enum MaterialEnum {
     DefaultMaterial(DefaultMaterial),
@@ -11,42 +11,156 @@ pub enum MaterialEnum {
     Dielectric(Dielectric),
     DiffuseLight(DiffuseLight),
     Isotropic(Isotropic),
+    OrenNayar(OrenNayar),
+    Blend(Blend),
+    AlphaMask(AlphaMask),
+    Coated(Coated),
+    ThinFilm(ThinFilm),
+    Subsurface(Subsurface),
+    NormalMap(NormalMap),
+    BumpMap(BumpMap),
+    MeasuredBRDF(MeasuredBRDF),
+    Sheen(Sheen),
+    Conductor(Conductor),
+    HenyeyGreenstein(HenyeyGreenstein),
+    Rayleigh(Rayleigh),
+    EmissiveVolume(EmissiveVolume),
+    ProfiledLight(ProfiledLight),
 }
 
 impl Material for MaterialEnum {
     #[inline]
-    fn emitted(&self, texture_service: &TextureService, ray:&Ray, hit: &HitRecord, emitted_out: &mut ColorRGB) {
+    fn emitted(&self, material_service: &MaterialService, texture_service: &TextureService, ray:&Ray, hit: &HitRecord, emitted_out: &mut ColorRGB) {
         match self {
-            MaterialEnum::DefaultMaterial(default) => default.emitted(texture_service, ray, hit, emitted_out),
-            MaterialEnum::Lambertian(lambertian) => lambertian.emitted(texture_service, ray, hit, emitted_out),
-            MaterialEnum::Metal(metal) => metal.emitted(texture_service, ray, hit, emitted_out),
-            MaterialEnum::Dielectric(dielectric) => dielectric.emitted(texture_service, ray, hit, emitted_out),
-            MaterialEnum::DiffuseLight(diffuse_light) => diffuse_light.emitted(texture_service, ray, hit, emitted_out),
-            MaterialEnum::Isotropic(isotropic) => isotropic.emitted(texture_service, ray, hit, emitted_out),
+            MaterialEnum::DefaultMaterial(default) => default.emitted(material_service, texture_service, ray, hit, emitted_out),
+            MaterialEnum::Lambertian(lambertian) => lambertian.emitted(material_service, texture_service, ray, hit, emitted_out),
+            MaterialEnum::Metal(metal) => metal.emitted(material_service, texture_service, ray, hit, emitted_out),
+            MaterialEnum::Dielectric(dielectric) => dielectric.emitted(material_service, texture_service, ray, hit, emitted_out),
+            MaterialEnum::DiffuseLight(diffuse_light) => diffuse_light.emitted(material_service, texture_service, ray, hit, emitted_out),
+            MaterialEnum::Isotropic(isotropic) => isotropic.emitted(material_service, texture_service, ray, hit, emitted_out),
+            MaterialEnum::OrenNayar(oren_nayar) => oren_nayar.emitted(material_service, texture_service, ray, hit, emitted_out),
+            MaterialEnum::Blend(blend) => blend.emitted(material_service, texture_service, ray, hit, emitted_out),
+            MaterialEnum::AlphaMask(alpha_mask) => alpha_mask.emitted(material_service, texture_service, ray, hit, emitted_out),
+            MaterialEnum::Coated(coated) => coated.emitted(material_service, texture_service, ray, hit, emitted_out),
+            MaterialEnum::ThinFilm(thin_film) => thin_film.emitted(material_service, texture_service, ray, hit, emitted_out),
+            MaterialEnum::Subsurface(subsurface) => subsurface.emitted(material_service, texture_service, ray, hit, emitted_out),
+            MaterialEnum::NormalMap(normal_map) => normal_map.emitted(material_service, texture_service, ray, hit, emitted_out),
+            MaterialEnum::BumpMap(bump_map) => bump_map.emitted(material_service, texture_service, ray, hit, emitted_out),
+            MaterialEnum::MeasuredBRDF(measured_brdf) => measured_brdf.emitted(material_service, texture_service, ray, hit, emitted_out),
+            MaterialEnum::Sheen(sheen) => sheen.emitted(material_service, texture_service, ray, hit, emitted_out),
+            MaterialEnum::Conductor(conductor) => conductor.emitted(material_service, texture_service, ray, hit, emitted_out),
+            MaterialEnum::HenyeyGreenstein(henyey_greenstein) => henyey_greenstein.emitted(material_service, texture_service, ray, hit, emitted_out),
+            MaterialEnum::Rayleigh(rayleigh) => rayleigh.emitted(material_service, texture_service, ray, hit, emitted_out),
+            MaterialEnum::EmissiveVolume(emissive_volume) => emissive_volume.emitted(material_service, texture_service, ray, hit, emitted_out),
+            MaterialEnum::ProfiledLight(profiled_light) => profiled_light.emitted(material_service, texture_service, ray, hit, emitted_out),
         }
     }
 
     #[inline]
-    fn scatter(&self, rng: &mut ThreadRng, texture_service: &TextureService, ray:&Ray, hit: &HitRecord, scatter_out: &mut ScatterRecord) -> bool {
+    fn scatter(&self, rng: &mut ThreadRng, material_service: &MaterialService, texture_service: &TextureService, ray:&Ray, hit: &HitRecord, scatter_out: &mut ScatterRecord) -> bool {
         match self {
-            MaterialEnum::DefaultMaterial(default) => default.scatter(rng, texture_service, ray, hit, scatter_out),
-            MaterialEnum::Lambertian(lambertian) => lambertian.scatter(rng, texture_service, ray, hit, scatter_out),
-            MaterialEnum::Metal(metal) => metal.scatter(rng, texture_service, ray, hit, scatter_out),
-            MaterialEnum::Dielectric(dielectric) => dielectric.scatter(rng, texture_service, ray, hit, scatter_out),
-            MaterialEnum::DiffuseLight(diffuse_light) => diffuse_light.scatter(rng, texture_service, ray, hit, scatter_out),
-            MaterialEnum::Isotropic(isotropic) => isotropic.scatter(rng, texture_service, ray, hit, scatter_out),
+            MaterialEnum::DefaultMaterial(default) => default.scatter(rng, material_service, texture_service, ray, hit, scatter_out),
+            MaterialEnum::Lambertian(lambertian) => lambertian.scatter(rng, material_service, texture_service, ray, hit, scatter_out),
+            MaterialEnum::Metal(metal) => metal.scatter(rng, material_service, texture_service, ray, hit, scatter_out),
+            MaterialEnum::Dielectric(dielectric) => dielectric.scatter(rng, material_service, texture_service, ray, hit, scatter_out),
+            MaterialEnum::DiffuseLight(diffuse_light) => diffuse_light.scatter(rng, material_service, texture_service, ray, hit, scatter_out),
+            MaterialEnum::Isotropic(isotropic) => isotropic.scatter(rng, material_service, texture_service, ray, hit, scatter_out),
+            MaterialEnum::OrenNayar(oren_nayar) => oren_nayar.scatter(rng, material_service, texture_service, ray, hit, scatter_out),
+            MaterialEnum::Blend(blend) => blend.scatter(rng, material_service, texture_service, ray, hit, scatter_out),
+            MaterialEnum::AlphaMask(alpha_mask) => alpha_mask.scatter(rng, material_service, texture_service, ray, hit, scatter_out),
+            MaterialEnum::Coated(coated) => coated.scatter(rng, material_service, texture_service, ray, hit, scatter_out),
+            MaterialEnum::ThinFilm(thin_film) => thin_film.scatter(rng, material_service, texture_service, ray, hit, scatter_out),
+            MaterialEnum::Subsurface(subsurface) => subsurface.scatter(rng, material_service, texture_service, ray, hit, scatter_out),
+            MaterialEnum::NormalMap(normal_map) => normal_map.scatter(rng, material_service, texture_service, ray, hit, scatter_out),
+            MaterialEnum::BumpMap(bump_map) => bump_map.scatter(rng, material_service, texture_service, ray, hit, scatter_out),
+            MaterialEnum::MeasuredBRDF(measured_brdf) => measured_brdf.scatter(rng, material_service, texture_service, ray, hit, scatter_out),
+            MaterialEnum::Sheen(sheen) => sheen.scatter(rng, material_service, texture_service, ray, hit, scatter_out),
+            MaterialEnum::Conductor(conductor) => conductor.scatter(rng, material_service, texture_service, ray, hit, scatter_out),
+            MaterialEnum::HenyeyGreenstein(henyey_greenstein) => henyey_greenstein.scatter(rng, material_service, texture_service, ray, hit, scatter_out),
+            MaterialEnum::Rayleigh(rayleigh) => rayleigh.scatter(rng, material_service, texture_service, ray, hit, scatter_out),
+            MaterialEnum::EmissiveVolume(emissive_volume) => emissive_volume.scatter(rng, material_service, texture_service, ray, hit, scatter_out),
+            MaterialEnum::ProfiledLight(profiled_light) => profiled_light.scatter(rng, material_service, texture_service, ray, hit, scatter_out),
         }
     }
 
     #[inline]
-    fn scattering_pdf(&self, rng: &mut ThreadRng, ray: &Ray, hit: &HitRecord, scattered_ray: &Ray) -> f32 {
+    fn scattering_pdf(&self, rng: &mut ThreadRng, material_service: &MaterialService, texture_service: &TextureService, ray: &Ray, hit: &HitRecord, scattered_ray: &Ray) -> f32 {
         match self {
-            MaterialEnum::DefaultMaterial(default) => default.scattering_pdf(rng, ray, hit, scattered_ray),
-            MaterialEnum::Lambertian(lambertian) => lambertian.scattering_pdf(rng, ray, hit, scattered_ray),
-            MaterialEnum::Metal(metal) => metal.scattering_pdf(rng, ray, hit, scattered_ray),
-            MaterialEnum::Dielectric(dielectric) => dielectric.scattering_pdf(rng, ray, hit, scattered_ray),
-            MaterialEnum::DiffuseLight(diffuse_light) => diffuse_light.scattering_pdf(rng, ray, hit, scattered_ray),
-            MaterialEnum::Isotropic(isotropic) => isotropic.scattering_pdf(rng, ray, hit, scattered_ray),
+            MaterialEnum::DefaultMaterial(default) => default.scattering_pdf(rng, material_service, texture_service, ray, hit, scattered_ray),
+            MaterialEnum::Lambertian(lambertian) => lambertian.scattering_pdf(rng, material_service, texture_service, ray, hit, scattered_ray),
+            MaterialEnum::Metal(metal) => metal.scattering_pdf(rng, material_service, texture_service, ray, hit, scattered_ray),
+            MaterialEnum::Dielectric(dielectric) => dielectric.scattering_pdf(rng, material_service, texture_service, ray, hit, scattered_ray),
+            MaterialEnum::DiffuseLight(diffuse_light) => diffuse_light.scattering_pdf(rng, material_service, texture_service, ray, hit, scattered_ray),
+            MaterialEnum::Isotropic(isotropic) => isotropic.scattering_pdf(rng, material_service, texture_service, ray, hit, scattered_ray),
+            MaterialEnum::OrenNayar(oren_nayar) => oren_nayar.scattering_pdf(rng, material_service, texture_service, ray, hit, scattered_ray),
+            MaterialEnum::Blend(blend) => blend.scattering_pdf(rng, material_service, texture_service, ray, hit, scattered_ray),
+            MaterialEnum::AlphaMask(alpha_mask) => alpha_mask.scattering_pdf(rng, material_service, texture_service, ray, hit, scattered_ray),
+            MaterialEnum::Coated(coated) => coated.scattering_pdf(rng, material_service, texture_service, ray, hit, scattered_ray),
+            MaterialEnum::ThinFilm(thin_film) => thin_film.scattering_pdf(rng, material_service, texture_service, ray, hit, scattered_ray),
+            MaterialEnum::Subsurface(subsurface) => subsurface.scattering_pdf(rng, material_service, texture_service, ray, hit, scattered_ray),
+            MaterialEnum::NormalMap(normal_map) => normal_map.scattering_pdf(rng, material_service, texture_service, ray, hit, scattered_ray),
+            MaterialEnum::BumpMap(bump_map) => bump_map.scattering_pdf(rng, material_service, texture_service, ray, hit, scattered_ray),
+            MaterialEnum::MeasuredBRDF(measured_brdf) => measured_brdf.scattering_pdf(rng, material_service, texture_service, ray, hit, scattered_ray),
+            MaterialEnum::Sheen(sheen) => sheen.scattering_pdf(rng, material_service, texture_service, ray, hit, scattered_ray),
+            MaterialEnum::Conductor(conductor) => conductor.scattering_pdf(rng, material_service, texture_service, ray, hit, scattered_ray),
+            MaterialEnum::HenyeyGreenstein(henyey_greenstein) => henyey_greenstein.scattering_pdf(rng, material_service, texture_service, ray, hit, scattered_ray),
+            MaterialEnum::Rayleigh(rayleigh) => rayleigh.scattering_pdf(rng, material_service, texture_service, ray, hit, scattered_ray),
+            MaterialEnum::EmissiveVolume(emissive_volume) => emissive_volume.scattering_pdf(rng, material_service, texture_service, ray, hit, scattered_ray),
+            MaterialEnum::ProfiledLight(profiled_light) => profiled_light.scattering_pdf(rng, material_service, texture_service, ray, hit, scattered_ray),
+        }
+    }
+
+    #[inline]
+    fn scattering_tint(&self, rng: &mut ThreadRng, material_service: &MaterialService, texture_service: &TextureService, ray: &Ray, hit: &HitRecord, scattered_ray: &Ray) -> ColorRGB {
+        match self {
+            MaterialEnum::DefaultMaterial(default) => default.scattering_tint(rng, material_service, texture_service, ray, hit, scattered_ray),
+            MaterialEnum::Lambertian(lambertian) => lambertian.scattering_tint(rng, material_service, texture_service, ray, hit, scattered_ray),
+            MaterialEnum::Metal(metal) => metal.scattering_tint(rng, material_service, texture_service, ray, hit, scattered_ray),
+            MaterialEnum::Dielectric(dielectric) => dielectric.scattering_tint(rng, material_service, texture_service, ray, hit, scattered_ray),
+            MaterialEnum::DiffuseLight(diffuse_light) => diffuse_light.scattering_tint(rng, material_service, texture_service, ray, hit, scattered_ray),
+            MaterialEnum::Isotropic(isotropic) => isotropic.scattering_tint(rng, material_service, texture_service, ray, hit, scattered_ray),
+            MaterialEnum::OrenNayar(oren_nayar) => oren_nayar.scattering_tint(rng, material_service, texture_service, ray, hit, scattered_ray),
+            MaterialEnum::Blend(blend) => blend.scattering_tint(rng, material_service, texture_service, ray, hit, scattered_ray),
+            MaterialEnum::AlphaMask(alpha_mask) => alpha_mask.scattering_tint(rng, material_service, texture_service, ray, hit, scattered_ray),
+            MaterialEnum::Coated(coated) => coated.scattering_tint(rng, material_service, texture_service, ray, hit, scattered_ray),
+            MaterialEnum::ThinFilm(thin_film) => thin_film.scattering_tint(rng, material_service, texture_service, ray, hit, scattered_ray),
+            MaterialEnum::Subsurface(subsurface) => subsurface.scattering_tint(rng, material_service, texture_service, ray, hit, scattered_ray),
+            MaterialEnum::NormalMap(normal_map) => normal_map.scattering_tint(rng, material_service, texture_service, ray, hit, scattered_ray),
+            MaterialEnum::BumpMap(bump_map) => bump_map.scattering_tint(rng, material_service, texture_service, ray, hit, scattered_ray),
+            MaterialEnum::MeasuredBRDF(measured_brdf) => measured_brdf.scattering_tint(rng, material_service, texture_service, ray, hit, scattered_ray),
+            MaterialEnum::Sheen(sheen) => sheen.scattering_tint(rng, material_service, texture_service, ray, hit, scattered_ray),
+            MaterialEnum::Conductor(conductor) => conductor.scattering_tint(rng, material_service, texture_service, ray, hit, scattered_ray),
+            MaterialEnum::HenyeyGreenstein(henyey_greenstein) => henyey_greenstein.scattering_tint(rng, material_service, texture_service, ray, hit, scattered_ray),
+            MaterialEnum::Rayleigh(rayleigh) => rayleigh.scattering_tint(rng, material_service, texture_service, ray, hit, scattered_ray),
+            MaterialEnum::EmissiveVolume(emissive_volume) => emissive_volume.scattering_tint(rng, material_service, texture_service, ray, hit, scattered_ray),
+            MaterialEnum::ProfiledLight(profiled_light) => profiled_light.scattering_tint(rng, material_service, texture_service, ray, hit, scattered_ray),
+        }
+    }
+
+    #[inline]
+    fn perturb_normal(&self, material_service: &MaterialService, texture_service: &TextureService, ray: &Ray, hit: &mut HitRecord) {
+        match self {
+            MaterialEnum::DefaultMaterial(default) => default.perturb_normal(material_service, texture_service, ray, hit),
+            MaterialEnum::Lambertian(lambertian) => lambertian.perturb_normal(material_service, texture_service, ray, hit),
+            MaterialEnum::Metal(metal) => metal.perturb_normal(material_service, texture_service, ray, hit),
+            MaterialEnum::Dielectric(dielectric) => dielectric.perturb_normal(material_service, texture_service, ray, hit),
+            MaterialEnum::DiffuseLight(diffuse_light) => diffuse_light.perturb_normal(material_service, texture_service, ray, hit),
+            MaterialEnum::Isotropic(isotropic) => isotropic.perturb_normal(material_service, texture_service, ray, hit),
+            MaterialEnum::OrenNayar(oren_nayar) => oren_nayar.perturb_normal(material_service, texture_service, ray, hit),
+            MaterialEnum::Blend(blend) => blend.perturb_normal(material_service, texture_service, ray, hit),
+            MaterialEnum::AlphaMask(alpha_mask) => alpha_mask.perturb_normal(material_service, texture_service, ray, hit),
+            MaterialEnum::Coated(coated) => coated.perturb_normal(material_service, texture_service, ray, hit),
+            MaterialEnum::ThinFilm(thin_film) => thin_film.perturb_normal(material_service, texture_service, ray, hit),
+            MaterialEnum::Subsurface(subsurface) => subsurface.perturb_normal(material_service, texture_service, ray, hit),
+            MaterialEnum::NormalMap(normal_map) => normal_map.perturb_normal(material_service, texture_service, ray, hit),
+            MaterialEnum::BumpMap(bump_map) => bump_map.perturb_normal(material_service, texture_service, ray, hit),
+            MaterialEnum::MeasuredBRDF(measured_brdf) => measured_brdf.perturb_normal(material_service, texture_service, ray, hit),
+            MaterialEnum::Sheen(sheen) => sheen.perturb_normal(material_service, texture_service, ray, hit),
+            MaterialEnum::Conductor(conductor) => conductor.perturb_normal(material_service, texture_service, ray, hit),
+            MaterialEnum::HenyeyGreenstein(henyey_greenstein) => henyey_greenstein.perturb_normal(material_service, texture_service, ray, hit),
+            MaterialEnum::Rayleigh(rayleigh) => rayleigh.perturb_normal(material_service, texture_service, ray, hit),
+            MaterialEnum::EmissiveVolume(emissive_volume) => emissive_volume.perturb_normal(material_service, texture_service, ray, hit),
+            MaterialEnum::ProfiledLight(profiled_light) => profiled_light.perturb_normal(material_service, texture_service, ray, hit),
         }
     }
 }
\ No newline at end of file
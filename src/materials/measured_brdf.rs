@@ -0,0 +1,214 @@
+use std::f32::consts::PI;
+use rand::rngs::ThreadRng;
+use ultraviolet::Vec3;
+
+use crate::{services::{material_service::MaterialService, texture_service::TextureService}, core::{ray::Ray, color_rgb::ColorRGB}, hittables::hit_record::HitRecord, pdfs::{cosine_pdf::CosinePDF, pdf_enum::PDFEnum}};
+use super::{material::Material, scatter_record::ScatterRecord};
+
+const THETA_HALF_RESOLUTION: usize = 90;
+const THETA_DIFF_RESOLUTION: usize = 90;
+const PHI_DIFF_RESOLUTION: usize = 180;
+const SAMPLE_COUNT: usize = THETA_HALF_RESOLUTION * THETA_DIFF_RESOLUTION * PHI_DIFF_RESOLUTION;
+
+const RED_SCALE: f32 = 1.0 / 1500.0;
+const GREEN_SCALE: f32 = 1.15 / 1500.0;
+const BLUE_SCALE: f32 = 1.66 / 1500.0;
+
+// Isotropic BRDF measured by MERL (Matusik et al. 2003), loaded from the .binary files of the database.
+// The table is indexed by the half angle/difference angle parameterization of Rusinkiewicz.
+// Directions are sampled from a cosine distribution, which is fine for the diffuse and glossy
+// materials of the database, but slow to converge for the mirror-like ones.
+pub struct MeasuredBRDF {
+    data: Vec<f32>,
+}
+
+impl MeasuredBRDF {
+    // The database isn't shipped with the renderer, so a missing or broken file is reported instead of panicking
+    pub fn load(path: &str) -> Result<Self, String> {
+        let bytes = std::fs::read(path).map_err(|error| format!("could not read measured brdf {}: {}", path, error))?;
+        Self::from_bytes(&bytes).map_err(|error| format!("{} {}", path, error))
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, String> {
+        if bytes.len() < 12 {
+            return Err("is missing its header".to_string());
+        }
+
+        let dimensions: Vec<usize> = bytes[0..12].chunks_exact(4).map(|chunk| i32::from_le_bytes(chunk.try_into().unwrap()) as usize).collect();
+        if dimensions[0] * dimensions[1] * dimensions[2] != SAMPLE_COUNT {
+            return Err(format!(
+                "has dimensions {}x{}x{}, expected {}x{}x{}", 
+                dimensions[0], dimensions[1], dimensions[2], 
+                THETA_HALF_RESOLUTION, THETA_DIFF_RESOLUTION, PHI_DIFF_RESOLUTION
+            ));
+        }
+        if bytes.len() != 12 + 3 * SAMPLE_COUNT * 8 {
+            return Err("is truncated".to_string());
+        }
+
+        // Stored as doubles, one block of samples per color channel
+        let data: Vec<f32> = bytes[12..].chunks_exact(8).map(|chunk| f64::from_le_bytes(chunk.try_into().unwrap()) as f32).collect();
+
+        Ok(MeasuredBRDF { data })
+    }
+
+    #[inline]
+    fn theta_half_index(theta_half: f32) -> usize {
+        if theta_half <= 0.0 {
+            return 0;
+        }
+
+        // The half angle is sampled more densely near the specular peak
+        let index = (theta_half / (PI * 0.5) * THETA_HALF_RESOLUTION as f32 * THETA_HALF_RESOLUTION as f32).sqrt() as usize;
+        index.min(THETA_HALF_RESOLUTION - 1)
+    }
+
+    #[inline]
+    fn theta_diff_index(theta_diff: f32) -> usize {
+        let index = (theta_diff / (PI * 0.5) * THETA_DIFF_RESOLUTION as f32).max(0.0) as usize;
+        index.min(THETA_DIFF_RESOLUTION - 1)
+    }
+
+    #[inline]
+    fn phi_diff_index(phi_diff: f32) -> usize {
+        // Reciprocity, phi_diff and phi_diff + PI are the same
+        let phi_diff = if phi_diff < 0.0 { phi_diff + PI } else { phi_diff };
+        let index = (phi_diff / PI * PHI_DIFF_RESOLUTION as f32).max(0.0) as usize;
+        index.min(PHI_DIFF_RESOLUTION - 1)
+    }
+
+    #[inline]
+    fn rotate(vector: &Vec3, axis: &Vec3, angle: f32) -> Vec3 {
+        let (sin, cos) = angle.sin_cos();
+        *vector * cos + *axis * (axis.dot(*vector) * (1.0 - cos)) + axis.cross(*vector) * sin
+    }
+
+    // Both directions are in the local frame of the surface, with the normal along z
+    pub fn evaluate(&self, incoming: &Vec3, outgoing: &Vec3) -> ColorRGB {
+        let half = (*incoming + *outgoing).normalized();
+        let theta_half = half.z.clamp(-1.0, 1.0).acos();
+        let phi_half = half.y.atan2(half.x);
+
+        let normal = Vec3::new(0.0, 0.0, 1.0);
+        let bitangent = Vec3::new(0.0, 1.0, 0.0);
+        let difference = Self::rotate(&Self::rotate(incoming, &normal, -phi_half), &bitangent, -theta_half);
+        let theta_diff = difference.z.clamp(-1.0, 1.0).acos();
+        let phi_diff = difference.y.atan2(difference.x);
+
+        let index = 
+            Self::phi_diff_index(phi_diff) + 
+            Self::theta_diff_index(theta_diff) * PHI_DIFF_RESOLUTION + 
+            Self::theta_half_index(theta_half) * PHI_DIFF_RESOLUTION * THETA_DIFF_RESOLUTION;
+
+        // Samples which could not be measured are stored as negative values
+        ColorRGB::new(
+            (self.data[index] * RED_SCALE).max(0.0),
+            (self.data[index + SAMPLE_COUNT] * GREEN_SCALE).max(0.0),
+            (self.data[index + 2 * SAMPLE_COUNT] * BLUE_SCALE).max(0.0),
+        )
+    }
+}
+
+impl Material for MeasuredBRDF {
+    fn scatter(&self, _rng: &mut ThreadRng, _material_service: &MaterialService, _texture_service: &TextureService, _ray:&Ray, hit: &HitRecord, scatter_out: &mut ScatterRecord) -> bool {
+        scatter_out.is_specular = false;
+        scatter_out.attenuation = ColorRGB::white();
+        scatter_out.pdf = PDFEnum::CosinePDF(CosinePDF::new(&hit.normal));
+
+        true
+    }
+
+    // Only the cosine term, the measured values come in through scattering_tint
+    fn scattering_pdf(&self, _rng: &mut ThreadRng, _material_service: &MaterialService, _texture_service: &TextureService, _ray: &Ray, hit: &HitRecord, scattered_ray:&Ray) -> f32 {
+        let cosine = hit.normal.dot(scattered_ray.direction.normalized());
+
+        if cosine < 0.0 { 0.0 } else { cosine }
+    }
+
+    fn scattering_tint(&self, _rng: &mut ThreadRng, _material_service: &MaterialService, _texture_service: &TextureService, ray: &Ray, hit: &HitRecord, scattered_ray:&Ray) -> ColorRGB {
+        let (tangent, bitangent) = hit.tangent_frame();
+        let to_local = |direction: Vec3| Vec3::new(direction.dot(tangent), direction.dot(bitangent), direction.dot(hit.normal));
+
+        let incoming = to_local(scattered_ray.direction.normalized());
+        let outgoing = to_local(-ray.direction.normalized());
+        if incoming.z <= 0.0 || outgoing.z <= 0.0 {
+            return ColorRGB::black();
+        }
+
+        self.evaluate(&incoming, &outgoing)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // A full size table where every sample holds its own index
+    fn indexed_table() -> MeasuredBRDF {
+        let mut bytes: Vec<u8> = Vec::with_capacity(12 + 3 * SAMPLE_COUNT * 8);
+        for dimension in [THETA_HALF_RESOLUTION, THETA_DIFF_RESOLUTION, PHI_DIFF_RESOLUTION] {
+            bytes.extend_from_slice(&(dimension as i32).to_le_bytes());
+        }
+        for _channel in 0..3 {
+            for index in 0..SAMPLE_COUNT {
+                bytes.extend_from_slice(&(index as f64).to_le_bytes());
+            }
+        }
+
+        MeasuredBRDF::from_bytes(&bytes).unwrap()
+    }
+
+    fn assert_sample(brdf: &MeasuredBRDF, incoming: Vec3, outgoing: Vec3, expected_index: usize) {
+        let value = brdf.evaluate(&incoming, &outgoing);
+        let index = expected_index as f32;
+        assert!((value.r - index * RED_SCALE).abs() < 1.0e-6 * index.max(1.0), "red {} for index {}", value.r, expected_index);
+        assert!((value.g - index * GREEN_SCALE).abs() < 1.0e-6 * index.max(1.0), "green {} for index {}", value.g, expected_index);
+        assert!((value.b - index * BLUE_SCALE).abs() < 1.0e-6 * index.max(1.0), "blue {} for index {}", value.b, expected_index);
+    }
+
+    fn direction(theta: f32, phi: f32) -> Vec3 {
+        Vec3::new(theta.sin() * phi.cos(), theta.sin() * phi.sin(), theta.cos())
+    }
+
+    #[test]
+    fn indexes_and_scales_tabulated_values() {
+        let brdf = indexed_table();
+
+        // Both along the normal, the very first sample
+        assert_sample(&brdf, Vec3::new(0.0, 0.0, 1.0), Vec3::new(0.0, 0.0, 1.0), 0);
+
+        // Mirrored around the normal, so the half vector is the normal and only theta_diff changes
+        let theta_diff = 45.5_f32.to_radians();
+        let incoming = direction(theta_diff, 0.0);
+        assert_sample(&brdf, incoming, Vec3::new(-incoming.x, -incoming.y, incoming.z), 45 * PHI_DIFF_RESOLUTION);
+
+        // The same direction twice, only theta_half changes, and its index is the square root of the angle
+        let theta_half = 20.5 * 20.5 / (THETA_HALF_RESOLUTION * THETA_HALF_RESOLUTION) as f32 * PI * 0.5;
+        assert_sample(&brdf, direction(theta_half, 0.3), direction(theta_half, 0.3), 20 * PHI_DIFF_RESOLUTION * THETA_DIFF_RESOLUTION);
+
+        // Swapping the directions gives the same sample
+        let incoming = direction(0.6, 0.2);
+        let outgoing = direction(0.9, 2.5);
+        let forward = brdf.evaluate(&incoming, &outgoing);
+        let backward = brdf.evaluate(&outgoing, &incoming);
+        assert_eq!((forward.r, forward.g, forward.b), (backward.r, backward.g, backward.b));
+    }
+
+    #[test]
+    fn rejects_wrong_dimensions_and_truncation() {
+        let mut bytes: Vec<u8> = Vec::new();
+        for dimension in [90_i32, 90, 90] {
+            bytes.extend_from_slice(&dimension.to_le_bytes());
+        }
+        assert!(MeasuredBRDF::from_bytes(&bytes).is_err());
+        assert!(MeasuredBRDF::from_bytes(&bytes[0..8]).is_err());
+
+        let mut bytes: Vec<u8> = Vec::new();
+        for dimension in [THETA_HALF_RESOLUTION, THETA_DIFF_RESOLUTION, PHI_DIFF_RESOLUTION] {
+            bytes.extend_from_slice(&(dimension as i32).to_le_bytes());
+        }
+        bytes.extend_from_slice(&[0; 64]);
+        assert!(MeasuredBRDF::from_bytes(&bytes).is_err());
+        assert!(MeasuredBRDF::load("brdfs/does-not-exist.binary").is_err());
+    }
+}
@@ -1,7 +1,7 @@
 use ultraviolet::Vec3;
 use rand::rngs::ThreadRng;
 
-use crate::{services::texture_service::TextureService, core::{ray::Ray, color_rgb::ColorRGB}, hittables::hit_record::HitRecord, pdfs::pdf_enum::PDFEnum, math::utility::{reflect, random_in_unit_sphere}};
+use crate::{services::{material_service::MaterialService, texture_service::TextureService}, core::{ray::Ray, color_rgb::ColorRGB}, hittables::hit_record::HitRecord, pdfs::pdf_enum::PDFEnum, math::utility::{reflect, random_in_unit_sphere}};
 
 use super::{material::Material, scatter_record::ScatterRecord};
 
@@ -17,7 +17,7 @@ impl Metal {
 }
 
 impl Material for Metal {
-    fn scatter(&self, rng: &mut ThreadRng, _texture_service: &TextureService, ray:&Ray, hit: &HitRecord, scatter_out: &mut ScatterRecord) -> bool {
+    fn scatter(&self, rng: &mut ThreadRng, _material_service: &MaterialService, _texture_service: &TextureService, ray:&Ray, hit: &HitRecord, scatter_out: &mut ScatterRecord) -> bool {
         let mut reflected: Vec3 = Vec3::default(); 
         reflect(&ray.direction.normalized(), &hit.normal, &mut reflected);
         scatter_out.specular_ray = Ray::new_normalized(hit.position, reflected + random_in_unit_sphere(rng) * self.fuzz, ray.time);
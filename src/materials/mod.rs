@@ -6,4 +6,19 @@ pub mod metal;
 pub mod dielectric;
 pub mod diffuse_light;
 pub mod isotropic;
+pub mod oren_nayar;
+pub mod blend;
+pub mod alpha_mask;
+pub mod coated;
+pub mod thin_film;
+pub mod subsurface;
+pub mod normal_map;
+pub mod bump_map;
+pub mod measured_brdf;
+pub mod sheen;
+pub mod conductor;
+pub mod henyey_greenstein;
+pub mod rayleigh;
+pub mod emissive_volume;
+pub mod profiled_light;
 pub mod material_enum;
\ No newline at end of file
@@ -0,0 +1,58 @@
+use rand::rngs::ThreadRng;
+use ultraviolet::Vec3;
+
+use crate::{services::{material_service::MaterialService, texture_service::TextureService}, core::{ray::Ray, color_rgb::ColorRGB}, hittables::hit_record::HitRecord};
+
+use super::{material::Material, scatter_record::ScatterRecord};
+
+// Tangent space normal map on top of any material. The texture stores the normal with every
+// component remapped from [-1, 1] to [0, 1], the usual flat blue look of normal maps.
+// Strength scales the tilt, 0 leaves the surface flat.
+pub struct NormalMap {
+    pub material: usize,
+    pub normal_texture_index: usize,
+    pub strength: f32,
+}
+
+impl NormalMap {
+    pub fn new(material: usize, normal_texture_index: usize, strength: f32) -> NormalMap {
+        NormalMap { material, normal_texture_index, strength }
+    }
+}
+
+impl Material for NormalMap {
+    fn emitted(&self, material_service: &MaterialService, texture_service: &TextureService, ray:&Ray, hit: &HitRecord, emitted_out: &mut ColorRGB) {
+        material_service.emitted_by_index(self.material, texture_service, ray, hit, emitted_out);
+    }
+
+    fn scatter(&self, rng: &mut ThreadRng, material_service: &MaterialService, texture_service: &TextureService, ray:&Ray, hit: &HitRecord, scatter_out: &mut ScatterRecord) -> bool {
+        if !material_service.scatter_by_index(self.material, rng, texture_service, ray, hit, scatter_out) {
+            return false;
+        }
+
+        if scatter_out.is_specular {
+            scatter_out.specular_ray.direction = hit.make_consistent(&scatter_out.specular_ray.direction);
+        }
+
+        true
+    }
+
+    fn perturb_normal(&self, material_service: &MaterialService, texture_service: &TextureService, ray: &Ray, hit: &mut HitRecord) {
+        let mut color: ColorRGB = ColorRGB::black();
+        texture_service.value_at_hit(self.normal_texture_index, hit, &mut color);
+
+        let tangent_space_normal = Vec3::new(
+            (2.0 * color.r - 1.0) * self.strength, 
+            (2.0 * color.g - 1.0) * self.strength, 
+            (2.0 * color.b - 1.0).max(0.0)
+        );
+
+        let (tangent, bitangent) = hit.tangent_frame();
+        let normal = tangent * tangent_space_normal.x + bitangent * tangent_space_normal.y + hit.normal * tangent_space_normal.z;
+        if 0.0 < normal.mag_sq() {
+            hit.set_shading_normal(ray, &normal);
+        }
+
+        material_service.perturb_normal_by_index(self.material, texture_service, ray, hit);
+    }
+}
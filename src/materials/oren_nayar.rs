@@ -0,0 +1,64 @@
+use std::f32::consts::PI;
+use rand::rngs::ThreadRng;
+
+use crate::{services::{material_service::MaterialService, texture_service::TextureService}, core::{ray::Ray, color_rgb::ColorRGB}, hittables::hit_record::HitRecord, pdfs::{cosine_pdf::CosinePDF, pdf_enum::PDFEnum}};
+use super::{material::Material, scatter_record::ScatterRecord};
+
+// Rough diffuse surface. The red channel of the sigma texture is the standard
+// deviation of the microfacet orientation angle in radians, 0 gives Lambertian.
+pub struct OrenNayar {
+    pub albedo_texture_index: usize,
+    pub sigma_texture_index: usize,
+}
+
+impl OrenNayar {
+    pub fn new(albedo_index: usize, sigma_index: usize) -> Self {
+        OrenNayar { albedo_texture_index: albedo_index, sigma_texture_index: sigma_index }
+    }
+}
+
+impl Material for OrenNayar {
+    fn scatter(&self, _rng: &mut ThreadRng, _material_service: &MaterialService, texture_service: &TextureService, _ray:&Ray, hit: &HitRecord, scatter_out: &mut ScatterRecord) -> bool {
+        scatter_out.is_specular = false;
+        texture_service.value_at_hit(self.albedo_texture_index, hit, &mut scatter_out.attenuation);
+        scatter_out.pdf = PDFEnum::CosinePDF(CosinePDF::new(&hit.normal));
+
+        true
+    }
+
+    fn scattering_pdf(&self, _rng: &mut ThreadRng, _material_service: &MaterialService, texture_service: &TextureService, ray: &Ray, hit: &HitRecord, scattered_ray:&Ray) -> f32 {
+        let incoming = scattered_ray.direction.normalized();
+        let outgoing = -ray.direction.normalized();
+
+        let cos_theta_i = hit.normal.dot(incoming);
+        if cos_theta_i <= 0.0 { return 0.0; }
+        let cos_theta_o = hit.normal.dot(outgoing).max(0.0);
+
+        let mut sigma: ColorRGB = ColorRGB::black();
+        texture_service.value_at_hit(self.sigma_texture_index, hit, &mut sigma);
+        let sigma_squared = sigma.r * sigma.r;
+        let a = 1.0 - 0.5 * sigma_squared / (sigma_squared + 0.33);
+        let b = 0.45 * sigma_squared / (sigma_squared + 0.09);
+
+        let sin_theta_i = (1.0 - cos_theta_i * cos_theta_i).max(0.0).sqrt();
+        let sin_theta_o = (1.0 - cos_theta_o * cos_theta_o).max(0.0).sqrt();
+
+        // Cosine of the azimuthal difference, found by projecting both directions onto the tangent plane
+        let mut max_cos_phi = 0.0;
+        if 1.0e-4 < sin_theta_i && 1.0e-4 < sin_theta_o {
+            let tangent_incoming = incoming - hit.normal * cos_theta_i;
+            let tangent_outgoing = outgoing - hit.normal * cos_theta_o;
+            max_cos_phi = (tangent_incoming.dot(tangent_outgoing) / (sin_theta_i * sin_theta_o)).max(0.0);
+        }
+
+        let (sin_alpha, tan_beta) =
+            if cos_theta_o < cos_theta_i {
+                (sin_theta_o, sin_theta_i / cos_theta_i)
+            } else {
+                (sin_theta_i, sin_theta_o / cos_theta_o.max(1.0e-4))
+            };
+
+        cos_theta_i / PI * (a + b * max_cos_phi * sin_alpha * tan_beta)
+    }
+
+}
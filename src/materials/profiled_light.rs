@@ -0,0 +1,43 @@
+use ultraviolet::Vec3;
+
+use crate::{core::{ray::Ray, color_rgb::ColorRGB}, hittables::hit_record::HitRecord, lights::ies_profile::{IesProfile, fixture_frame}, services::{material_service::MaterialService, texture_service::TextureService}};
+
+use super::material::Material;
+
+// An area emitter following a photometric profile, pointing along the front face normal,
+// with its length along the direction of increasing u.
+// The whole surface counts as the luminous opening of the fixture, so its radiance is the profile's intensity
+// divided by the projected area. radiance_per_candela can be found with PhotometricUnits::area_radiance_per_candela.
+pub struct ProfiledLight {
+    pub profile: IesProfile,
+    pub radiance_per_candela: ColorRGB,
+}
+
+impl ProfiledLight {
+    pub fn new(profile: IesProfile, radiance_per_candela: ColorRGB) -> ProfiledLight {
+        ProfiledLight { profile, radiance_per_candela }
+    }
+}
+
+impl Material for ProfiledLight {
+    fn emitted(&self, _material_service: &MaterialService, _texture_service: &TextureService, ray:&Ray, hit: &HitRecord, emitted_out: &mut ColorRGB) {
+        emitted_out.r = 0.0;
+        emitted_out.g = 0.0;
+        emitted_out.b = 0.0;
+
+        if !hit.is_front_face {
+            return;
+        }
+
+        let direction: Vec3 = -ray.direction.normalized();
+        let cosine = direction.dot(hit.normal);
+        if cosine <= 0.0 {
+            return;
+        }
+
+        let candela = self.profile.candela_in_direction(&fixture_frame(&hit.normal, &hit.tangent), &direction);
+
+        // Keep grazing angles from blowing up, where the projected area goes to zero
+        *emitted_out = self.radiance_per_candela * (candela / cosine.max(0.01));
+    }
+}
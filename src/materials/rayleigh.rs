@@ -0,0 +1,29 @@
+use rand::rngs::ThreadRng;
+
+use crate::{services::{material_service::MaterialService, texture_service::TextureService}, core::ray::Ray, hittables::hit_record::HitRecord, math::phase_function::PhaseFunction, pdfs::{phase_pdf::PhasePDF, pdf_enum::PDFEnum}};
+use super::{material::Material, scatter_record::ScatterRecord};
+
+// Phase function of particles much smaller than the wavelength, like the molecules of air.
+// Scatters equally forward and backward, less to the sides. The wavelength dependence
+// which makes the sky blue belongs in the albedo.
+pub struct Rayleigh {
+    pub albedo_texture_index: usize,
+}
+
+impl Rayleigh {
+    pub fn new(albedo_index: usize) -> Self {
+        Rayleigh { albedo_texture_index: albedo_index }
+    }
+}
+
+impl Material for Rayleigh {
+    fn scatter(&self, _rng: &mut ThreadRng, _material_service: &MaterialService, texture_service: &TextureService, ray:&Ray, hit: &HitRecord, scatter_out: &mut ScatterRecord) -> bool {
+        scatter_out.is_specular = false;
+        scatter_out.pdf = PDFEnum::PhasePDF(PhasePDF::new(&ray.direction, PhaseFunction::Rayleigh));
+        texture_service.value_at_hit(self.albedo_texture_index, hit, &mut scatter_out.attenuation)
+    }
+
+    fn scattering_pdf(&self, _rng: &mut ThreadRng, _material_service: &MaterialService, _texture_service: &TextureService, ray: &Ray, _hit: &HitRecord, scattered_ray:&Ray) -> f32 {
+        PhaseFunction::Rayleigh.evaluate(ray.direction.normalized().dot(scattered_ray.direction.normalized()))
+    }
+}
\ No newline at end of file
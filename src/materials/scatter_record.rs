@@ -5,6 +5,7 @@ pub struct ScatterRecord {
     pub is_specular: bool,
     pub attenuation: ColorRGB,
     pub pdf: PDFEnum, // Try to remove this box
+    pub material: usize, // The material which actually scattered, blends pick one of their components
 }
 
 impl ScatterRecord {
@@ -14,6 +15,7 @@ impl ScatterRecord {
             is_specular: false, 
             attenuation: ColorRGB::default(), 
             pdf: PDFEnum::None(),
+            material: 0,
         }
     }
 }
\ No newline at end of file
@@ -0,0 +1,181 @@
+use std::f32::consts::PI;
+use ultraviolet::Vec3;
+use rand::rngs::ThreadRng;
+
+use crate::{services::{material_service::MaterialService, texture_service::TextureService}, core::{ray::Ray, color_rgb::ColorRGB}, hittables::hit_record::HitRecord, pdfs::{cosine_pdf::CosinePDF, pdf_enum::PDFEnum}};
+use super::{material::Material, scatter_record::ScatterRecord};
+
+const ALBEDO_TABLE_SIZE: usize = 16;
+const ALBEDO_INTEGRATION_STEPS: usize = 64;
+
+// Cloth, velvet and other fabrics. A diffuse base with the Charlie sheen lobe of Estevez and Kulla on top,
+// which lights up at grazing angles where fibers stick out of the surface.
+// The red channel of the roughness texture controls how far the sheen spreads, 1 is soft velvet.
+// Light reflected by the sheen never reaches the base, so the base is scaled by one minus the
+// directional albedo of the sheen lobe, tabulated over the outgoing angle and roughness.
+pub struct Sheen {
+    pub albedo_texture_index: usize,
+    pub sheen_texture_index: usize,
+    pub roughness_texture_index: usize,
+    sheen_albedo_table: Vec<f32>, // Rows of roughness, columns of the outgoing cosine
+}
+
+impl Sheen {
+    pub fn new(albedo_texture_index: usize, sheen_texture_index: usize, roughness_texture_index: usize) -> Self {
+        let mut sheen_albedo_table: Vec<f32> = Vec::with_capacity(ALBEDO_TABLE_SIZE * ALBEDO_TABLE_SIZE);
+        for alpha_index in 0..ALBEDO_TABLE_SIZE {
+            let alpha = Self::table_coordinate(alpha_index);
+            for cos_index in 0..ALBEDO_TABLE_SIZE {
+                sheen_albedo_table.push(Self::directional_albedo(alpha, Self::table_coordinate(cos_index), ALBEDO_INTEGRATION_STEPS));
+            }
+        }
+
+        Sheen { albedo_texture_index, sheen_texture_index, roughness_texture_index, sheen_albedo_table }
+    }
+
+    #[inline]
+    fn table_coordinate(index: usize) -> f32 {
+        (index as f32 / (ALBEDO_TABLE_SIZE - 1) as f32).max(1.0e-3)
+    }
+
+    // Midpoint integration of the sheen lobe times the cosine over the hemisphere, with the normal along z
+    fn directional_albedo(alpha: f32, cos_theta_o: f32, steps: usize) -> f32 {
+        let outgoing = Vec3::new((1.0 - cos_theta_o * cos_theta_o).max(0.0).sqrt(), 0.0, cos_theta_o);
+        let cos_step = 1.0 / steps as f32;
+        let phi_step = 2.0 * PI / (2 * steps) as f32;
+
+        let mut albedo = 0.0;
+        for cos_index in 0..steps {
+            let cos_theta_i = (cos_index as f32 + 0.5) * cos_step;
+            let sin_theta_i = (1.0 - cos_theta_i * cos_theta_i).sqrt();
+            for phi_index in 0..(2 * steps) {
+                let phi = (phi_index as f32 + 0.5) * phi_step;
+                let incoming = Vec3::new(sin_theta_i * phi.cos(), sin_theta_i * phi.sin(), cos_theta_i);
+                let half = (incoming + outgoing).normalized();
+                albedo += Self::charlie_distribution(alpha, half.z) * Self::visibility(cos_theta_i, cos_theta_o) * cos_theta_i;
+            }
+        }
+
+        (albedo * cos_step * phi_step).min(1.0)
+    }
+
+    // Bilinear lookup in the tabulated directional albedo
+    fn sheen_albedo(&self, alpha: f32, cos_theta_o: f32) -> f32 {
+        let scale = (ALBEDO_TABLE_SIZE - 1) as f32;
+        let x = cos_theta_o.clamp(0.0, 1.0) * scale;
+        let y = alpha.clamp(0.0, 1.0) * scale;
+        let x0 = (x as usize).min(ALBEDO_TABLE_SIZE - 2);
+        let y0 = (y as usize).min(ALBEDO_TABLE_SIZE - 2);
+        let (tx, ty) = (x - x0 as f32, y - y0 as f32);
+
+        let at = |x: usize, y: usize| self.sheen_albedo_table[y * ALBEDO_TABLE_SIZE + x];
+        let bottom = at(x0, y0) * (1.0 - tx) + at(x0 + 1, y0) * tx;
+        let top = at(x0, y0 + 1) * (1.0 - tx) + at(x0 + 1, y0 + 1) * tx;
+
+        bottom * (1.0 - ty) + top * ty
+    }
+
+    #[inline]
+    fn charlie_distribution(alpha: f32, cos_theta_half: f32) -> f32 {
+        let inverse_alpha = 1.0 / alpha;
+        let sin_squared = (1.0 - cos_theta_half * cos_theta_half).max(0.0);
+
+        (2.0 + inverse_alpha) * sin_squared.powf(inverse_alpha * 0.5) / (2.0 * PI)
+    }
+
+    // Visibility term of Neubelt and Pettineo
+    #[inline]
+    fn visibility(cos_theta_i: f32, cos_theta_o: f32) -> f32 {
+        1.0 / (4.0 * (cos_theta_i + cos_theta_o - cos_theta_i * cos_theta_o))
+    }
+}
+
+impl Material for Sheen {
+    fn scatter(&self, _rng: &mut ThreadRng, _material_service: &MaterialService, _texture_service: &TextureService, _ray:&Ray, hit: &HitRecord, scatter_out: &mut ScatterRecord) -> bool {
+        scatter_out.is_specular = false;
+        scatter_out.attenuation = ColorRGB::white();
+        scatter_out.pdf = PDFEnum::CosinePDF(CosinePDF::new(&hit.normal));
+
+        true
+    }
+
+    // Only the cosine term, the two lobes come in through scattering_tint
+    fn scattering_pdf(&self, _rng: &mut ThreadRng, _material_service: &MaterialService, _texture_service: &TextureService, _ray: &Ray, hit: &HitRecord, scattered_ray:&Ray) -> f32 {
+        let cosine = hit.normal.dot(scattered_ray.direction.normalized());
+
+        if cosine < 0.0 { 0.0 } else { cosine }
+    }
+
+    fn scattering_tint(&self, _rng: &mut ThreadRng, _material_service: &MaterialService, texture_service: &TextureService, ray: &Ray, hit: &HitRecord, scattered_ray:&Ray) -> ColorRGB {
+        let incoming = scattered_ray.direction.normalized();
+        let outgoing = -ray.direction.normalized();
+
+        let cos_theta_i = hit.normal.dot(incoming);
+        let cos_theta_o = hit.normal.dot(outgoing);
+        if cos_theta_i <= 0.0 || cos_theta_o <= 0.0 {
+            return ColorRGB::black();
+        }
+
+        let mut albedo: ColorRGB = ColorRGB::black();
+        texture_service.value_at_hit(self.albedo_texture_index, hit, &mut albedo);
+
+        let mut sheen: ColorRGB = ColorRGB::black();
+        texture_service.value_at_hit(self.sheen_texture_index, hit, &mut sheen);
+
+        let mut roughness: ColorRGB = ColorRGB::black();
+        texture_service.value_at_hit(self.roughness_texture_index, hit, &mut roughness);
+        let alpha = (roughness.r * roughness.r).clamp(1.0e-3, 1.0);
+
+        let half = (incoming + outgoing).normalized();
+        let sheen_lobe = Self::charlie_distribution(alpha, hit.normal.dot(half)) * Self::visibility(cos_theta_i, cos_theta_o);
+
+        let sheen_strength = sheen.r.max(sheen.g).max(sheen.b);
+        let base_scale = 1.0 - sheen_strength * self.sheen_albedo(alpha, cos_theta_o);
+
+        albedo * (base_scale / PI) + sheen * sheen_lobe
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sheen_albedo_stays_below_one() {
+        let sheen = Sheen::new(0, 0, 0);
+
+        for alpha in [0.01, 0.1, 0.4, 0.7, 1.0] {
+            for cos_theta_o in [0.02, 0.1, 0.3, 0.6, 1.0] {
+                let albedo = sheen.sheen_albedo(alpha, cos_theta_o);
+                assert!((0.0..=1.0).contains(&albedo), "albedo {} at alpha {} and cosine {}", albedo, alpha, cos_theta_o);
+            }
+        }
+    }
+
+    #[test]
+    fn white_sheen_on_a_white_base_conserves_energy() {
+        let sheen = Sheen::new(0, 0, 0);
+
+        // A finer integration than the table, the compensated base and the sheen add up to about 1
+        for alpha in [0.16, 0.64, 1.0] {
+            for cos_theta_o in [0.05, 0.25, 0.5, 0.9] {
+                let sheen_albedo = Sheen::directional_albedo(alpha, cos_theta_o, 128);
+                let base_albedo = 1.0 - sheen.sheen_albedo(alpha, cos_theta_o);
+                let total = sheen_albedo + base_albedo;
+
+                assert!((0.95..=1.02).contains(&total), "total albedo {} at alpha {} and cosine {}", total, alpha, cos_theta_o);
+            }
+        }
+    }
+
+    #[test]
+    fn table_matches_the_integral_at_grid_points() {
+        let sheen = Sheen::new(0, 0, 0);
+
+        let alpha = Sheen::table_coordinate(5);
+        let cos_theta_o = Sheen::table_coordinate(3);
+        let expected = Sheen::directional_albedo(alpha, cos_theta_o, ALBEDO_INTEGRATION_STEPS);
+
+        assert!((sheen.sheen_albedo(alpha, cos_theta_o) - expected).abs() < 1.0e-5);
+    }
+}
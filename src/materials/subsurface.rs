@@ -0,0 +1,47 @@
+use rand::rngs::ThreadRng;
+
+use crate::{core::{ray::Ray, color_rgb::ColorRGB}, services::{material_service::MaterialService, texture_service::TextureService}, hittables::hit_record::HitRecord, math::utility::random_in_unit_sphere};
+
+use super::{material::Material, scatter_record::ScatterRecord};
+
+// Phase function for the inside of skin, wax, marble and the like. Used as the phase function of a
+// ConstantMedium with density 1 / mean free path inside a closed boundary with a Dielectric material,
+// the path tracer then does the random walk and rays leave through the dielectric boundary.
+// The albedo texture is the color the surface should end up with after multiple scattering,
+// which is converted to the single scattering albedo of the medium.
+pub struct Subsurface {
+    pub albedo_texture_index: usize,
+}
+
+impl Subsurface {
+    pub fn new(texture_index: usize) -> Subsurface {
+        Subsurface { albedo_texture_index: texture_index }
+    }
+
+    // Inversion of multiple scattering albedo to single scattering albedo for an isotropic random walk,
+    // fitted by Chiang et al. in "Practical and Controllable Subsurface Scattering for Production Path Tracing"
+    #[inline]
+    fn single_scattering_albedo(multiple_scattering_albedo: f32) -> f32 {
+        let a = multiple_scattering_albedo.clamp(0.0, 0.999);
+        let term = 4.09712 + 4.20863 * a - (9.59217 + 41.6808 * a + 17.7126 * a * a).sqrt();
+
+        1.0 - term * term
+    }
+}
+
+impl Material for Subsurface {
+    fn scatter(&self, rng: &mut ThreadRng, _material_service: &MaterialService, texture_service: &TextureService, ray:&Ray, hit: &HitRecord, scatter_out: &mut ScatterRecord) -> bool {
+        let mut surface_albedo: ColorRGB = ColorRGB::black();
+        texture_service.value_at_hit(self.albedo_texture_index, hit, &mut surface_albedo);
+
+        scatter_out.is_specular = true;
+        scatter_out.specular_ray = Ray::new(hit.position, random_in_unit_sphere(rng), ray.time);
+        scatter_out.attenuation = ColorRGB::new(
+            Subsurface::single_scattering_albedo(surface_albedo.r),
+            Subsurface::single_scattering_albedo(surface_albedo.g),
+            Subsurface::single_scattering_albedo(surface_albedo.b),
+        );
+
+        true
+    }
+}
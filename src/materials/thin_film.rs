@@ -0,0 +1,85 @@
+use ultraviolet::Vec3;
+use rand::{rngs::ThreadRng, Rng};
+
+use crate::{services::{material_service::MaterialService, texture_service::TextureService}, core::{ray::Ray, color_rgb::ColorRGB}, hittables::hit_record::HitRecord, pdfs::pdf_enum::PDFEnum, math::{utility::{reflect, refract}, fresnel::thin_film_reflectance}};
+
+use super::{material::Material, scatter_record::ScatterRecord};
+
+// Representative wavelengths in nanometers for the red, green and blue channels
+const RGB_WAVELENGTHS: [f32; 3] = [650.0, 550.0, 450.0];
+
+// Iridescent thin film, like soap bubbles, oil slicks and anodized metal.
+// The film thickness in nanometers is the red channel of the thickness texture times max_thickness.
+// A base with zero extinction (base_k) is a dielectric which transmits, otherwise it is a conductor.
+pub struct ThinFilm {
+    pub thickness_texture_index: usize,
+    pub max_thickness: f32,
+    pub film_index_of_refraction: f32,
+    pub base_eta: ColorRGB,
+    pub base_k: ColorRGB,
+}
+
+impl ThinFilm {
+    pub fn over_dielectric(thickness_texture_index: usize, max_thickness: f32, film_index_of_refraction: f32, base_index_of_refraction: f32) -> ThinFilm {
+        ThinFilm { 
+            thickness_texture_index, 
+            max_thickness, 
+            film_index_of_refraction, 
+            base_eta: ColorRGB::new(base_index_of_refraction, base_index_of_refraction, base_index_of_refraction), 
+            base_k: ColorRGB::black(),
+        }
+    }
+
+    pub fn over_conductor(thickness_texture_index: usize, max_thickness: f32, film_index_of_refraction: f32, base_eta: ColorRGB, base_k: ColorRGB) -> ThinFilm {
+        ThinFilm { thickness_texture_index, max_thickness, film_index_of_refraction, base_eta, base_k }
+    }
+}
+
+impl Material for ThinFilm {
+    fn scatter(&self, rng: &mut ThreadRng, _material_service: &MaterialService, texture_service: &TextureService, ray:&Ray, hit: &HitRecord, scatter_out: &mut ScatterRecord) -> bool {
+        scatter_out.is_specular = true;
+        scatter_out.pdf = PDFEnum::None();
+
+        let mut thickness: ColorRGB = ColorRGB::black();
+        texture_service.value_at_hit(self.thickness_texture_index, hit, &mut thickness);
+        let film_thickness = thickness.r.max(0.0) * self.max_thickness;
+
+        let unit_direction: Vec3 = ray.direction.normalized();
+        let cos_theta = (-unit_direction).dot(hit.normal).min(1.0);
+
+        let is_conductor = !self.base_k.is_black();
+        let mut reflectance: ColorRGB = ColorRGB::black();
+        for channel in 0..3 {
+            // Leaving a dielectric base the film is seen from the inside
+            let (incident_eta, base_eta) = if hit.is_front_face || is_conductor { (1.0, self.base_eta[channel]) } else { (self.base_eta[channel], 1.0) };
+            reflectance[channel] = thin_film_reflectance(cos_theta, incident_eta, self.film_index_of_refraction, film_thickness, base_eta, self.base_k[channel], RGB_WAVELENGTHS[channel]);
+        }
+
+        let mut direction: Vec3 = Vec3::zero();
+        reflect(&unit_direction, &hit.normal, &mut direction);
+
+        if is_conductor {
+            scatter_out.attenuation = reflectance;
+            scatter_out.specular_ray = Ray::new_normalized(hit.position, direction, ray.time);
+            return true;
+        }
+
+        // Pick reflection or transmission by the average reflectance and reweight each channel
+        let reflection_probability = ((reflectance.r + reflectance.g + reflectance.b) / 3.0).clamp(0.001, 0.999);
+        if rng.gen::<f32>() < reflection_probability {
+            scatter_out.attenuation = reflectance / reflection_probability;
+        } else {
+            let base_index_of_refraction = (self.base_eta.r + self.base_eta.g + self.base_eta.b) / 3.0;
+            let refraction_ratio = if hit.is_front_face { 1.0 / base_index_of_refraction } else { base_index_of_refraction };
+            let sin_theta = (1.0 - cos_theta * cos_theta).sqrt();
+            if refraction_ratio * sin_theta <= 1.0 {
+                refract(&unit_direction, &hit.normal, refraction_ratio, &mut direction);
+            }
+            scatter_out.attenuation = (ColorRGB::white() - reflectance) / (1.0 - reflection_probability);
+        }
+
+        scatter_out.specular_ray = Ray::new_normalized(hit.position, direction, ray.time);
+
+        true
+    }
+}
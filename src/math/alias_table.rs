@@ -0,0 +1,122 @@
+// Walker/Vose alias table for picking one of many discrete options in constant time,
+// with a probability proportional to its weight.
+pub struct AliasTable {
+    probability: Vec<f32>,
+    alias: Vec<usize>,
+    pmf: Vec<f32>,
+}
+
+impl AliasTable {
+    pub fn new(weights: &[f32]) -> AliasTable {
+        let count = weights.len();
+        let total: f32 = weights.iter().map(|weight| weight.max(0.0)).sum();
+
+        let pmf: Vec<f32> =
+            if total <= 0.0 {
+                // Nothing to go by, fall back to uniform
+                vec![1.0 / count as f32; count]
+            } else {
+                weights.iter().map(|weight| weight.max(0.0) / total).collect()
+            };
+
+        let mut probability: Vec<f32> = pmf.iter().map(|value| value * count as f32).collect();
+        let mut alias: Vec<usize> = (0..count).collect();
+
+        let mut small: Vec<usize> = Vec::new();
+        let mut large: Vec<usize> = Vec::new();
+        for (index, value) in probability.iter().enumerate() {
+            if *value < 1.0 { small.push(index); } else { large.push(index); }
+        }
+
+        while !small.is_empty() && !large.is_empty() {
+            let small_index = small.pop().unwrap();
+            let large_index = large.pop().unwrap();
+
+            alias[small_index] = large_index;
+            probability[large_index] = (probability[large_index] + probability[small_index]) - 1.0;
+
+            if probability[large_index] < 1.0 { small.push(large_index); } else { large.push(large_index); }
+        }
+
+        // Whatever is left over is only off from 1 by rounding
+        for index in small.into_iter().chain(large) {
+            probability[index] = 1.0;
+        }
+
+        AliasTable { probability, alias, pmf }
+    }
+
+    #[inline]
+    pub fn count(&self) -> usize {
+        self.pmf.len()
+    }
+
+    // Picks an index from a single uniform sample in [0, 1)
+    pub fn sample(&self, u: f32) -> usize {
+        let scaled = u * self.count() as f32;
+        let index = (scaled as usize).min(self.count() - 1);
+        let remainder = scaled - index as f32;
+
+        if remainder < self.probability[index] { index } else { self.alias[index] }
+    }
+
+    #[inline]
+    pub fn pmf(&self, index: usize) -> f32 {
+        self.pmf[index]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sample_frequencies_match_weights() {
+        let weights = [1.0, 0.0, 4.0, 2.5, 0.5, 2.0];
+        let total: f32 = weights.iter().sum();
+        let table = AliasTable::new(&weights);
+
+        let sample_count = 600_000;
+        let mut counts = vec![0usize; weights.len()];
+        for step in 0..sample_count {
+            counts[table.sample((step as f32 + 0.5) / sample_count as f32)] += 1;
+        }
+
+        for (index, weight) in weights.iter().enumerate() {
+            let expected = weight / total;
+            assert!((table.pmf(index) - expected).abs() < 1.0e-6);
+
+            let frequency = counts[index] as f32 / sample_count as f32;
+            assert!((frequency - expected).abs() < 1.0e-3, "index {} sampled {} of the time, expected {}", index, frequency, expected);
+        }
+        assert_eq!(counts[1], 0);
+    }
+
+    #[test]
+    fn all_zero_weights_are_uniform() {
+        let table = AliasTable::new(&[0.0, 0.0, 0.0, 0.0]);
+
+        let mut counts = [0usize; 4];
+        for step in 0..4000 {
+            counts[table.sample((step as f32 + 0.5) / 4000.0)] += 1;
+        }
+
+        assert_eq!(counts, [1000; 4]);
+        for index in 0..4 {
+            assert_eq!(table.pmf(index), 0.25);
+        }
+    }
+
+    #[test]
+    fn single_entry_is_always_picked() {
+        for weight in [0.0, 3.0] {
+            let table = AliasTable::new(&[weight]);
+
+            assert_eq!(table.count(), 1);
+            assert_eq!(table.pmf(0), 1.0);
+            for u in [0.0, 0.5, 1.0 - f32::EPSILON, 1.0] {
+                assert_eq!(table.sample(u), 0);
+            }
+        }
+    }
+}
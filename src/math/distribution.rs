@@ -0,0 +1,174 @@
+// Piecewise constant distributions for importance sampling tabulated functions, like images.
+// Samples and pdfs are over [0, 1), the pdfs are densities with respect to that domain.
+pub struct Distribution1D {
+    function: Vec<f32>,
+    cdf: Vec<f32>,
+    integral: f32,
+}
+
+impl Distribution1D {
+    pub fn new(function: &[f32]) -> Distribution1D {
+        let count = function.len();
+        let function: Vec<f32> = function.iter().map(|value| value.max(0.0)).collect();
+
+        let mut cdf: Vec<f32> = Vec::with_capacity(count + 1);
+        cdf.push(0.0);
+        for index in 0..count {
+            cdf.push(cdf[index] + function[index] / count as f32);
+        }
+
+        let integral = cdf[count];
+        if integral <= 0.0 {
+            // Nothing to go by, fall back to uniform
+            for (index, value) in cdf.iter_mut().enumerate() {
+                *value = index as f32 / count as f32;
+            }
+        } else {
+            for value in cdf.iter_mut() {
+                *value /= integral;
+            }
+        }
+
+        Distribution1D { function, cdf, integral }
+    }
+
+    #[inline]
+    pub fn integral(&self) -> f32 {
+        self.integral
+    }
+
+    #[inline]
+    pub fn count(&self) -> usize {
+        self.function.len()
+    }
+
+    // Returns the sampled position, its pdf and the index of the segment it fell in
+    pub fn sample(&self, u: f32) -> (f32, f32, usize) {
+        // The last segment whose cdf is at or below u
+        let index = self.cdf.partition_point(|value| *value <= u).clamp(1, self.count()) - 1;
+
+        let segment_width = self.cdf[index + 1] - self.cdf[index];
+        let offset = if 0.0 < segment_width { (u - self.cdf[index]) / segment_width } else { 0.0 };
+
+        ((index as f32 + offset.clamp(0.0, 1.0)) / self.count() as f32, self.pdf(index), index)
+    }
+
+    #[inline]
+    pub fn pdf(&self, index: usize) -> f32 {
+        if self.integral <= 0.0 {
+            return 1.0;
+        }
+
+        self.function[index] / self.integral
+    }
+}
+
+// Samples the rows proportionally to their sums, then a column within the row
+pub struct Distribution2D {
+    rows: Vec<Distribution1D>,
+    marginal: Distribution1D,
+}
+
+impl Distribution2D {
+    // Values are row major, with width values per row
+    pub fn new(function: &[f32], width: usize, height: usize) -> Distribution2D {
+        let rows: Vec<Distribution1D> = function.chunks_exact(width).take(height).map(Distribution1D::new).collect();
+        let row_integrals: Vec<f32> = rows.iter().map(|row| row.integral()).collect();
+        let marginal = Distribution1D::new(&row_integrals);
+
+        Distribution2D { rows, marginal }
+    }
+
+    // Returns the sampled (x, y) and its pdf
+    pub fn sample(&self, u0: f32, u1: f32) -> (f32, f32, f32) {
+        let (y, pdf_y, row) = self.marginal.sample(u1);
+        let (x, pdf_x, _column) = self.rows[row].sample(u0);
+
+        (x, y, pdf_x * pdf_y)
+    }
+
+    pub fn pdf(&self, x: f32, y: f32) -> f32 {
+        let row = ((y * self.marginal.count() as f32) as usize).min(self.marginal.count() - 1);
+        let column = ((x * self.rows[row].count() as f32) as usize).min(self.rows[row].count() - 1);
+
+        self.marginal.pdf(row) * self.rows[row].pdf(column)
+    }
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pdf_1d_integrates_to_one_and_follows_the_function() {
+        let distribution = Distribution1D::new(&[1.0, 3.0, 0.0, 4.0]);
+        assert!((distribution.integral() - 2.0).abs() < 1.0e-6);
+
+        let integral: f32 = (0..distribution.count()).map(|index| distribution.pdf(index) / distribution.count() as f32).sum();
+        assert!((integral - 1.0).abs() < 1.0e-6);
+        assert_eq!(distribution.pdf(2), 0.0);
+        assert!((distribution.pdf(3) - 2.0).abs() < 1.0e-6);
+    }
+
+    #[test]
+    fn samples_1d_land_in_the_right_segment() {
+        let distribution = Distribution1D::new(&[1.0, 3.0, 0.0, 4.0]);
+
+        // The cdf is 0, 1/8, 4/8, 4/8, 1, so the middle of each range maps to the middle of its segment
+        let (x, pdf, index) = distribution.sample(1.0 / 16.0);
+        assert_eq!(index, 0);
+        assert!((x - 0.125).abs() < 1.0e-6 && (pdf - 0.5).abs() < 1.0e-6);
+
+        let (x, pdf, index) = distribution.sample(5.0 / 16.0);
+        assert_eq!(index, 1);
+        assert!((x - 0.375).abs() < 1.0e-6 && (pdf - 1.5).abs() < 1.0e-6);
+
+        let (x, _pdf, index) = distribution.sample(0.75);
+        assert_eq!(index, 3);
+        assert!((x - 0.875).abs() < 1.0e-6);
+
+        // Never the empty segment, and never outside [0, 1]
+        for step in 0..=1000 {
+            let (x, pdf, index) = distribution.sample(step as f32 / 1000.0);
+            assert!(index != 2 && 0.0 < pdf && (0.0..=1.0).contains(&x));
+        }
+    }
+
+    #[test]
+    fn all_zero_1d_falls_back_to_uniform() {
+        let distribution = Distribution1D::new(&[0.0, 0.0, 0.0, 0.0]);
+
+        assert_eq!(distribution.pdf(1), 1.0);
+        let (x, pdf, index) = distribution.sample(0.6);
+        assert_eq!((index, pdf), (2, 1.0));
+        assert!((x - 0.6).abs() < 1.0e-6);
+    }
+
+    #[test]
+    fn pdf_2d_integrates_to_one_and_matches_samples() {
+        let (width, height) = (4, 3);
+        let function: Vec<f32> = vec![
+            1.0, 0.0, 2.0, 1.0,
+            0.0, 0.0, 0.0, 0.0,
+            5.0, 1.0, 1.0, 1.0,
+        ];
+        let distribution = Distribution2D::new(&function, width, height);
+
+        let mut integral = 0.0;
+        for y in 0..height {
+            for x in 0..width {
+                let pdf = distribution.pdf((x as f32 + 0.5) / width as f32, (y as f32 + 0.5) / height as f32);
+                integral += pdf / (width * height) as f32;
+                assert_eq!(pdf == 0.0, function[y * width + x] == 0.0);
+            }
+        }
+        assert!((integral - 1.0).abs() < 1.0e-5);
+
+        for step_0 in 0..50 {
+            for step_1 in 0..50 {
+                let (x, y, pdf) = distribution.sample((step_0 as f32 + 0.5) / 50.0, (step_1 as f32 + 0.5) / 50.0);
+                assert!(0.0 < pdf);
+                assert!((distribution.pdf(x, y) - pdf).abs() < 1.0e-4 * pdf, "sampled pdf {} evaluated as {}", pdf, distribution.pdf(x, y));
+            }
+        }
+    }
+}
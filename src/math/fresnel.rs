@@ -0,0 +1,141 @@
+use std::{f32::consts::PI, ops::{Add, Sub, Mul, Div}};
+
+// Just enough complex arithmetic for Fresnel equations with absorbing media
+#[derive(Copy, Clone, Debug)]
+struct Complex {
+    re: f32,
+    im: f32,
+}
+
+impl Complex {
+    #[inline]
+    fn new(re: f32, im: f32) -> Complex {
+        Complex { re, im }
+    }
+
+    #[inline]
+    fn norm_squared(&self) -> f32 {
+        self.re * self.re + self.im * self.im
+    }
+
+    #[inline]
+    fn sqrt(&self) -> Complex {
+        let magnitude = self.norm_squared().sqrt();
+        let re = (0.5 * (magnitude + self.re)).max(0.0).sqrt();
+        let im = (0.5 * (magnitude - self.re)).max(0.0).sqrt();
+
+        Complex::new(re, if self.im < 0.0 { -im } else { im })
+    }
+
+    #[inline]
+    fn from_phase(phase: f32) -> Complex {
+        Complex::new(phase.cos(), phase.sin())
+    }
+}
+
+impl Add for Complex {
+    type Output = Complex;
+
+    #[inline]
+    fn add(self, rhs: Complex) -> Complex {
+        Complex::new(self.re + rhs.re, self.im + rhs.im)
+    }
+}
+
+impl Sub for Complex {
+    type Output = Complex;
+
+    #[inline]
+    fn sub(self, rhs: Complex) -> Complex {
+        Complex::new(self.re - rhs.re, self.im - rhs.im)
+    }
+}
+
+impl Mul for Complex {
+    type Output = Complex;
+
+    #[inline]
+    fn mul(self, rhs: Complex) -> Complex {
+        Complex::new(self.re * rhs.re - self.im * rhs.im, self.re * rhs.im + self.im * rhs.re)
+    }
+}
+
+impl Div for Complex {
+    type Output = Complex;
+
+    #[inline]
+    fn div(self, rhs: Complex) -> Complex {
+        let denominator = rhs.norm_squared();
+        Complex::new(
+            (self.re * rhs.re + self.im * rhs.im) / denominator,
+            (self.im * rhs.re - self.re * rhs.im) / denominator
+        )
+    }
+}
+
+// Amplitude coefficients for s and p polarized light crossing from medium i to medium j
+#[inline]
+fn amplitude_coefficients(eta_i: Complex, cos_i: Complex, eta_j: Complex, cos_j: Complex) -> (Complex, Complex) {
+    let s = (eta_i * cos_i - eta_j * cos_j) / (eta_i * cos_i + eta_j * cos_j);
+    let p = (eta_j * cos_i - eta_i * cos_j) / (eta_j * cos_i + eta_i * cos_j);
+
+    (s, p)
+}
+
+#[inline]
+fn refracted_cosine(eta_i: Complex, sin_squared_i: f32, eta_t: Complex) -> Complex {
+    let ratio = eta_i / eta_t;
+    Complex::new(1.0, 0.0) - ratio * ratio * Complex::new(sin_squared_i, 0.0)
+}
+
+// Reflectance of a film of film_thickness nanometers and index of refraction film_eta, lying on a substrate
+// with complex index of refraction base_eta + i * base_k, seen from a medium with index of refraction incident_eta.
+// Interference between the two interfaces is evaluated for a single wavelength in nanometers (Airy summation),
+// a film thickness of zero gives the plain Fresnel reflectance of the substrate.
+pub fn thin_film_reflectance(cos_theta: f32, incident_eta: f32, film_eta: f32, film_thickness: f32, base_eta: f32, base_k: f32, wavelength: f32) -> f32 {
+    let cos_theta = cos_theta.clamp(0.0, 1.0);
+    let sin_squared = 1.0 - cos_theta * cos_theta;
+
+    let eta_0 = Complex::new(incident_eta, 0.0);
+    let eta_1 = Complex::new(film_eta, 0.0);
+    let eta_2 = Complex::new(base_eta, base_k);
+
+    let cos_0 = Complex::new(cos_theta, 0.0);
+    let cos_1 = refracted_cosine(eta_0, sin_squared, eta_1).sqrt();
+    let cos_2 = refracted_cosine(eta_0, sin_squared, eta_2).sqrt();
+
+    let (r01_s, r01_p) = amplitude_coefficients(eta_0, cos_0, eta_1, cos_1);
+    let (r12_s, r12_p) = amplitude_coefficients(eta_1, cos_1, eta_2, cos_2);
+
+    // Phase difference between the ray reflected at the top and the bottom of the film
+    let phase = 4.0 * PI * film_eta * film_thickness * cos_1.re / wavelength;
+    let phase_shift = Complex::from_phase(phase) * Complex::new((-4.0 * PI * film_eta * film_thickness * cos_1.im / wavelength).exp(), 0.0);
+
+    let one = Complex::new(1.0, 0.0);
+    let r_s = (r01_s + r12_s * phase_shift) / (one + r01_s * r12_s * phase_shift);
+    let r_p = (r01_p + r12_p * phase_shift) / (one + r01_p * r12_p * phase_shift);
+
+    (0.5 * (r_s.norm_squared() + r_p.norm_squared())).clamp(0.0, 1.0)
+}
+
+// Unpolarized Fresnel reflectance of a conductor with complex index of refraction eta + i * k,
+// seen from a medium with an index of refraction of 1
+pub fn conductor_reflectance(cos_theta: f32, eta: f32, k: f32) -> f32 {
+    let cos_squared = cos_theta.clamp(0.0, 1.0).powi(2);
+    let sin_squared = 1.0 - cos_squared;
+    let eta_squared = eta * eta;
+    let k_squared = k * k;
+
+    let t0 = eta_squared - k_squared - sin_squared;
+    let a_squared_plus_b_squared = (t0 * t0 + 4.0 * eta_squared * k_squared).sqrt();
+    let t1 = a_squared_plus_b_squared + cos_squared;
+    let a = (0.5 * (a_squared_plus_b_squared + t0)).max(0.0).sqrt();
+    let t2 = 2.0 * cos_squared.sqrt() * a;
+    let r_s = (t1 - t2) / (t1 + t2);
+
+    let t3 = cos_squared * a_squared_plus_b_squared + sin_squared * sin_squared;
+    let t4 = t2 * sin_squared;
+    let r_p = r_s * (t3 - t4) / (t3 + t4);
+
+    (0.5 * (r_s + r_p)).clamp(0.0, 1.0)
+}
\ No newline at end of file
@@ -1,2 +1,6 @@
 pub mod ortho_normal_base;
-pub mod utility;
\ No newline at end of file
+pub mod utility;
+pub mod fresnel;
+pub mod phase_function;
+pub mod distribution;
+pub mod alias_table;
\ No newline at end of file
@@ -0,0 +1,68 @@
+use std::f32::consts::PI;
+
+use ultraviolet::Vec3;
+use rand::{rngs::ThreadRng, Rng};
+
+use super::ortho_normal_base::OrthoNormalBase;
+
+// Angular distribution of light scattered inside a medium. All of them are
+// functions of the cosine between the direction the light was travelling in
+// and the scattered direction, so positive asymmetry means forward scattering.
+#[derive(Clone, Copy)]
+pub enum PhaseFunction {
+    HenyeyGreenstein { g: f32 },
+    // A forward and a backward lobe, like the silver lining and the glory of clouds
+    DoubleHenyeyGreenstein { g_forward: f32, g_backward: f32, forward_weight: f32 },
+    Rayleigh,
+}
+
+#[inline]
+fn henyey_greenstein(cos_theta: f32, g: f32) -> f32 {
+    let denominator = 1.0 + g * g - 2.0 * g * cos_theta;
+    (1.0 - g * g) / (4.0 * PI * denominator * denominator.max(1.0e-8).sqrt())
+}
+
+#[inline]
+fn sample_henyey_greenstein_cosine(rng: &mut ThreadRng, g: f32) -> f32 {
+    let xi: f32 = rng.gen::<f32>();
+    if g.abs() < 1.0e-3 {
+        return 1.0 - 2.0 * xi;
+    }
+
+    let term = (1.0 - g * g) / (1.0 - g + 2.0 * g * xi);
+    ((1.0 + g * g - term * term) / (2.0 * g)).clamp(-1.0, 1.0)
+}
+
+impl PhaseFunction {
+    pub fn evaluate(&self, cos_theta: f32) -> f32 {
+        match self {
+            PhaseFunction::HenyeyGreenstein { g } => henyey_greenstein(cos_theta, *g),
+            PhaseFunction::DoubleHenyeyGreenstein { g_forward, g_backward, forward_weight } => 
+                forward_weight * henyey_greenstein(cos_theta, *g_forward) + (1.0 - forward_weight) * henyey_greenstein(cos_theta, *g_backward),
+            PhaseFunction::Rayleigh => 3.0 / (16.0 * PI) * (1.0 + cos_theta * cos_theta),
+        }
+    }
+
+    // Samples a direction proportionally to the phase function, which is therefore also its pdf
+    pub fn sample(&self, rng: &mut ThreadRng, incoming: &Vec3) -> Vec3 {
+        let cos_theta = match self {
+            PhaseFunction::HenyeyGreenstein { g } => sample_henyey_greenstein_cosine(rng, *g),
+            PhaseFunction::DoubleHenyeyGreenstein { g_forward, g_backward, forward_weight } => {
+                let g = if rng.gen::<f32>() < *forward_weight { *g_forward } else { *g_backward };
+                sample_henyey_greenstein_cosine(rng, g)
+            },
+            PhaseFunction::Rayleigh => {
+                // Inverting the cdf leaves the cubic cos^3 + 3 cos - 2q = 0, solved with Cardano's formula
+                let q = 4.0 * rng.gen::<f32>() - 2.0;
+                let root = (q * q + 1.0).sqrt();
+                ((q + root).cbrt() + (q - root).cbrt()).clamp(-1.0, 1.0)
+            },
+        };
+
+        let sin_theta = (1.0 - cos_theta * cos_theta).max(0.0).sqrt();
+        let phi = 2.0 * PI * rng.gen::<f32>();
+
+        let uvw = OrthoNormalBase::build_from_w(incoming);
+        uvw.local_vector(&Vec3::new(sin_theta * phi.cos(), sin_theta * phi.sin(), cos_theta))
+    }
+}
\ No newline at end of file
@@ -1 +1,2 @@
-pub mod perlin;
\ No newline at end of file
+pub mod perlin;
+pub mod simplex;
\ No newline at end of file
@@ -1,5 +1,6 @@
 use ultraviolet::Vec3;
-use rand::{rngs::ThreadRng, Rng};
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha20Rng;
 
 
 pub struct Perlin {
@@ -11,7 +12,9 @@ pub struct Perlin {
 }
 
 impl Perlin {
-    pub fn new (rng: &mut ThreadRng, point_count: u32) -> Perlin {
+    // The same seed always gives the same noise, on any machine
+    pub fn new (seed: u64, point_count: u32) -> Perlin {
+        let mut rng: ChaCha20Rng = ChaCha20Rng::seed_from_u64(seed);
         let mut result = 
             Perlin{
                 point_count, 
@@ -21,10 +24,10 @@ impl Perlin {
                 permutation_z: Vec::<i32>::new(),
             };
 
-            Perlin::populate_random_vectors(rng, &mut result.random_vectors, result.point_count);
-            Perlin::generate_permutation(rng, &mut result.permutation_x, result.point_count);
-            Perlin::generate_permutation(rng, &mut result.permutation_y, result.point_count);
-            Perlin::generate_permutation(rng, &mut result.permutation_z, result.point_count);
+            Perlin::populate_random_vectors(&mut rng, &mut result.random_vectors, result.point_count);
+            Perlin::generate_permutation(&mut rng, &mut result.permutation_x, result.point_count);
+            Perlin::generate_permutation(&mut rng, &mut result.permutation_y, result.point_count);
+            Perlin::generate_permutation(&mut rng, &mut result.permutation_z, result.point_count);
 
         result
     }
@@ -108,7 +111,7 @@ impl Perlin {
         sum
     }
 
-    fn populate_random_vectors(rng: &mut ThreadRng, vector: &mut Vec<Vec3>, desired_element_count: u32) {
+    fn populate_random_vectors(rng: &mut ChaCha20Rng, vector: &mut Vec<Vec3>, desired_element_count: u32) {
         *vector = (0..desired_element_count).into_iter()
             .map(|_| Vec3::new(
                     rng.gen_range(-1.0..1.0), 
@@ -119,7 +122,7 @@ impl Perlin {
             .collect();
     }
 
-    fn generate_permutation(rng: &mut ThreadRng, vector: &mut Vec<i32>, desired_element_count: u32) {
+    fn generate_permutation(rng: &mut ChaCha20Rng, vector: &mut Vec<i32>, desired_element_count: u32) {
         *vector = (0..(desired_element_count as i32)).into_iter().collect();
 
         for index in (0..(desired_element_count as usize)).rev() {
@@ -0,0 +1,96 @@
+use ultraviolet::Vec3;
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha20Rng;
+
+const GRADIENTS: [[f32; 3]; 12] = [
+    [1.0, 1.0, 0.0], [-1.0, 1.0, 0.0], [1.0, -1.0, 0.0], [-1.0, -1.0, 0.0],
+    [1.0, 0.0, 1.0], [-1.0, 0.0, 1.0], [1.0, 0.0, -1.0], [-1.0, 0.0, -1.0],
+    [0.0, 1.0, 1.0], [0.0, -1.0, 1.0], [0.0, 1.0, -1.0], [0.0, -1.0, -1.0],
+];
+
+// Skewing factors between the regular grid and the simplex grid of tetrahedra
+const SKEW: f32 = 1.0 / 3.0;
+const UNSKEW: f32 = 1.0 / 6.0;
+
+// 3D simplex noise after Stefan Gustavson's reference implementation.
+// Cheaper than Perlin noise in higher octaves and without its axis aligned artifacts.
+pub struct Simplex {
+    permutation: Vec<usize>, // Doubled, so lookups never have to wrap
+}
+
+impl Simplex {
+    pub fn new(seed: u64) -> Simplex {
+        let mut rng: ChaCha20Rng = ChaCha20Rng::seed_from_u64(seed);
+        let mut permutation: Vec<usize> = (0..256).collect();
+        for index in (1..256).rev() {
+            let target = rng.gen_range(0..=index);
+            permutation.swap(index, target);
+        }
+
+        let doubled: Vec<usize> = permutation.iter().chain(permutation.iter()).copied().collect();
+
+        Simplex { permutation: doubled }
+    }
+
+    #[inline]
+    fn corner_contribution(&self, gradient_index: usize, x: f32, y: f32, z: f32) -> f32 {
+        let t = 0.6 - x * x - y * y - z * z;
+        if t < 0.0 {
+            return 0.0;
+        }
+
+        let gradient = GRADIENTS[gradient_index % 12];
+        let t_squared = t * t;
+        t_squared * t_squared * (gradient[0] * x + gradient[1] * y + gradient[2] * z)
+    }
+
+    // Roughly in [-1, 1]
+    pub fn noise(&self, point: &Vec3) -> f32 {
+        let skew = (point.x + point.y + point.z) * SKEW;
+        let i = (point.x + skew).floor();
+        let j = (point.y + skew).floor();
+        let k = (point.z + skew).floor();
+
+        let unskew = (i + j + k) * UNSKEW;
+        let x0 = point.x - (i - unskew);
+        let y0 = point.y - (j - unskew);
+        let z0 = point.z - (k - unskew);
+
+        // Find out which of the six tetrahedra of the cube the point is in
+        let (i1, j1, k1, i2, j2, k2) =
+            if y0 <= x0 {
+                if z0 <= y0 { (1, 0, 0, 1, 1, 0) }
+                else if z0 <= x0 { (1, 0, 0, 1, 0, 1) }
+                else { (0, 0, 1, 1, 0, 1) }
+            } else if y0 < z0 { (0, 0, 1, 0, 1, 1) }
+            else if x0 < z0 { (0, 1, 0, 0, 1, 1) }
+            else { (0, 1, 0, 1, 1, 0) };
+
+        let x1 = x0 - i1 as f32 + UNSKEW;
+        let y1 = y0 - j1 as f32 + UNSKEW;
+        let z1 = z0 - k1 as f32 + UNSKEW;
+        let x2 = x0 - i2 as f32 + 2.0 * UNSKEW;
+        let y2 = y0 - j2 as f32 + 2.0 * UNSKEW;
+        let z2 = z0 - k2 as f32 + 2.0 * UNSKEW;
+        let x3 = x0 - 1.0 + 3.0 * UNSKEW;
+        let y3 = y0 - 1.0 + 3.0 * UNSKEW;
+        let z3 = z0 - 1.0 + 3.0 * UNSKEW;
+
+        let ii = (i as i64 & 255) as usize;
+        let jj = (j as i64 & 255) as usize;
+        let kk = (k as i64 & 255) as usize;
+        let p = &self.permutation;
+
+        let gradient_0 = p[ii + p[jj + p[kk]]];
+        let gradient_1 = p[ii + i1 + p[jj + j1 + p[kk + k1]]];
+        let gradient_2 = p[ii + i2 + p[jj + j2 + p[kk + k2]]];
+        let gradient_3 = p[ii + 1 + p[jj + 1 + p[kk + 1]]];
+
+        32.0 * (
+            self.corner_contribution(gradient_0, x0, y0, z0) + 
+            self.corner_contribution(gradient_1, x1, y1, z1) + 
+            self.corner_contribution(gradient_2, x2, y2, z2) + 
+            self.corner_contribution(gradient_3, x3, y3, z3)
+        )
+    }
+}
\ No newline at end of file
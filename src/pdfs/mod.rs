@@ -1,5 +1,6 @@
 pub mod pdf;
 pub mod cosine_pdf;
 pub mod hittable_pdf;
+pub mod phase_pdf;
 pub mod mixture_pdf;
 pub mod pdf_enum;
\ No newline at end of file
@@ -3,7 +3,7 @@ use rand::rngs::ThreadRng;
 
 use crate::{services::hittable_service::HittableService};
 
-use super::{cosine_pdf::CosinePDF, pdf::PDF, hittable_pdf::HittablePDF};
+use super::{cosine_pdf::CosinePDF, pdf::PDF, hittable_pdf::HittablePDF, phase_pdf::PhasePDF};
 
 // Mixture PDF is not in here because it creates a potentially infinite size
 // Also a mixture PDF should at this point only every hold 2 PDFs
@@ -12,6 +12,7 @@ pub enum PDFEnum {
     None(),
     CosinePDF(CosinePDF),
     HittablePDF(HittablePDF),
+    PhasePDF(PhasePDF),
 }
 
 impl PDF for PDFEnum {
@@ -22,6 +23,7 @@ impl PDF for PDFEnum {
             PDFEnum::None() => 0.0,
             PDFEnum::CosinePDF (cosine_pdf ) => cosine_pdf.value(rng, hittable_service, direction),
             PDFEnum::HittablePDF(hittable_pdf) => hittable_pdf.value(rng, hittable_service, direction),
+            PDFEnum::PhasePDF(phase_pdf) => phase_pdf.value(rng, hittable_service, direction),
         }
     }
 
@@ -31,6 +33,7 @@ impl PDF for PDFEnum {
             PDFEnum::None() => Vec3::zero(),
             PDFEnum::CosinePDF (cosine_pdf ) => cosine_pdf.generate(rng, hittable_service),
             PDFEnum::HittablePDF(hittable_pdf) => hittable_pdf.generate(rng, hittable_service),
+            PDFEnum::PhasePDF(phase_pdf) => phase_pdf.generate(rng, hittable_service),
         }
     }    
 }
\ No newline at end of file
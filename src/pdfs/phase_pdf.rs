@@ -0,0 +1,28 @@
+use ultraviolet::Vec3;
+use rand::rngs::ThreadRng;
+
+use crate::{math::phase_function::PhaseFunction, services::hittable_service::HittableService};
+
+use super::pdf::PDF;
+
+#[derive(Clone, Copy)]
+pub struct PhasePDF {
+    pub incoming: Vec3,
+    pub phase_function: PhaseFunction,
+}
+
+impl PhasePDF {
+    pub fn new(incoming: &Vec3, phase_function: PhaseFunction) -> PhasePDF {
+        PhasePDF{ incoming: incoming.normalized(), phase_function }
+    }
+}
+
+impl PDF for PhasePDF {
+    fn value(&self, _rng: &mut ThreadRng, _hittable_service: &HittableService, direction: &Vec3) -> f32 {
+        self.phase_function.evaluate(self.incoming.dot(direction.normalized()))
+    }
+
+    fn generate(&self, rng: &mut ThreadRng, _hittable_service: &HittableService) -> Vec3 {
+        self.phase_function.sample(rng, &self.incoming)
+    }
+}
\ No newline at end of file
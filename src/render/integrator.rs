@@ -5,22 +5,69 @@ use crate::{
         service_locator::ServiceLocator, 
         material_service::MaterialService, 
         hittable_service::HittableService, 
-        texture_service::TextureService, scene_service::SceneService
+        texture_service::TextureService, 
+        light_service::LightService, 
+        scene_service::SceneService
     }, 
     core::{
         color_rgb::ColorRGB, 
         ray::Ray
     }, 
     hittables::hit_record::HitRecord, 
+    lights::light::LightSample, 
     materials::scatter_record::ScatterRecord, 
     pdfs::{
         pdf_enum::PDFEnum, 
         hittable_pdf::HittablePDF, 
         mixture_pdf::MixturePDF, 
         pdf::PDF
-    }, utility::render_config::RenderConfig, scene::{camera::Camera}
+    }, utility::render_config::RenderConfig, scene::{camera::Camera, background::BackgroundEnum}
 };
 
+// Next event estimation for the delta lights, which scattered rays can never hit.
+// Every light gets a shadow ray, the atmosphere attenuates the ones which are unoccluded.
+fn sample_delta_lights(
+    rng: &mut ThreadRng,
+    service_locator: &ServiceLocator,
+    ray: &Ray,
+    rec: &HitRecord,
+    scatter_record: &ScatterRecord) -> ColorRGB {
+
+    let light_service: &LightService = service_locator.get_light_service();
+    let material_service: &MaterialService = service_locator.get_material_service();
+    let hittable_service: &HittableService = service_locator.get_hittable_service();
+    let texture_service: &TextureService = service_locator.get_texture_service();
+
+    let mut direct: ColorRGB = ColorRGB::black();
+    let mut light_sample: LightSample = LightSample::default();
+    let mut shadow_rec: HitRecord = HitRecord::default();
+    for light_index in 0..light_service.light_count() {
+        if !light_service.sample(light_index, rng, &rec.position, &mut light_sample) || !rec.is_consistent(&light_sample.direction) {
+            continue;
+        }
+
+        let shadow_ray: Ray = Ray::new(rec.position, light_sample.direction, ray.time);
+        let scattering_pdf: f32 = material_service.scattering_pdf(rng, texture_service, ray, rec, scatter_record, &shadow_ray);
+        if scattering_pdf <= 0.0 {
+            continue;
+        }
+
+        if hittable_service.hit(hittable_service.get_bvh_root_index(), rng, &shadow_ray, 0.001, light_sample.distance, &mut shadow_rec) {
+            continue;
+        }
+
+        let transmittance: f32 = service_locator.get_scene_service().get_atmosphere().map_or(1.0, |atmosphere| atmosphere.transmittance(&shadow_ray, light_sample.distance));
+
+        direct += 
+            scatter_record.attenuation 
+            * material_service.scattering_tint(rng, texture_service, ray, rec, scatter_record, &shadow_ray) 
+            * light_sample.radiance 
+            * (scattering_pdf * transmittance);
+    }
+
+    direct
+}
+
 // These functions aren't needed. The only one that should stay is ray_color_loop_lights. 
 // The no_lights version is staying so I don't have to modify any of the scenes from the book.
 // The recursive functions are left here for pedagogical reasons to make it easier to translate
@@ -33,7 +80,7 @@ fn ray_color_recursive(
     texture_service: &TextureService,
     bvh_root_index: usize,
     lights_root_index: usize,
-    background: &ColorRGB,
+    background: &BackgroundEnum,
     ray: &Ray,
     depth: usize,
     has_lights: bool) -> ColorRGB {
@@ -44,10 +91,20 @@ fn ray_color_recursive(
 
     let mut rec:HitRecord = HitRecord::default();
 
-    if !hittable_service.hit(bvh_root_index, rng, ray, 0.001, f32::MAX, &mut rec) {
-        return *background;
+    let mut hit_anything: bool = hittable_service.hit(bvh_root_index, rng, ray, 0.001, f32::MAX, &mut rec);
+
+    // The atmosphere can scatter the ray before it reaches whatever it hit, or the background
+    if let Some(atmosphere) = service_locator.get_scene_service().get_atmosphere() {
+        let t_max: f32 = if hit_anything { rec.t } else { f32::MAX };
+        hit_anything |= atmosphere.hit(rng, ray, 0.001, t_max, &mut rec);
     }
 
+    if !hit_anything {
+        return background.value(&ray.direction);
+    }
+
+    rec.set_footprint(ray);
+    material_service.perturb_normal(texture_service, ray, &mut rec);
 
     let mut scatter_record= ScatterRecord::default();
     let mut emitted: ColorRGB = ColorRGB::black();
@@ -57,8 +114,9 @@ fn ray_color_recursive(
         return emitted;
     }
 
+    // Media which glow, like fire, emit and scatter specularly in the same collision
     if scatter_record.is_specular {
-        return scatter_record.attenuation *
+        return emitted + scatter_record.attenuation *
             ray_color_recursive(
                 rng,
                 service_locator,
@@ -68,22 +126,29 @@ fn ray_color_recursive(
                 bvh_root_index,
                 lights_root_index,
                 background,
-                &scatter_record.specular_ray,
+                &scatter_record.specular_ray.with_cone(ray.cone_width_at(rec.t), ray.cone_spread),
                 depth - 1,
                 has_lights
             );
     }
 
+    let emitted: ColorRGB = emitted + sample_delta_lights(rng, service_locator, ray, &rec, &scatter_record);
+
     if has_lights {
         // Maybe put the non-recursive loop after this if statement and move the above in there
         let light_pdf: PDFEnum = PDFEnum::HittablePDF(HittablePDF::new(&rec.position, lights_root_index));
         let mixture_pdf: MixturePDF = MixturePDF::new( light_pdf, scatter_record.pdf );
-        let scattered = Ray::new_normalized(rec.position, mixture_pdf.generate(rng, hittable_service), ray.time);
+        let scattered = Ray::new_normalized(rec.position, mixture_pdf.generate(rng, hittable_service), ray.time).with_cone(ray.cone_width_at(rec.t), ray.cone_spread);
         let pdf_val = mixture_pdf.value(rng, hittable_service, &scattered.direction);
 
+        if !rec.is_consistent(&scattered.direction) {
+            return emitted;
+        }
+
         emitted +
         scatter_record.attenuation *
-        material_service.scattering_pdf(rng, ray, &rec, &scattered) *
+        material_service.scattering_tint(rng, texture_service, ray, &rec, &scatter_record, &scattered) *
+        material_service.scattering_pdf(rng, texture_service, ray, &rec, &scatter_record, &scattered) *
         ray_color_recursive(
             rng,
             service_locator,
@@ -99,12 +164,17 @@ fn ray_color_recursive(
         pdf_val
     } else {
         let pdf: PDFEnum = scatter_record.pdf;
-        let scattered = Ray::new_normalized(rec.position, pdf.generate(rng, hittable_service), ray.time);
+        let scattered = Ray::new_normalized(rec.position, pdf.generate(rng, hittable_service), ray.time).with_cone(ray.cone_width_at(rec.t), ray.cone_spread);
         let pdf_val = pdf.value(rng, hittable_service, &scattered.direction);
+
+        if !rec.is_consistent(&scattered.direction) {
+            return emitted;
+        }
     
         emitted +
         scatter_record.attenuation *
-        material_service.scattering_pdf(rng, ray, &rec, &scattered) *
+        material_service.scattering_tint(rng, texture_service, ray, &rec, &scatter_record, &scattered) *
+        material_service.scattering_pdf(rng, texture_service, ray, &rec, &scatter_record, &scattered) *
         ray_color_recursive(
             rng,
             service_locator,
@@ -129,11 +199,14 @@ fn ray_color_loop(
     texture_service: &TextureService,
     bvh_root_index: usize,
     lights_root_index: usize,
-    background: &ColorRGB,
+    service_locator: &ServiceLocator,
     first_ray: &Ray,
     max_depth: usize,
     has_lights: bool) -> ColorRGB {
 
+    let scene_service: &SceneService = service_locator.get_scene_service();
+    let background: &BackgroundEnum = scene_service.get_background();
+    let has_delta_lights: bool = 0 < service_locator.get_light_service().light_count();
     let mut l: ColorRGB = ColorRGB::black();
     let mut beta: ColorRGB = ColorRGB::white();
     let mut rec:HitRecord = HitRecord::default();
@@ -146,12 +219,23 @@ fn ray_color_loop(
             break;
         }
 
+        let mut hit_anything: bool = hittable_service.hit(bvh_root_index, rng, &ray, 0.001, f32::MAX, &mut rec);
+
+        // The atmosphere can scatter the ray before it reaches whatever it hit, or the background
+        if let Some(atmosphere) = scene_service.get_atmosphere() {
+            let t_max: f32 = if hit_anything { rec.t } else { f32::MAX };
+            hit_anything |= atmosphere.hit(rng, &ray, 0.001, t_max, &mut rec);
+        }
+
         // Hit nothing, add background color
-        if !hittable_service.hit(bvh_root_index, rng, &ray, 0.001, f32::MAX, &mut rec) {
-            l += beta * *background;
+        if !hit_anything {
+            l += beta * background.value(&ray.direction);
             break;
         }
 
+        rec.set_footprint(&ray);
+        material_service.perturb_normal(texture_service, &ray, &mut rec);
+
         material_service.emitted(texture_service, &ray, &rec, &mut emitted);
 
         // We probably hit a lighting material and just have to add the emission
@@ -160,8 +244,10 @@ fn ray_color_loop(
             break;
         }
 
+        // Media which glow, like fire, emit and scatter specularly in the same collision
         if scatter_record.is_specular {
-            ray = scatter_record.specular_ray;
+            l += beta * emitted;
+            ray = scatter_record.specular_ray.with_cone(ray.cone_width_at(rec.t), ray.cone_spread);
             beta *= scatter_record.attenuation;
             depth += 1;
             continue;
@@ -169,15 +255,26 @@ fn ray_color_loop(
 
         if beta.is_nan() || emitted.is_nan() { break }
 
+        if has_delta_lights {
+            l += beta * sample_delta_lights(rng, service_locator, &ray, &rec, &scatter_record);
+        }
+
         if has_lights {
             let light_pdf: PDFEnum = PDFEnum::HittablePDF(HittablePDF::new(&rec.position, lights_root_index));
             let mixture_pdf: MixturePDF = MixturePDF::new( light_pdf, scatter_record.pdf );
-            let scattered: Ray = Ray::new_normalized(rec.position, mixture_pdf.generate(rng, hittable_service), ray.time);
+            let scattered: Ray = Ray::new_normalized(rec.position, mixture_pdf.generate(rng, hittable_service), ray.time).with_cone(ray.cone_width_at(rec.t), ray.cone_spread);
             let pdf_val: f32 = mixture_pdf.value(rng, hittable_service, &scattered.direction);
 
+            // Reflected through the geometric surface by a perturbed normal
+            if !rec.is_consistent(&scattered.direction) {
+                l += beta * emitted;
+                break;
+            }
+
             let l_i: ColorRGB = 
                 scatter_record.attenuation 
-                * material_service.scattering_pdf(rng, &ray, &rec, &scattered) 
+                * material_service.scattering_tint(rng, texture_service, &ray, &rec, &scatter_record, &scattered) 
+                * material_service.scattering_pdf(rng, texture_service, &ray, &rec, &scatter_record, &scattered) 
                 / pdf_val;
 
             if l_i.is_nan() { break }
@@ -188,12 +285,18 @@ fn ray_color_loop(
             ray = scattered;
         } else {
             let pdf: PDFEnum = scatter_record.pdf;
-            let scattered = Ray::new_normalized(rec.position, pdf.generate(rng, hittable_service), ray.time);
+            let scattered = Ray::new_normalized(rec.position, pdf.generate(rng, hittable_service), ray.time).with_cone(ray.cone_width_at(rec.t), ray.cone_spread);
             let pdf_val = pdf.value(rng, hittable_service, &scattered.direction);
+
+            if !rec.is_consistent(&scattered.direction) {
+                l += beta * emitted;
+                break;
+            }
     
             let new_term: ColorRGB = 
                 scatter_record.attenuation 
-                * material_service.scattering_pdf(rng, &ray, &rec, &scattered) 
+                * material_service.scattering_tint(rng, texture_service, &ray, &rec, &scatter_record, &scattered) 
+                * material_service.scattering_pdf(rng, texture_service, &ray, &rec, &scatter_record, &scattered) 
                 / pdf_val;
     
             if new_term.is_nan() { break }
@@ -230,7 +333,7 @@ pub fn render_pixel(
 
     let scene_service: &SceneService = service_locator.get_scene_service();
     let camera: &Camera = scene_service.get_camera();
-    let background: &ColorRGB = scene_service.get_background();
+    let background: &BackgroundEnum = scene_service.get_background();
 
     let material_service: &MaterialService = service_locator.get_material_service();
     let texture_service: &TextureService = service_locator.get_texture_service();
@@ -240,6 +343,7 @@ pub fn render_pixel(
     let lights_root_index: usize = hittable_service.get_lights_root_index();
 
     let has_lights: bool = hittable_service.has_lights();
+    let pixel_spread_angle: f32 = camera.pixel_spread_angle(config.image_height);
 
     let sample_scale: f32 = 1.0 / (config.subpixels_per_pixel * config.subpixels_per_pixel * config.samples_per_pixel) as f32;
     let subpixels_offset: f32 = 1.0 / config.subpixels_per_pixel as f32;
@@ -259,7 +363,7 @@ pub fn render_pixel(
         let u = ( (sx as f32) + 0.5 + (dx as f32)) * subpixels_offset + (column_index as f32);
         let v = ( (sy as f32) + 0.5 + (dy as f32)) * subpixels_offset + (row_index as f32);
 
-        let ray = camera.get_ray(&mut rng, u / (config.image_width - 1) as f32, v / (config.image_height - 1) as f32);
+        let ray = camera.get_ray(&mut rng, u / (config.image_width - 1) as f32, v / (config.image_height - 1) as f32).with_cone(0.0, pixel_spread_angle);
         if config.use_loop_rendering {
             ray_color_loop(
                 &mut rng,
@@ -268,7 +372,7 @@ pub fn render_pixel(
                 texture_service,
                 bvh_root_index,
                 lights_root_index,
-                background,
+                service_locator,
                 &ray,
                 config.max_depth,
                 has_lights
@@ -0,0 +1,90 @@
+use ultraviolet::Vec3;
+use rand::{rngs::ThreadRng, Rng};
+
+use crate::{core::ray::Ray, hittables::hit_record::HitRecord};
+
+// A participating medium filling the whole scene, like fog or haze.
+// The density is density at base_height and falls off exponentially with height,
+// a falloff of zero gives homogeneous fog. The optical depth along a ray has a
+// closed form, so free flight distances are sampled exactly instead of by tracking.
+// Collisions are handed to the integrator like the ones of a ConstantMedium,
+// so the phase function material at them samples the lights through the fog as well.
+pub struct Atmosphere {
+    density: f32,
+    base_height: f32,
+    falloff: f32,
+    phase_function: usize,
+}
+
+impl Atmosphere {
+    pub fn new(density: f32, phase_function: usize) -> Atmosphere {
+        Atmosphere { density, base_height: 0.0, falloff: 0.0, phase_function }
+    }
+
+    pub fn with_height_falloff(mut self, base_height: f32, falloff: f32) -> Atmosphere {
+        self.base_height = base_height;
+        self.falloff = falloff.max(0.0);
+        self
+    }
+
+    // Optical depth from the ray origin to ray.at(t) is
+    // scale * (1 - exp(-rate * t)) / rate, or scale * t when rate is zero
+    #[inline]
+    fn optical_depth_terms(&self, ray: &Ray) -> (f32, f32) {
+        let scale = self.density * (-self.falloff * (ray.origin.y - self.base_height)).exp() * ray.direction.mag();
+        let rate = self.falloff * ray.direction.y;
+
+        (scale, rate)
+    }
+
+    // Fraction of light which makes it from the ray origin to ray.at(t_max) without colliding
+    pub fn transmittance(&self, ray: &Ray, t_max: f32) -> f32 {
+        if self.density <= 0.0 {
+            return 1.0;
+        }
+
+        let (scale, rate) = self.optical_depth_terms(ray);
+        let optical_depth = if rate.abs() < 1.0e-6 { scale * t_max } else { scale * (1.0 - (-rate * t_max).exp()) / rate };
+
+        (-optical_depth).exp()
+    }
+
+    // Samples where the ray collides with the atmosphere before t_max
+    pub fn hit(&self, rng: &mut ThreadRng, ray: &Ray, t_min: f32, t_max: f32, hit_out: &mut HitRecord) -> bool {
+        if self.density <= 0.0 {
+            return false;
+        }
+
+        let (scale, rate) = self.optical_depth_terms(ray);
+        let sampled_depth = -(1.0 - rng.gen::<f32>()).ln();
+
+        let t = if rate.abs() < 1.0e-6 {
+            sampled_depth / scale
+        } else {
+            // Rays going up can only pass through scale / rate of optical depth in total
+            let remaining = 1.0 - rate * sampled_depth / scale;
+            if remaining <= 0.0 {
+                return false;
+            }
+
+            -remaining.ln() / rate
+        };
+
+        if !t.is_finite() || t < t_min || t_max <= t {
+            return false;
+        }
+
+        hit_out.t = t;
+        hit_out.u = 0.0;
+        hit_out.v = 0.0;
+        hit_out.position = ray.at(t);
+        hit_out.normal = Vec3::new(1.0, 0.0, 0.0 );
+        hit_out.geometric_normal = hit_out.normal;
+        hit_out.set_tangents(&Vec3::zero(), &Vec3::zero());
+        hit_out.uv_scale = (0.0, 0.0);
+        hit_out.is_front_face = true;
+        hit_out.material = self.phase_function;
+
+        true
+    }
+}
\ No newline at end of file
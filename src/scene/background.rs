@@ -0,0 +1,23 @@
+use ultraviolet::Vec3;
+
+use crate::core::color_rgb::ColorRGB;
+
+use super::{environment_map::EnvironmentMap, sky::PreethamSky};
+
+// What rays which escape the scene see
+pub enum BackgroundEnum {
+    SolidColor(ColorRGB),
+    EnvironmentMap(EnvironmentMap),
+    Sky(PreethamSky),
+}
+
+impl BackgroundEnum {
+    #[inline]
+    pub fn value(&self, direction: &Vec3) -> ColorRGB {
+        match self {
+            BackgroundEnum::SolidColor(color) => *color,
+            BackgroundEnum::EnvironmentMap(environment_map) => environment_map.value(direction),
+            BackgroundEnum::Sky(sky) => sky.value(direction),
+        }
+    }
+}
\ No newline at end of file
@@ -16,6 +16,7 @@ pub struct Camera {
     v: Vec3,
     _w: Vec3,
     lens_radius: f32,
+    viewport_height: f32,
     time_0: f32,
     time_1: f32,
 }
@@ -45,6 +46,7 @@ impl Camera {
             lower_left_corner, 
             u, v, _w:w, 
             lens_radius, 
+            viewport_height,
             time_0, time_1
         }
     }
@@ -57,10 +59,18 @@ impl Camera {
         Ray{
             origin: self.origin + offset, 
             direction: self.lower_left_corner + self.horizontal * s + self.vertical * t - self.origin - offset, 
-            time:(self.time_1 - self.time_0) * rng.gen::<f32>() + self.time_0
+            time:(self.time_1 - self.time_0) * rng.gen::<f32>() + self.time_0,
+            cone_width: 0.0,
+            cone_spread: 0.0,
         }
     }
 
+    // Angle between the rays through neighbouring pixels, at the center of the image
+    #[inline]
+    pub fn pixel_spread_angle(&self, image_height: usize) -> f32 {
+        self.viewport_height / (image_height.max(2) - 1) as f32
+    }
+
     #[inline]
     pub fn get_start_time(&self) -> f32{
         self.time_0
@@ -0,0 +1,131 @@
+use std::f32::consts::PI;
+
+use ultraviolet::Vec3;
+
+use crate::{core::color_rgb::ColorRGB, math::distribution::Distribution2D, textures::image::{load_image_data, default_color_space}};
+
+// Rotates around the y axis like RotateY
+#[inline]
+fn rotate_y(vector: &Vec3, cos_theta: f32, sin_theta: f32) -> Vec3 {
+    Vec3::new(cos_theta * vector.x + sin_theta * vector.z, vector.y, -sin_theta * vector.x + cos_theta * vector.z)
+}
+
+// How close to the poles v gets, where the mapping from image to directions breaks down
+const POLE_EPSILON: f32 = 1.0e-4;
+
+#[inline]
+pub fn clamp_to_poles(v: f32) -> f32 {
+    v.clamp(POLE_EPSILON, 1.0 - POLE_EPSILON)
+}
+
+// Maps between directions and equirectangular image coordinates, v = 1 is straight up.
+// The rotation in radians turns the image around the y axis.
+#[derive(Clone, Copy)]
+pub struct EquirectangularMapping {
+    cos_rotation: f32,
+    sin_rotation: f32,
+}
+
+impl EquirectangularMapping {
+    pub fn new(rotation: f32) -> EquirectangularMapping {
+        EquirectangularMapping { cos_rotation: rotation.cos(), sin_rotation: rotation.sin() }
+    }
+
+    pub fn uv(&self, direction: &Vec3) -> (f32, f32) {
+        let local = rotate_y(&direction.normalized(), self.cos_rotation, -self.sin_rotation);
+        let u = (local.z.atan2(local.x) + PI) / (2.0 * PI);
+        let v = 1.0 - local.y.clamp(-1.0, 1.0).acos() / PI;
+
+        (u, v)
+    }
+
+    pub fn direction(&self, u: f32, v: f32) -> Vec3 {
+        let phi = 2.0 * PI * u - PI;
+        let theta = (1.0 - v) * PI;
+        let local = Vec3::new(theta.sin() * phi.cos(), theta.cos(), theta.sin() * phi.sin());
+
+        rotate_y(&local, self.cos_rotation, self.sin_rotation)
+    }
+
+    // Converts a density over the image to one over solid angle.
+    // The poles are squeezed to a point, so v is kept just inside them, like sampled directions are.
+    #[inline]
+    pub fn solid_angle_pdf(&self, pdf_uv: f32, v: f32) -> f32 {
+        let sin_theta = ((1.0 - clamp_to_poles(v)) * PI).sin();
+
+        pdf_uv / (2.0 * PI * PI * sin_theta)
+    }
+}
+
+// An equirectangular image surrounding the scene, seen by every ray which escapes it.
+// To also light the scene efficiently add an EnvironmentLight built from it to the lights.
+pub struct EnvironmentMap {
+    data: Vec<f32>, // RGBA, bottom row first
+    width: usize,
+    height: usize,
+    intensity: f32,
+    mapping: EquirectangularMapping,
+}
+
+impl EnvironmentMap {
+    pub fn new(path: &str) -> EnvironmentMap {
+        let (data, width, height) = load_image_data(path, default_color_space(path));
+        EnvironmentMap { data, width, height, intensity: 1.0, mapping: EquirectangularMapping::new(0.0) }
+    }
+
+    pub fn with_intensity(mut self, intensity: f32) -> EnvironmentMap {
+        self.intensity = intensity;
+        self
+    }
+
+    // In degrees around the y axis
+    pub fn with_rotation(mut self, rotation: f32) -> EnvironmentMap {
+        self.mapping = EquirectangularMapping::new(rotation.to_radians());
+        self
+    }
+
+    #[inline]
+    pub fn mapping(&self) -> EquirectangularMapping {
+        self.mapping
+    }
+
+    #[inline]
+    fn texel(&self, x: usize, y: usize) -> ColorRGB {
+        let index = (y * self.width + x) * 4;
+        ColorRGB::new(self.data[index], self.data[index + 1], self.data[index + 2])
+    }
+
+    // Weighs every pixel by its luminance and the solid angle it covers, which shrinks towards the poles
+    pub fn luminance_distribution(&self) -> Distribution2D {
+        let mut weights: Vec<f32> = Vec::with_capacity(self.width * self.height);
+        for y in 0..self.height {
+            let sin_theta = ((1.0 - (y as f32 + 0.5) / self.height as f32) * PI).sin();
+            for x in 0..self.width {
+                weights.push(self.texel(x, y).luminance() * sin_theta);
+            }
+        }
+
+        Distribution2D::new(&weights, self.width, self.height)
+    }
+
+    // Bilinear, wrapping around horizontally
+    pub fn value(&self, direction: &Vec3) -> ColorRGB {
+        let (u, v) = self.mapping.uv(direction);
+        let x = u * self.width as f32 - 0.5;
+        let y = (v * self.height as f32 - 0.5).clamp(0.0, (self.height - 1) as f32);
+        let x_floor = x.floor();
+        let y_floor = y.floor();
+        let x_weight = x - x_floor;
+        let y_weight = y - y_floor;
+
+        let x0 = (x_floor as i64).rem_euclid(self.width as i64) as usize;
+        let x1 = (x0 + 1) % self.width;
+        let y0 = y_floor as usize;
+        let y1 = (y0 + 1).min(self.height - 1);
+
+        let bottom = self.texel(x0, y0) * (1.0 - x_weight) + self.texel(x1, y0) * x_weight;
+        let top = self.texel(x0, y1) * (1.0 - x_weight) + self.texel(x1, y1) * x_weight;
+
+        (bottom * (1.0 - y_weight) + top * y_weight) * self.intensity
+    }
+}
\ No newline at end of file
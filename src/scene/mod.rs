@@ -1,2 +1,6 @@
 pub mod camera;
+pub mod atmosphere;
+pub mod background;
+pub mod environment_map;
+pub mod sky;
 pub mod scene_builder;
\ No newline at end of file
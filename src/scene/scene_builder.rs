@@ -2,15 +2,16 @@
 // IT IS AN ABSOLUTE PAIN TO CHANGE ANYTHING.
 
 use ultraviolet::Vec3;
-use rand::{SeedableRng, Rng, rngs::ThreadRng};
+use rand::{SeedableRng, Rng};
 use rand_chacha::ChaCha20Rng;
 
 use crate::{
-    scene::camera::Camera, 
+    scene::{camera::Camera, atmosphere::Atmosphere, background::BackgroundEnum, environment_map::EnvironmentMap, sky::{PreethamSky, sun_direction_at}}, 
+    lights::{light_enum::LightEnum, point_light::PointLight, spot_light::SpotLight, directional_light::DirectionalLight, photometry::{PhotometricUnits, LuminousFlux, HALOGEN_EFFICACY, LED_EFFICACY}, ies_profile::IesProfile}, 
     hittables::{sphere::Sphere, hittable_list::HittableList}, 
-    hittables::{moving_sphere::MovingSphere, yz_rect::YZRect, flip_face::FlipFace, xz_rect::XZRect, translate::Translate, constant_medium::ConstantMedium, box_hittable::BoxHittable, rotate_y::RotateY}, hittables::{bvh_node::BVHNode, xy_rect::XYRect, hittable_enum::HittableEnum}, 
-    services::scene_service::{SceneService},
-    services::service_locator::{ServiceLocator}, materials::{lambertian::Lambertian, dielectric::Dielectric, metal::Metal, diffuse_light::DiffuseLight, isotropic::Isotropic, material_enum::MaterialEnum}, textures::{solid_color::SolidColor, checker::Checker, noise::Noise, image::Image, texture_enum::TextureEnum}, core::color_rgb::ColorRGB, math::utility::random_range_chacha, utility::render_config::RenderConfig
+    hittables::{moving_sphere::MovingSphere, yz_rect::YZRect, flip_face::FlipFace, xz_rect::XZRect, translate::Translate, constant_medium::ConstantMedium, heterogeneous_medium::HeterogeneousMedium, environment_light::EnvironmentLight, box_hittable::BoxHittable, rotate_y::RotateY}, hittables::{bvh_node::BVHNode, xy_rect::XYRect, hittable_enum::HittableEnum}, 
+    services::scene_service::{SceneService}, noise::{perlin::Perlin, simplex::Simplex},
+    services::service_locator::{ServiceLocator}, materials::{default_material::DefaultMaterial, lambertian::Lambertian, dielectric::Dielectric, metal::Metal, diffuse_light::DiffuseLight, isotropic::Isotropic, oren_nayar::OrenNayar, blend::Blend, alpha_mask::AlphaMask, coated::Coated, thin_film::ThinFilm, subsurface::Subsurface, normal_map::NormalMap, bump_map::{BumpMap, HeightSpace}, measured_brdf::MeasuredBRDF, sheen::Sheen, conductor::Conductor, henyey_greenstein::HenyeyGreenstein, rayleigh::Rayleigh, emissive_volume::{EmissiveVolume, VolumeEmission, BlackbodyEmission}, profiled_light::ProfiledLight, material_enum::MaterialEnum}, textures::{solid_color::SolidColor, checker::Checker, noise::Noise, image::{Image, ImageFilter, WrapMode}, uv_transform::UVTransform, fbm::{Fbm, NoiseBasis}, worley::{Worley, WorleyPattern}, marble::Marble, wood::Wood, color_ramp::ColorRamp, mix::Mix, arithmetic::{Arithmetic, Operation}, remap::Remap, channel_extract::{ChannelExtract, Channel}, uv_transform_node::UVTransformNode, triplanar::Triplanar, volume_grid::VolumeGrid, texture_enum::TextureEnum}, core::color_rgb::ColorRGB, math::utility::random_range_chacha, utility::render_config::RenderConfig
 };
 
 pub struct SceneBuilder {
@@ -48,8 +49,21 @@ fn build_acceleration_structures(rng: &mut ChaCha20Rng, service_locator: &mut Se
     }
 
     if !light_index_list.is_empty() {
+        // Pick lights proportionally to their power. Lights which can't tell, like environment maps,
+        // get the average of the ones that can, or all the same weight if none can.
+        let mut light_powers: Vec<f32> = light_index_list.iter().map(|light_index| 
+            service_locator.get_hittable_service().emitted_power(*light_index, service_locator.get_material_service(), service_locator.get_texture_service(), false)
+        ).collect();
+        let known_powers: Vec<f32> = light_powers.iter().copied().filter(|power| 0.0 < *power && power.is_finite()).collect();
+        let fallback_power = if known_powers.is_empty() { 1.0 } else { known_powers.iter().sum::<f32>() / known_powers.len() as f32 };
+        for power in light_powers.iter_mut() {
+            if !(0.0 < *power && power.is_finite()) {
+                *power = fallback_power;
+            }
+        }
+
         let hittable_service = service_locator.get_hittable_service_mut();
-        let light_list = HittableEnum::HittableList(HittableList::from_list(light_index_list));
+        let light_list = HittableEnum::HittableList(HittableList::from_list(light_index_list).with_selection_weights(&light_powers));
         let root_node_index = hittable_service.add_hittable(light_list);
         hittable_service.set_lights_root_index(root_node_index);
     }
@@ -77,6 +91,29 @@ impl SceneBuilder {
             9 => Self::cornell_box_diffuse_metal_boxes_scene(config),
             10 => Self::final_scene_book_2(config, noise_points_count, cube_sphere_count),
             11 => Self::final_scene_book_3(config),
+            12 => Self::cornell_box_oren_nayar_scene(config, noise_points_count),
+            13 => Self::cornell_box_blend_and_mask_scene(config, noise_points_count),
+            14 => Self::cornell_box_coated_scene(config, noise_points_count),
+            15 => Self::thin_film_scene(config, noise_points_count),
+            16 => Self::cornell_box_subsurface_scene(config),
+            17 => Self::cornell_box_bump_and_normal_map_scene(config, noise_points_count),
+            18 => Self::cornell_box_measured_brdf_scene(config),
+            19 => Self::cornell_box_cloth_scene(config),
+            20 => Self::cornell_box_conductors_scene(config),
+            21 => Self::texture_filtering_scene(config),
+            22 => Self::hdr_dome_scene(config),
+            23 => Self::procedural_textures_scene(config, noise_points_count),
+            24 => Self::texture_nodes_scene(config),
+            25 => Self::cornell_box_heterogeneous_media_scene(config),
+            26 => Self::cornell_box_phase_functions_scene(config),
+            27 => Self::cornell_box_emissive_volumes_scene(config),
+            28 => Self::foggy_valley_scene(config),
+            29 => Self::delta_lights_scene(config),
+            30 => Self::environment_map_scene(config),
+            31 => Self::physical_sky_scene(config),
+            32 => Self::city_at_night_scene(config),
+            33 => Self::photometric_room_scene(config),
+            34 => Self::ies_gallery_scene(config),
             _ => panic!("Incorrect scene chosen!"),
         }
 
@@ -247,10 +284,7 @@ impl SceneBuilder {
         let (mut rng, mut service_locator, mut hittable_index_list, mut _light_index_list) = init_build_resources(config, camera, background);
 
     
-        // The Noise Texture runs pretty deep
-        // I just need some determinism, not all the way
-        let mut thread_rng = rand::thread_rng();
-        let perlin_texture_index: usize = service_locator.get_texture_service_mut().add_texture(TextureEnum::Noise(Noise::new(&mut thread_rng, element_count, 4.0)));
+        let perlin_texture_index: usize = service_locator.get_texture_service_mut().add_texture(TextureEnum::Noise(Noise::new(rng.gen(), element_count, 4.0)));
         let perlin_material_index = service_locator.get_material_service_mut().add_material(MaterialEnum::Lambertian(Lambertian::new(perlin_texture_index)));
 
         hittable_index_list.push(service_locator.get_hittable_service_mut().add_hittable(HittableEnum::Sphere(Sphere::new(Vec3::new( 0.0, -1000.0, 0.0), 1000.0, perlin_material_index))));
@@ -300,10 +334,7 @@ impl SceneBuilder {
 
         let (mut rng, mut service_locator, mut hittable_index_list, mut light_index_list) = init_build_resources(config, camera, background);
 
-        // The Noise Texture runs pretty deep
-        // I just need some determinism, not all the way
-        let mut thread_rng = rand::thread_rng();
-        let perlin_texture_index: usize =  service_locator.get_texture_service_mut().add_texture(TextureEnum::Noise(Noise::new(&mut thread_rng, element_count, 4.0)));
+        let perlin_texture_index: usize =  service_locator.get_texture_service_mut().add_texture(TextureEnum::Noise(Noise::new(rng.gen(), element_count, 4.0)));
         let perlin_material_index = service_locator.get_material_service_mut().add_material(MaterialEnum::Lambertian(Lambertian::new(perlin_texture_index)));
         hittable_index_list.push(service_locator.get_hittable_service_mut().add_hittable(HittableEnum::Sphere(Sphere::new(Vec3::new(0.0, -1000.0, 0.0), 1000.0, perlin_material_index))));
         hittable_index_list.push(service_locator.get_hittable_service_mut().add_hittable(HittableEnum::Sphere(Sphere::new(Vec3::new( 0.0, 2.0, 0.0), 2.0, perlin_material_index))));
@@ -607,10 +638,7 @@ impl SceneBuilder {
         hittable_index_list.push(service_locator.get_hittable_service_mut().add_hittable(HittableEnum::Sphere(Sphere::new(Vec3::new(400.0, 200.0, 400.0), 100.0, earth_material_index))));
     
         
-        // The Noise Texture runs pretty deep
-        // I just need some determinism, not all the way
-        let mut thread_rng: ThreadRng = rand::thread_rng();
-        let perlin_texture_index: usize = service_locator.get_texture_service_mut().add_texture(TextureEnum::Noise(Noise::new(&mut thread_rng, perlin_element_count, 0.1)));
+        let perlin_texture_index: usize = service_locator.get_texture_service_mut().add_texture(TextureEnum::Noise(Noise::new(rng.gen(), perlin_element_count, 0.1)));
         let perlin_material_index = service_locator.get_material_service_mut().add_material(MaterialEnum::Lambertian(Lambertian::new(perlin_texture_index)));
         hittable_index_list.push(service_locator.get_hittable_service_mut().add_hittable(HittableEnum::Sphere(Sphere::new(Vec3::new( 220.0, 280.0, 300.0), 80.0, perlin_material_index))));
     
@@ -657,4 +685,938 @@ impl SceneBuilder {
 
         service_locator
     }
-}
\ No newline at end of file
+
+    fn cornell_box_oren_nayar_scene(config: &RenderConfig, element_count: u32) -> ServiceLocator {
+        let (mut rng, mut service_locator, mut hittable_index_list, light_index_list) = Self::empty_cornell_box_scene_prebuild(config);
+
+        let clay_texture_index: usize = service_locator.get_texture_service_mut().add_texture(TextureEnum::SolidColor(SolidColor::from_color(&ColorRGB::new(0.75, 0.45, 0.3))));
+        let cloth_texture_index: usize = service_locator.get_texture_service_mut().add_texture(TextureEnum::SolidColor(SolidColor::from_color(&ColorRGB::new(0.3, 0.35, 0.7))));
+
+        let noisy_sigma_texture_index: usize = service_locator.get_texture_service_mut().add_texture(TextureEnum::Noise(Noise::new(rng.gen(), element_count, 0.05)));
+        let rough_sigma_texture_index: usize = service_locator.get_texture_service_mut().add_texture(TextureEnum::SolidColor(SolidColor::from_color(&ColorRGB::new(1.0, 1.0, 1.0))));
+
+        let clay_material_index: usize = service_locator.get_material_service_mut().add_material(MaterialEnum::OrenNayar(OrenNayar::new(clay_texture_index, noisy_sigma_texture_index)));
+        let cloth_material_index: usize = service_locator.get_material_service_mut().add_material(MaterialEnum::OrenNayar(OrenNayar::new(cloth_texture_index, rough_sigma_texture_index)));
+
+        let box_1: BoxHittable = BoxHittable::new(&mut rng, service_locator.get_hittable_service_mut(), Vec3::zero(), Vec3::new(165.0, 330.0, 165.0), clay_material_index);
+        let box_1_index: usize = service_locator.get_hittable_service_mut().add_hittable(HittableEnum::BoxHittable(box_1));
+        let box_1_rotation: RotateY = RotateY::new(service_locator.get_hittable_service_mut(), 15.0, box_1_index);
+        let box_1_rotation_index: usize = service_locator.get_hittable_service_mut().add_hittable(HittableEnum::RotateY(box_1_rotation));
+        let box_1_translated_index: usize = service_locator.get_hittable_service_mut().add_hittable(HittableEnum::Translate(Translate::new(Vec3::new( 265.0, 0.0, 295.0 ), box_1_rotation_index)));
+        hittable_index_list.push(box_1_translated_index);
+
+        let sphere_index: usize = service_locator.get_hittable_service_mut().add_hittable(HittableEnum::Sphere(Sphere::new(Vec3::new( 190.0, 90.0, 190.0), 90.0, cloth_material_index)));
+        hittable_index_list.push(sphere_index);
+
+        build_acceleration_structures(&mut rng, &mut service_locator, hittable_index_list, light_index_list);
+
+        service_locator
+    }
+
+    fn cornell_box_blend_and_mask_scene(config: &RenderConfig, element_count: u32) -> ServiceLocator {
+        let (mut rng, mut service_locator, mut hittable_index_list, light_index_list) = Self::empty_cornell_box_scene_prebuild(config);
+
+        let metal_material_index: usize = service_locator.get_material_service_mut().add_material(MaterialEnum::Metal(Metal::new(ColorRGB::new(0.8, 0.85, 0.88), 0.05)));
+        let rust_texture_index: usize = service_locator.get_texture_service_mut().add_texture(TextureEnum::SolidColor(SolidColor::from_color(&ColorRGB::new(0.45, 0.2, 0.07))));
+        let rust_material_index: usize = service_locator.get_material_service_mut().add_material(MaterialEnum::Lambertian(Lambertian::new(rust_texture_index)));
+
+        let rust_mask_texture_index: usize = service_locator.get_texture_service_mut().add_texture(TextureEnum::Noise(Noise::new(rng.gen(), element_count, 0.1)));
+        let rusty_metal_material_index: usize = service_locator.get_material_service_mut().add_material(MaterialEnum::Blend(Blend::new(metal_material_index, rust_material_index, rust_mask_texture_index)));
+
+        let box_1: BoxHittable = BoxHittable::new(&mut rng, service_locator.get_hittable_service_mut(), Vec3::zero(), Vec3::new(165.0, 330.0, 165.0), rusty_metal_material_index);
+        let box_1_index: usize = service_locator.get_hittable_service_mut().add_hittable(HittableEnum::BoxHittable(box_1));
+        let box_1_rotation: RotateY = RotateY::new(service_locator.get_hittable_service_mut(), 15.0, box_1_index);
+        let box_1_rotation_index: usize = service_locator.get_hittable_service_mut().add_hittable(HittableEnum::RotateY(box_1_rotation));
+        let box_1_translated_index: usize = service_locator.get_hittable_service_mut().add_hittable(HittableEnum::Translate(Translate::new(Vec3::new( 265.0, 0.0, 295.0 ), box_1_rotation_index)));
+        hittable_index_list.push(box_1_translated_index);
+
+        let fence_texture_index: usize = service_locator.get_texture_service_mut().add_texture(TextureEnum::Image(Image::new("fence.png")));
+        let fence_material_index: usize = service_locator.get_material_service_mut().add_material(MaterialEnum::Lambertian(Lambertian::new(fence_texture_index)));
+        let fence_cutout_material_index: usize = service_locator.get_material_service_mut().add_material(MaterialEnum::AlphaMask(AlphaMask::new(fence_material_index, fence_texture_index)));
+        hittable_index_list.push(service_locator.get_hittable_service_mut().add_hittable(HittableEnum::XYRect(XYRect::new(80.0, 475.0, 0.0, 260.0, 120.0, fence_cutout_material_index))));
+
+        build_acceleration_structures(&mut rng, &mut service_locator, hittable_index_list, light_index_list);
+
+        service_locator
+    }
+
+    fn cornell_box_coated_scene(config: &RenderConfig, element_count: u32) -> ServiceLocator {
+        let (mut rng, mut service_locator, mut hittable_index_list, light_index_list) = Self::empty_cornell_box_scene_prebuild(config);
+
+        let clear_coat_texture_index: usize = service_locator.get_texture_service_mut().add_texture(TextureEnum::SolidColor(SolidColor::from_color(&ColorRGB::new(1.0, 1.0, 1.0))));
+        let varnish_texture_index: usize = service_locator.get_texture_service_mut().add_texture(TextureEnum::SolidColor(SolidColor::from_color(&ColorRGB::new(0.95, 0.8, 0.55))));
+
+        // Car paint, a smooth clearcoat over a red diffuse base
+        let paint_texture_index: usize = service_locator.get_texture_service_mut().add_texture(TextureEnum::SolidColor(SolidColor::from_color(&ColorRGB::new(0.6, 0.04, 0.04))));
+        let paint_material_index: usize = service_locator.get_material_service_mut().add_material(MaterialEnum::Lambertian(Lambertian::new(paint_texture_index)));
+        let car_paint_material_index: usize = service_locator.get_material_service_mut().add_material(MaterialEnum::Coated(Coated::new(paint_material_index, clear_coat_texture_index, 1.5, 0.0)));
+
+        // Varnished wood, a slightly rough amber coat over a noisy diffuse base
+        let wood_texture_index: usize = service_locator.get_texture_service_mut().add_texture(TextureEnum::Noise(Noise::new(rng.gen(), element_count, 0.2)));
+        let wood_material_index: usize = service_locator.get_material_service_mut().add_material(MaterialEnum::Lambertian(Lambertian::new(wood_texture_index)));
+        let varnished_wood_material_index: usize = service_locator.get_material_service_mut().add_material(MaterialEnum::Coated(Coated::new(wood_material_index, varnish_texture_index, 1.5, 0.1)));
+
+        let box_1: BoxHittable = BoxHittable::new(&mut rng, service_locator.get_hittable_service_mut(), Vec3::zero(), Vec3::new(165.0, 330.0, 165.0), varnished_wood_material_index);
+        let box_1_index: usize = service_locator.get_hittable_service_mut().add_hittable(HittableEnum::BoxHittable(box_1));
+        let box_1_rotation: RotateY = RotateY::new(service_locator.get_hittable_service_mut(), 15.0, box_1_index);
+        let box_1_rotation_index: usize = service_locator.get_hittable_service_mut().add_hittable(HittableEnum::RotateY(box_1_rotation));
+        let box_1_translated_index: usize = service_locator.get_hittable_service_mut().add_hittable(HittableEnum::Translate(Translate::new(Vec3::new( 265.0, 0.0, 295.0 ), box_1_rotation_index)));
+        hittable_index_list.push(box_1_translated_index);
+
+        let sphere_index: usize = service_locator.get_hittable_service_mut().add_hittable(HittableEnum::Sphere(Sphere::new(Vec3::new( 190.0, 90.0, 190.0), 90.0, car_paint_material_index)));
+        hittable_index_list.push(sphere_index);
+
+        build_acceleration_structures(&mut rng, &mut service_locator, hittable_index_list, light_index_list);
+
+        service_locator
+    }
+
+    fn thin_film_scene(config: &RenderConfig, element_count: u32) -> ServiceLocator {
+        // Camera
+        let look_from: Vec3 = Vec3::new(13.0, 2.0, 3.0);
+        let look_at: Vec3 = Vec3::new( 0.0, 1.0, 0.0);
+        let v_up: Vec3 = Vec3::new(0.0, 1.0, 0.0);
+        let dist_to_focus: f32 = 15.0;
+        let aperture: f32 = 0.05;
+        let time_0: f32 = 0.0;
+        let time_1: f32 = 1.0;
+        let camera: Camera = Camera::new(look_from, look_at, v_up,20.0, config.aspect_ratio, aperture, dist_to_focus, time_0, time_1);
+
+        let background: ColorRGB = ColorRGB::new(0.7, 0.8, 1.0);
+
+        let (mut rng, mut service_locator, mut hittable_index_list, mut _light_index_list) = init_build_resources(config, camera, background);
+
+        let checker_odd_texture_index: usize = service_locator.get_texture_service_mut().add_texture(TextureEnum::SolidColor(SolidColor::from_color(&ColorRGB::new(0.2, 0.3, 0.1))));
+        let checker_even_texture_index: usize = service_locator.get_texture_service_mut().add_texture(TextureEnum::SolidColor(SolidColor::from_color(&ColorRGB::new(0.9, 0.9, 0.9))));
+        let checker_texture_index: usize = service_locator.get_texture_service_mut().add_texture(TextureEnum::Checker(Checker::new(checker_odd_texture_index, checker_even_texture_index)));
+        let checker_material_index: usize = service_locator.get_material_service_mut().add_material(MaterialEnum::Lambertian(Lambertian::new(checker_texture_index)));
+        hittable_index_list.push(service_locator.get_hittable_service_mut().add_hittable(HittableEnum::Sphere(Sphere::new(Vec3::new( 0.0, -1000.0, 0.0), 1000.0, checker_material_index))));
+
+        // Soap bubble, a swirly water film with air on both sides
+        let bubble_thickness_texture_index: usize = service_locator.get_texture_service_mut().add_texture(TextureEnum::Noise(Noise::new(rng.gen(), element_count, 2.0)));
+        let bubble_material_index: usize = service_locator.get_material_service_mut().add_material(MaterialEnum::ThinFilm(ThinFilm::over_dielectric(bubble_thickness_texture_index, 800.0, 1.33, 1.0)));
+        hittable_index_list.push(service_locator.get_hittable_service_mut().add_hittable(HittableEnum::Sphere(Sphere::new(Vec3::new( 0.0, 1.0, 1.2), 1.0, bubble_material_index))));
+
+        // Anodized titanium, an oxide layer of constant thickness on a conductor
+        let oxide_thickness_texture_index: usize = service_locator.get_texture_service_mut().add_texture(TextureEnum::SolidColor(SolidColor::from_color(&ColorRGB::new(0.5, 0.5, 0.5))));
+        let titanium_eta: ColorRGB = ColorRGB::new(2.16, 1.93, 1.72);
+        let titanium_k: ColorRGB = ColorRGB::new(2.93, 2.68, 2.44);
+        let anodized_material_index: usize = service_locator.get_material_service_mut().add_material(MaterialEnum::ThinFilm(ThinFilm::over_conductor(oxide_thickness_texture_index, 500.0, 2.2, titanium_eta, titanium_k)));
+        hittable_index_list.push(service_locator.get_hittable_service_mut().add_hittable(HittableEnum::Sphere(Sphere::new(Vec3::new( 0.0, 1.0, -1.2), 1.0, anodized_material_index))));
+
+        build_acceleration_structures(&mut rng, &mut service_locator, hittable_index_list, _light_index_list);
+
+        service_locator
+    }
+
+    fn cornell_box_subsurface_scene(config: &RenderConfig) -> ServiceLocator {
+        let (mut rng, mut service_locator, mut hittable_index_list, light_index_list) = Self::empty_cornell_box_scene_prebuild(config);
+
+        // Subsurface objects are a dielectric boundary with a medium inside it
+        let index_of_refraction: f32 = 1.4;
+        let boundary_material_index: usize = service_locator.get_material_service_mut().add_material(MaterialEnum::Dielectric(Dielectric{index_of_refraction, inverse_index_of_refraction: 1.0 / index_of_refraction}));
+
+        let marble_texture_index: usize = service_locator.get_texture_service_mut().add_texture(TextureEnum::SolidColor(SolidColor::from_color(&ColorRGB::new(0.83, 0.79, 0.75))));
+        let marble_material_index: usize = service_locator.get_material_service_mut().add_material(MaterialEnum::Subsurface(Subsurface::new(marble_texture_index)));
+
+        let box_1: BoxHittable = BoxHittable::new(&mut rng, service_locator.get_hittable_service_mut(), Vec3::zero(), Vec3::new(165.0, 330.0, 165.0), boundary_material_index);
+        let box_1_index: usize = service_locator.get_hittable_service_mut().add_hittable(HittableEnum::BoxHittable(box_1));
+        let box_1_rotation: RotateY = RotateY::new(service_locator.get_hittable_service_mut(), 15.0, box_1_index);
+        let box_1_rotation_index: usize = service_locator.get_hittable_service_mut().add_hittable(HittableEnum::RotateY(box_1_rotation));
+        let box_1_translated_index: usize = service_locator.get_hittable_service_mut().add_hittable(HittableEnum::Translate(Translate::new(Vec3::new( 265.0, 0.0, 295.0 ), box_1_rotation_index)));
+        hittable_index_list.push(box_1_translated_index);
+        hittable_index_list.push(service_locator.get_hittable_service_mut().add_hittable(HittableEnum::ConstantMedium(ConstantMedium::from_mean_free_path(box_1_translated_index, marble_material_index, 40.0))));
+
+        let wax_texture_index: usize = service_locator.get_texture_service_mut().add_texture(TextureEnum::SolidColor(SolidColor::from_color(&ColorRGB::new(0.9, 0.6, 0.3))));
+        let wax_material_index: usize = service_locator.get_material_service_mut().add_material(MaterialEnum::Subsurface(Subsurface::new(wax_texture_index)));
+
+        let sphere_index: usize = service_locator.get_hittable_service_mut().add_hittable(HittableEnum::Sphere(Sphere::new(Vec3::new( 190.0, 90.0, 190.0), 90.0, boundary_material_index)));
+        hittable_index_list.push(sphere_index);
+        hittable_index_list.push(service_locator.get_hittable_service_mut().add_hittable(HittableEnum::ConstantMedium(ConstantMedium::from_mean_free_path(sphere_index, wax_material_index, 25.0))));
+
+        build_acceleration_structures(&mut rng, &mut service_locator, hittable_index_list, light_index_list);
+
+        service_locator
+    }
+
+    fn cornell_box_bump_and_normal_map_scene(config: &RenderConfig, element_count: u32) -> ServiceLocator {
+        let (mut rng, mut service_locator, mut hittable_index_list, light_index_list) = Self::empty_cornell_box_scene_prebuild(config);
+
+        // Beveled tiles on the floor from a tangent space normal map
+        let tile_texture_index: usize = service_locator.get_texture_service_mut().add_texture(TextureEnum::SolidColor(SolidColor::from_color(&ColorRGB::new(0.8, 0.8, 0.75))));
+        let tile_material_index: usize = service_locator.get_material_service_mut().add_material(MaterialEnum::Lambertian(Lambertian::new(tile_texture_index)));
+        let tile_normals_texture_index: usize = service_locator.get_texture_service_mut().add_texture(TextureEnum::Image(Image::new_data("tiles_normal.png")));
+        let tiled_floor_material_index: usize = service_locator.get_material_service_mut().add_material(MaterialEnum::NormalMap(NormalMap::new(tile_material_index, tile_normals_texture_index, 1.0)));
+        hittable_index_list.push(service_locator.get_hittable_service_mut().add_hittable(HittableEnum::XZRect(XZRect::new(0.0, 555.0, 0.0, 555.0, 0.5, tiled_floor_material_index))));
+
+        // Hammered metal and rough plaster, both bumped by a noise height field
+        let height_texture_index: usize = service_locator.get_texture_service_mut().add_texture(TextureEnum::Noise(Noise::new(rng.gen(), element_count, 0.1)));
+
+        let metal_material_index: usize = service_locator.get_material_service_mut().add_material(MaterialEnum::Metal(Metal::new(ColorRGB::new(0.8, 0.85, 0.88), 0.0)));
+        let hammered_metal_material_index: usize = service_locator.get_material_service_mut().add_material(MaterialEnum::BumpMap(BumpMap::new(metal_material_index, height_texture_index, 0.1, HeightSpace::Solid)));
+        hittable_index_list.push(service_locator.get_hittable_service_mut().add_hittable(HittableEnum::Sphere(Sphere::new(Vec3::new( 190.0, 90.0, 190.0), 90.0, hammered_metal_material_index))));
+
+        let plaster_texture_index: usize = service_locator.get_texture_service_mut().add_texture(TextureEnum::SolidColor(SolidColor::from_color(&ColorRGB::new(0.73, 0.73, 0.73))));
+        let plaster_material_index: usize = service_locator.get_material_service_mut().add_material(MaterialEnum::Lambertian(Lambertian::new(plaster_texture_index)));
+        let rough_plaster_material_index: usize = service_locator.get_material_service_mut().add_material(MaterialEnum::BumpMap(BumpMap::new(plaster_material_index, height_texture_index, 0.2, HeightSpace::Solid)));
+
+        let box_1: BoxHittable = BoxHittable::new(&mut rng, service_locator.get_hittable_service_mut(), Vec3::zero(), Vec3::new(165.0, 330.0, 165.0), rough_plaster_material_index);
+        let box_1_index: usize = service_locator.get_hittable_service_mut().add_hittable(HittableEnum::BoxHittable(box_1));
+        let box_1_rotation: RotateY = RotateY::new(service_locator.get_hittable_service_mut(), 15.0, box_1_index);
+        let box_1_rotation_index: usize = service_locator.get_hittable_service_mut().add_hittable(HittableEnum::RotateY(box_1_rotation));
+        let box_1_translated_index: usize = service_locator.get_hittable_service_mut().add_hittable(HittableEnum::Translate(Translate::new(Vec3::new( 265.0, 0.0, 295.0 ), box_1_rotation_index)));
+        hittable_index_list.push(box_1_translated_index);
+
+        // An embossed globe, the continents of the earth map raised off the oceans in uv space
+        let globe_height_texture_index: usize = service_locator.get_texture_service_mut().add_texture(TextureEnum::Image(Image::new_data("earthmap.png").with_filter(ImageFilter::Bilinear).with_wrap_mode(WrapMode::Repeat)));
+        let globe_texture_index: usize = service_locator.get_texture_service_mut().add_texture(TextureEnum::SolidColor(SolidColor::from_color(&ColorRGB::new(0.75, 0.6, 0.4))));
+        let globe_material_index: usize = service_locator.get_material_service_mut().add_material(MaterialEnum::Lambertian(Lambertian::new(globe_texture_index)));
+        let embossed_globe_material_index: usize = service_locator.get_material_service_mut().add_material(MaterialEnum::BumpMap(BumpMap::new(globe_material_index, globe_height_texture_index, 0.006, HeightSpace::UV)));
+        hittable_index_list.push(service_locator.get_hittable_service_mut().add_hittable(HittableEnum::Sphere(Sphere::new(Vec3::new(430.0, 60.0, 120.0), 60.0, embossed_globe_material_index))));
+
+        build_acceleration_structures(&mut rng, &mut service_locator, hittable_index_list, light_index_list);
+
+        service_locator
+    }
+
+    // Needs a file from the MERL BRDF database, https://www.merl.com/brdf/
+    fn cornell_box_measured_brdf_scene(config: &RenderConfig) -> ServiceLocator {
+        let (mut rng, mut service_locator, mut hittable_index_list, light_index_list) = Self::empty_cornell_box_scene_prebuild(config);
+
+        // The MERL database is too large to ship, download it and place the file in brdfs/ to see it.
+        // Without it the box is rendered empty.
+        match MeasuredBRDF::load("brdfs/gold-metallic-paint.binary") {
+            Ok(measured_brdf) => {
+                let measured_material_index: usize = service_locator.get_material_service_mut().add_material(MaterialEnum::MeasuredBRDF(measured_brdf));
+                hittable_index_list.push(service_locator.get_hittable_service_mut().add_hittable(HittableEnum::Sphere(Sphere::new(Vec3::new( 278.0, 150.0, 278.0), 150.0, measured_material_index))));
+            },
+            Err(error) => println!("Skipping the measured brdf, {}", error),
+        }
+
+        build_acceleration_structures(&mut rng, &mut service_locator, hittable_index_list, light_index_list);
+
+        service_locator
+    }
+
+    fn cornell_box_cloth_scene(config: &RenderConfig) -> ServiceLocator {
+        let (mut rng, mut service_locator, mut hittable_index_list, light_index_list) = Self::empty_cornell_box_scene_prebuild(config);
+
+        // Dark red velvet with a pale, wide sheen
+        let velvet_texture_index: usize = service_locator.get_texture_service_mut().add_texture(TextureEnum::SolidColor(SolidColor::from_color(&ColorRGB::new(0.25, 0.02, 0.04))));
+        let velvet_sheen_texture_index: usize = service_locator.get_texture_service_mut().add_texture(TextureEnum::SolidColor(SolidColor::from_color(&ColorRGB::new(1.0, 0.75, 0.8))));
+        let velvet_roughness_texture_index: usize = service_locator.get_texture_service_mut().add_texture(TextureEnum::SolidColor(SolidColor::from_color(&ColorRGB::new(0.8, 0.8, 0.8))));
+        let velvet_material_index: usize = service_locator.get_material_service_mut().add_material(MaterialEnum::Sheen(Sheen::new(velvet_texture_index, velvet_sheen_texture_index, velvet_roughness_texture_index)));
+
+        // Blue cotton with a tight, faint sheen, checkered to show off the textured sheen
+        let cotton_texture_index: usize = service_locator.get_texture_service_mut().add_texture(TextureEnum::SolidColor(SolidColor::from_color(&ColorRGB::new(0.1, 0.15, 0.45))));
+        let faint_sheen_texture_index: usize = service_locator.get_texture_service_mut().add_texture(TextureEnum::SolidColor(SolidColor::from_color(&ColorRGB::new(0.2, 0.2, 0.2))));
+        let strong_sheen_texture_index: usize = service_locator.get_texture_service_mut().add_texture(TextureEnum::SolidColor(SolidColor::from_color(&ColorRGB::new(0.8, 0.8, 0.8))));
+        let cotton_sheen_texture_index: usize = service_locator.get_texture_service_mut().add_texture(TextureEnum::Checker(Checker::new(faint_sheen_texture_index, strong_sheen_texture_index)));
+        let cotton_roughness_texture_index: usize = service_locator.get_texture_service_mut().add_texture(TextureEnum::SolidColor(SolidColor::from_color(&ColorRGB::new(0.4, 0.4, 0.4))));
+        let cotton_material_index: usize = service_locator.get_material_service_mut().add_material(MaterialEnum::Sheen(Sheen::new(cotton_texture_index, cotton_sheen_texture_index, cotton_roughness_texture_index)));
+
+        let box_1: BoxHittable = BoxHittable::new(&mut rng, service_locator.get_hittable_service_mut(), Vec3::zero(), Vec3::new(165.0, 330.0, 165.0), cotton_material_index);
+        let box_1_index: usize = service_locator.get_hittable_service_mut().add_hittable(HittableEnum::BoxHittable(box_1));
+        let box_1_rotation: RotateY = RotateY::new(service_locator.get_hittable_service_mut(), 15.0, box_1_index);
+        let box_1_rotation_index: usize = service_locator.get_hittable_service_mut().add_hittable(HittableEnum::RotateY(box_1_rotation));
+        let box_1_translated_index: usize = service_locator.get_hittable_service_mut().add_hittable(HittableEnum::Translate(Translate::new(Vec3::new( 265.0, 0.0, 295.0 ), box_1_rotation_index)));
+        hittable_index_list.push(box_1_translated_index);
+
+        let sphere_index: usize = service_locator.get_hittable_service_mut().add_hittable(HittableEnum::Sphere(Sphere::new(Vec3::new( 190.0, 90.0, 190.0), 90.0, velvet_material_index)));
+        hittable_index_list.push(sphere_index);
+
+        build_acceleration_structures(&mut rng, &mut service_locator, hittable_index_list, light_index_list);
+
+        service_locator
+    }
+
+    fn cornell_box_conductors_scene(config: &RenderConfig) -> ServiceLocator {
+        let (mut rng, mut service_locator, mut hittable_index_list, light_index_list) = Self::empty_cornell_box_scene_prebuild(config);
+
+        let conductors: [(&str, f32); 4] = [("gold", 0.0), ("copper", 0.1), ("aluminium", 0.2), ("silver", 0.0)];
+        for (column_index, (name, roughness)) in conductors.iter().enumerate() {
+            let conductor: Conductor = Conductor::preset(name, *roughness).expect("unknown conductor preset");
+            let conductor_material_index: usize = service_locator.get_material_service_mut().add_material(MaterialEnum::Conductor(conductor));
+
+            let center: Vec3 = Vec3::new(100.0 + 118.0 * column_index as f32, 55.0, 278.0);
+            hittable_index_list.push(service_locator.get_hittable_service_mut().add_hittable(HittableEnum::Sphere(Sphere::new(center, 55.0, conductor_material_index))));
+        }
+
+        build_acceleration_structures(&mut rng, &mut service_locator, hittable_index_list, light_index_list);
+
+        service_locator
+    }
+
+    fn texture_filtering_scene(config: &RenderConfig) -> ServiceLocator {
+        // Camera
+        let look_from: Vec3 = Vec3::new(0.0, 2.0, 12.0);
+        let look_at: Vec3 = Vec3::new( 0.0, 0.0, -20.0);
+        let v_up: Vec3 = Vec3::new(0.0, 1.0, 0.0);
+        let dist_to_focus: f32 = 10.0;
+        let aperture: f32 = 0.0;
+        let time_0: f32 = 0.0;
+        let time_1: f32 = 1.0;
+        let camera: Camera = Camera::new(look_from, look_at, v_up, 40.0, config.aspect_ratio, aperture, dist_to_focus, time_0, time_1);
+
+        let background: ColorRGB = ColorRGB::new(0.7, 0.8, 1.0);
+
+        let (mut rng, mut service_locator, mut hittable_index_list, mut _light_index_list) = init_build_resources(config, camera, background);
+
+        // Three strips of floor running into the distance, tiled with the same map.
+        // Left is nearest and repeated, the middle is bilinear, mirrored and rotated, right is repeated and trilinear,
+        // which stays sharp up close and fades to the average color in the distance instead of aliasing.
+        let tiling: UVTransform = UVTransform::new(4.0, 40.0, 0.0, 0.0, 0.0);
+        let nearest_texture_index: usize = service_locator.get_texture_service_mut().add_texture(TextureEnum::Image(
+            Image::new("earthmap.png")
+                .with_wrap_mode(WrapMode::Repeat)
+                .with_uv_transform(tiling)
+        ));
+        let bilinear_texture_index: usize = service_locator.get_texture_service_mut().add_texture(TextureEnum::Image(
+            Image::new("earthmap.png")
+                .with_filter(ImageFilter::Bilinear)
+                .with_wrap_mode(WrapMode::Mirror)
+                .with_uv_transform(UVTransform::new(4.0, 40.0, 0.0, 0.0, 0.3))
+        ));
+        let trilinear_texture_index: usize = service_locator.get_texture_service_mut().add_texture(TextureEnum::Image(
+            Image::new("earthmap.png")
+                .with_filter(ImageFilter::Trilinear)
+                .with_wrap_mode(WrapMode::Repeat)
+                .with_uv_transform(tiling)
+        ));
+
+        let strip_texture_indices: [usize; 3] = [nearest_texture_index, bilinear_texture_index, trilinear_texture_index];
+        for (strip_index, texture_index) in strip_texture_indices.iter().enumerate() {
+            let material_index: usize = service_locator.get_material_service_mut().add_material(MaterialEnum::Lambertian(Lambertian::new(*texture_index)));
+            let x0: f32 = -6.0 + 4.0 * strip_index as f32;
+            hittable_index_list.push(service_locator.get_hittable_service_mut().add_hittable(HittableEnum::XZRect(XZRect::new(x0, x0 + 3.9, -100.0, 10.0, 0.0, material_index))));
+        }
+
+        build_acceleration_structures(&mut rng, &mut service_locator, hittable_index_list, _light_index_list);
+
+        service_locator
+    }
+
+    fn hdr_dome_scene(config: &RenderConfig) -> ServiceLocator {
+        // Camera
+        let look_from: Vec3 = Vec3::new(13.0, 2.0, 3.0);
+        let look_at: Vec3 = Vec3::new( 0.0, 1.0, 0.0);
+        let v_up: Vec3 = Vec3::new(0.0, 1.0, 0.0);
+        let dist_to_focus: f32 = 10.0;
+        let aperture: f32 = 0.0;
+        let time_0: f32 = 0.0;
+        let time_1: f32 = 1.0;
+        let camera: Camera = Camera::new(look_from, look_at, v_up, 25.0, config.aspect_ratio, aperture, dist_to_focus, time_0, time_1);
+
+        let background: ColorRGB = ColorRGB::black();
+
+        let (mut rng, mut service_locator, mut hittable_index_list, mut _light_index_list) = init_build_resources(config, camera, background);
+
+        // All of the light comes from a dome with a high dynamic range sunset on the inside
+        let sunset_texture_index: usize = service_locator.get_texture_service_mut().add_texture(TextureEnum::Image(Image::new("sunset.pfm").with_filter(ImageFilter::Bilinear)));
+        let sunset_material_index: usize = service_locator.get_material_service_mut().add_material(MaterialEnum::DiffuseLight(DiffuseLight::new(sunset_texture_index)));
+        let dome_index: usize = service_locator.get_hittable_service_mut().add_hittable(HittableEnum::Sphere(Sphere::new(Vec3::zero(), 100.0, sunset_material_index)));
+        hittable_index_list.push(service_locator.get_hittable_service_mut().add_hittable(HittableEnum::FlipFace(FlipFace::new(dome_index))));
+
+        let ground_texture_index: usize = service_locator.get_texture_service_mut().add_texture(TextureEnum::SolidColor(SolidColor::from_color(&ColorRGB::new(0.4, 0.4, 0.4))));
+        let ground_material_index: usize = service_locator.get_material_service_mut().add_material(MaterialEnum::Lambertian(Lambertian::new(ground_texture_index)));
+        hittable_index_list.push(service_locator.get_hittable_service_mut().add_hittable(HittableEnum::Sphere(Sphere::new(Vec3::new( 0.0, -1000.0, 0.0), 1000.0, ground_material_index))));
+
+        let diffuse_texture_index: usize = service_locator.get_texture_service_mut().add_texture(TextureEnum::SolidColor(SolidColor::from_color(&ColorRGB::new(0.8, 0.8, 0.8))));
+        let diffuse_material_index: usize = service_locator.get_material_service_mut().add_material(MaterialEnum::Lambertian(Lambertian::new(diffuse_texture_index)));
+        hittable_index_list.push(service_locator.get_hittable_service_mut().add_hittable(HittableEnum::Sphere(Sphere::new(Vec3::new( 0.0, 1.0, 1.2), 1.0, diffuse_material_index))));
+
+        let metal_material_index: usize = service_locator.get_material_service_mut().add_material(MaterialEnum::Metal(Metal::new(ColorRGB::new(0.9, 0.9, 0.9), 0.0)));
+        hittable_index_list.push(service_locator.get_hittable_service_mut().add_hittable(HittableEnum::Sphere(Sphere::new(Vec3::new( 0.0, 1.0, -1.2), 1.0, metal_material_index))));
+
+        build_acceleration_structures(&mut rng, &mut service_locator, hittable_index_list, _light_index_list);
+
+        service_locator
+    }
+
+    fn procedural_textures_scene(config: &RenderConfig, element_count: u32) -> ServiceLocator {
+        // Camera
+        let look_from: Vec3 = Vec3::new(13.0, 3.0, 3.0);
+        let look_at: Vec3 = Vec3::new( 0.0, 1.0, 0.0);
+        let v_up: Vec3 = Vec3::new(0.0, 1.0, 0.0);
+        let dist_to_focus: f32 = 10.0;
+        let aperture: f32 = 0.0;
+        let time_0: f32 = 0.0;
+        let time_1: f32 = 1.0;
+        let camera: Camera = Camera::new(look_from, look_at, v_up, 35.0, config.aspect_ratio, aperture, dist_to_focus, time_0, time_1);
+
+        let background: ColorRGB = ColorRGB::new(0.7, 0.8, 1.0);
+
+        let (mut rng, mut service_locator, mut hittable_index_list, mut _light_index_list) = init_build_resources(config, camera, background);
+
+
+        // Flagstones, the borders between Worley cells colored as grout
+        let cells_texture_index: usize = service_locator.get_texture_service_mut().add_texture(TextureEnum::Worley(Worley::new(rng.gen(), 0.8, WorleyPattern::F2MinusF1)));
+        let flagstones_texture_index: usize = service_locator.get_texture_service_mut().add_texture(TextureEnum::ColorRamp(ColorRamp::new(cells_texture_index, vec![(0.0, ColorRGB::new(0.1, 0.1, 0.1)), (0.08, ColorRGB::new(0.45, 0.42, 0.38)), (1.0, ColorRGB::new(0.6, 0.58, 0.52))])));
+
+        // Clouds, a few octaves of simplex noise
+        let clouds_fbm: Fbm = Fbm::new(NoiseBasis::Simplex(Simplex::new(rng.gen())), 1.5, 6, 2.0, 0.5);
+        let clouds_fbm_index: usize = service_locator.get_texture_service_mut().add_texture(TextureEnum::Fbm(clouds_fbm));
+        let clouds_texture_index: usize = service_locator.get_texture_service_mut().add_texture(TextureEnum::ColorRamp(ColorRamp::new(clouds_fbm_index, vec![(0.35, ColorRGB::new(0.1, 0.3, 0.8)), (0.65, ColorRGB::new(0.95, 0.95, 0.95))])));
+
+        // Moss, rougher Perlin fbm with a higher gain
+        let moss_fbm: Fbm = Fbm::new(NoiseBasis::Perlin(Perlin::new(rng.gen(), element_count)), 3.0, 5, 2.2, 0.7);
+        let moss_fbm_index: usize = service_locator.get_texture_service_mut().add_texture(TextureEnum::Fbm(moss_fbm));
+        let moss_texture_index: usize = service_locator.get_texture_service_mut().add_texture(TextureEnum::ColorRamp(ColorRamp::new(moss_fbm_index, vec![(0.3, ColorRGB::new(0.05, 0.12, 0.02)), (0.7, ColorRGB::new(0.4, 0.55, 0.1))])));
+
+        let marble_pattern_index: usize = service_locator.get_texture_service_mut().add_texture(TextureEnum::Marble(Marble::new(rng.gen(), element_count, 4.0, 6.0, 5)));
+        let marble_texture_index: usize = service_locator.get_texture_service_mut().add_texture(TextureEnum::ColorRamp(ColorRamp::new(marble_pattern_index, vec![(0.0, ColorRGB::new(0.15, 0.15, 0.17)), (0.4, ColorRGB::new(0.85, 0.85, 0.82)), (1.0, ColorRGB::new(0.95, 0.95, 0.93))])));
+
+        let wood_pattern_index: usize = service_locator.get_texture_service_mut().add_texture(TextureEnum::Wood(Wood::new(rng.gen(), element_count, 6.0, 1.5)));
+        let wood_texture_index: usize = service_locator.get_texture_service_mut().add_texture(TextureEnum::ColorRamp(ColorRamp::new(wood_pattern_index, vec![(0.0, ColorRGB::new(0.75, 0.5, 0.28)), (1.0, ColorRGB::new(0.35, 0.18, 0.07))])));
+
+        // Scales, round Worley cells
+        let scales_pattern_index: usize = service_locator.get_texture_service_mut().add_texture(TextureEnum::Worley(Worley::new(rng.gen(), 5.0, WorleyPattern::F1)));
+        let scales_texture_index: usize = service_locator.get_texture_service_mut().add_texture(TextureEnum::ColorRamp(ColorRamp::new(scales_pattern_index, vec![(0.0, ColorRGB::new(0.1, 0.5, 0.4)), (0.8, ColorRGB::new(0.02, 0.08, 0.05))])));
+
+        let ground_material_index: usize = service_locator.get_material_service_mut().add_material(MaterialEnum::Lambertian(Lambertian::new(flagstones_texture_index)));
+        hittable_index_list.push(service_locator.get_hittable_service_mut().add_hittable(HittableEnum::Sphere(Sphere::new(Vec3::new( 0.0, -1000.0, 0.0), 1000.0, ground_material_index))));
+
+        let sphere_texture_indices: [usize; 5] = [clouds_texture_index, moss_texture_index, scales_texture_index, marble_texture_index, wood_texture_index];
+        for (sphere_index, texture_index) in sphere_texture_indices.iter().enumerate() {
+            let material_index: usize = service_locator.get_material_service_mut().add_material(MaterialEnum::Lambertian(Lambertian::new(*texture_index)));
+            let center: Vec3 = Vec3::new(0.0, 0.8, -3.6 + 1.8 * sphere_index as f32);
+            hittable_index_list.push(service_locator.get_hittable_service_mut().add_hittable(HittableEnum::Sphere(Sphere::new(center, 0.8, material_index))));
+        }
+
+        build_acceleration_structures(&mut rng, &mut service_locator, hittable_index_list, _light_index_list);
+
+        service_locator
+    }
+
+    fn texture_nodes_scene(config: &RenderConfig) -> ServiceLocator {
+        // Camera
+        let look_from: Vec3 = Vec3::new(13.0, 3.0, 3.0);
+        let look_at: Vec3 = Vec3::new( 0.0, 1.0, 0.0);
+        let v_up: Vec3 = Vec3::new(0.0, 1.0, 0.0);
+        let dist_to_focus: f32 = 10.0;
+        let aperture: f32 = 0.0;
+        let time_0: f32 = 0.0;
+        let time_1: f32 = 1.0;
+        let camera: Camera = Camera::new(look_from, look_at, v_up, 35.0, config.aspect_ratio, aperture, dist_to_focus, time_0, time_1);
+
+        let background: ColorRGB = ColorRGB::new(0.7, 0.8, 1.0);
+
+        let (mut rng, mut service_locator, mut hittable_index_list, mut _light_index_list) = init_build_resources(config, camera, background);
+        let texture_service = service_locator.get_texture_service_mut();
+
+
+        // Grass growing through the holes of a tiled lattice, the alpha of the fence image is the mask
+        let lattice_index: usize = texture_service.add_texture(TextureEnum::Image(Image::new("fence.png").with_wrap_mode(WrapMode::Repeat)));
+        let tiled_lattice_index: usize = texture_service.add_texture(TextureEnum::UVTransformNode(UVTransformNode::new(lattice_index, UVTransform::new(20.0, 20.0, 0.0, 0.0, 0.0))));
+        let lattice_mask_index: usize = texture_service.add_texture(TextureEnum::ChannelExtract(ChannelExtract::new(tiled_lattice_index, Channel::Alpha)));
+        let grass_index: usize = texture_service.add_texture(TextureEnum::SolidColor(SolidColor::from_color(&ColorRGB::new(0.1, 0.35, 0.05))));
+        let stone_index: usize = texture_service.add_texture(TextureEnum::SolidColor(SolidColor::from_color(&ColorRGB::new(0.5, 0.48, 0.45))));
+        let ground_texture_index: usize = texture_service.add_texture(TextureEnum::Mix(Mix::new(grass_index, stone_index, lattice_mask_index)));
+
+        // The earth map projected along the axes instead of wrapped around the hittable
+        let earth_index: usize = texture_service.add_texture(TextureEnum::Image(Image::new("earthmap.png").with_filter(ImageFilter::Bilinear).with_wrap_mode(WrapMode::Repeat)));
+        let triplanar_earth_index: usize = texture_service.add_texture(TextureEnum::Triplanar(Triplanar::new(earth_index, 0.4, 4.0)));
+
+        // Weathered copper, patina where the simplex fbm is high
+        let copper_index: usize = texture_service.add_texture(TextureEnum::SolidColor(SolidColor::from_color(&ColorRGB::new(0.7, 0.35, 0.2))));
+        let patina_index: usize = texture_service.add_texture(TextureEnum::SolidColor(SolidColor::from_color(&ColorRGB::new(0.3, 0.65, 0.55))));
+        let weathering_index: usize = texture_service.add_texture(TextureEnum::Fbm(Fbm::new(NoiseBasis::Simplex(Simplex::new(rng.gen())), 2.0, 5, 2.0, 0.5)));
+        let weathering_mask_index: usize = texture_service.add_texture(TextureEnum::Remap(Remap::new(weathering_index, 0.45, 0.6, 0.0, 1.0)));
+        let weathered_copper_index: usize = texture_service.add_texture(TextureEnum::Mix(Mix::new(copper_index, patina_index, weathering_mask_index)));
+
+        // The brightness of the earth map, tinted orange
+        let earth_luminance_index: usize = texture_service.add_texture(TextureEnum::ChannelExtract(ChannelExtract::new(earth_index, Channel::Luminance)));
+        let orange_index: usize = texture_service.add_texture(TextureEnum::SolidColor(SolidColor::from_color(&ColorRGB::new(1.0, 0.55, 0.1))));
+        let tinted_earth_index: usize = texture_service.add_texture(TextureEnum::Arithmetic(Arithmetic::new(earth_luminance_index, orange_index, Operation::Multiply)));
+
+        // Bright inverted Worley cells added on top of a dark base
+        let cells_index: usize = texture_service.add_texture(TextureEnum::Worley(Worley::new(rng.gen(), 4.0, WorleyPattern::F1)));
+        let inverted_cells_index: usize = texture_service.add_texture(TextureEnum::Remap(Remap::invert(cells_index)));
+        let dark_base_index: usize = texture_service.add_texture(TextureEnum::SolidColor(SolidColor::from_color(&ColorRGB::new(0.05, 0.0, 0.1))));
+        let glowing_cells_index: usize = texture_service.add_texture(TextureEnum::Arithmetic(Arithmetic::new(dark_base_index, inverted_cells_index, Operation::Add)));
+
+        // Land and water told apart by the channels of the earth map, red plus green minus twice blue is positive on land
+        let earth_red_index: usize = texture_service.add_texture(TextureEnum::ChannelExtract(ChannelExtract::new(earth_index, Channel::Red)));
+        let earth_green_index: usize = texture_service.add_texture(TextureEnum::ChannelExtract(ChannelExtract::new(earth_index, Channel::Green)));
+        let earth_blue_index: usize = texture_service.add_texture(TextureEnum::ChannelExtract(ChannelExtract::new(earth_index, Channel::Blue)));
+        let earth_warm_index: usize = texture_service.add_texture(TextureEnum::Arithmetic(Arithmetic::new(earth_red_index, earth_green_index, Operation::Add)));
+        let earth_cool_index: usize = texture_service.add_texture(TextureEnum::Arithmetic(Arithmetic::new(earth_blue_index, earth_blue_index, Operation::Add)));
+        let earth_land_index: usize = texture_service.add_texture(TextureEnum::Arithmetic(Arithmetic::new(earth_warm_index, earth_cool_index, Operation::Subtract)));
+        let land_mask_index: usize = texture_service.add_texture(TextureEnum::Remap(Remap::new(earth_land_index, 0.0, 0.05, 0.0, 1.0)));
+        let water_index: usize = texture_service.add_texture(TextureEnum::SolidColor(SolidColor::from_color(&ColorRGB::new(0.05, 0.15, 0.4))));
+        let sand_index: usize = texture_service.add_texture(TextureEnum::SolidColor(SolidColor::from_color(&ColorRGB::new(0.8, 0.7, 0.45))));
+        let land_and_water_index: usize = texture_service.add_texture(TextureEnum::Mix(Mix::new(water_index, sand_index, land_mask_index)));
+
+        let ground_material_index: usize = service_locator.get_material_service_mut().add_material(MaterialEnum::Lambertian(Lambertian::new(ground_texture_index)));
+        hittable_index_list.push(service_locator.get_hittable_service_mut().add_hittable(HittableEnum::XZRect(XZRect::new(-20.0, 20.0, -20.0, 20.0, 0.0, ground_material_index))));
+
+        let sphere_texture_indices: [usize; 5] = [triplanar_earth_index, weathered_copper_index, tinted_earth_index, glowing_cells_index, land_and_water_index];
+        for (sphere_index, texture_index) in sphere_texture_indices.iter().enumerate() {
+            let material_index: usize = service_locator.get_material_service_mut().add_material(MaterialEnum::Lambertian(Lambertian::new(*texture_index)));
+            let center: Vec3 = Vec3::new(0.0, 0.8, -3.6 + 1.8 * sphere_index as f32);
+            hittable_index_list.push(service_locator.get_hittable_service_mut().add_hittable(HittableEnum::Sphere(Sphere::new(center, 0.8, material_index))));
+        }
+
+        // The same projection on the flat faces of a box behind the row
+        let earth_box_material_index: usize = service_locator.get_material_service_mut().add_material(MaterialEnum::Lambertian(Lambertian::new(triplanar_earth_index)));
+        let earth_box: BoxHittable = BoxHittable::new(&mut rng, service_locator.get_hittable_service_mut(), Vec3::new(-4.5, 0.0, -1.5), Vec3::new(-2.0, 2.5, 1.5), earth_box_material_index);
+        hittable_index_list.push(service_locator.get_hittable_service_mut().add_hittable(HittableEnum::BoxHittable(earth_box)));
+
+        build_acceleration_structures(&mut rng, &mut service_locator, hittable_index_list, _light_index_list);
+
+        service_locator
+    }
+
+    fn cornell_box_heterogeneous_media_scene(config: &RenderConfig) -> ServiceLocator {
+        let (mut rng, mut service_locator, mut hittable_index_list, light_index_list) = Self::empty_cornell_box_scene_prebuild(config);
+
+        // A smoke plume loaded from a grid, the denser parts are sooty and darker
+        let plume: VolumeGrid = VolumeGrid::from_vol("plume.vol");
+        let plume_density_index: usize = service_locator.get_texture_service_mut().add_texture(TextureEnum::VolumeGrid(plume.clone()));
+        let plume_albedo_index: usize = service_locator.get_texture_service_mut().add_texture(TextureEnum::Remap(Remap::new(plume_density_index, 0.0, 1.0, 0.9, 0.3)));
+        let plume_phase_function_index: usize = service_locator.get_material_service_mut().add_material(MaterialEnum::Isotropic(Isotropic::new(plume_albedo_index)));
+
+        // Only the shape of a medium boundary is used, it is never shaded
+        let invisible_material_index: usize = service_locator.get_material_service_mut().add_material(MaterialEnum::DefaultMaterial(DefaultMaterial{}));
+        let plume_boundary: BoxHittable = BoxHittable::new(&mut rng, service_locator.get_hittable_service_mut(), Vec3::new(300.0, 0.0, 200.0), Vec3::new(500.0, 400.0, 400.0), invisible_material_index);
+        let plume_boundary_index: usize = service_locator.get_hittable_service_mut().add_hittable(HittableEnum::BoxHittable(plume_boundary));
+        hittable_index_list.push(service_locator.get_hittable_service_mut().add_hittable(HittableEnum::HeterogeneousMedium(HeterogeneousMedium::new(plume_boundary_index, plume_phase_function_index, plume, 0.15))));
+
+        // A cloud baked from simplex fbm, cut off below a threshold to get distinct puffs
+        let cloud_noise_index: usize = service_locator.get_texture_service_mut().add_texture(TextureEnum::Fbm(Fbm::new(NoiseBasis::Simplex(Simplex::new(rng.gen())), 0.015, 5, 2.0, 0.5)));
+        let cloud_density_index: usize = service_locator.get_texture_service_mut().add_texture(TextureEnum::Remap(Remap::new(cloud_noise_index, 0.55, 0.7, 0.0, 1.0)));
+        let cloud_center: Vec3 = Vec3::new(160.0, 300.0, 250.0);
+        let cloud_radius: f32 = 110.0;
+        let cloud: VolumeGrid = VolumeGrid::bake(service_locator.get_texture_service(), cloud_density_index, [48, 48, 48], cloud_center - Vec3::broadcast(cloud_radius), cloud_center + Vec3::broadcast(cloud_radius));
+
+        let cloud_albedo_index: usize = service_locator.get_texture_service_mut().add_texture(TextureEnum::SolidColor(SolidColor::from_color(&ColorRGB::new(0.95, 0.95, 0.95))));
+        let cloud_phase_function_index: usize = service_locator.get_material_service_mut().add_material(MaterialEnum::Isotropic(Isotropic::new(cloud_albedo_index)));
+        let cloud_boundary_index: usize = service_locator.get_hittable_service_mut().add_hittable(HittableEnum::Sphere(Sphere::new(cloud_center, cloud_radius, invisible_material_index)));
+        hittable_index_list.push(service_locator.get_hittable_service_mut().add_hittable(HittableEnum::HeterogeneousMedium(HeterogeneousMedium::new(cloud_boundary_index, cloud_phase_function_index, cloud, 0.2))));
+
+        build_acceleration_structures(&mut rng, &mut service_locator, hittable_index_list, light_index_list);
+
+        service_locator
+    }
+
+    fn cornell_box_phase_functions_scene(config: &RenderConfig) -> ServiceLocator {
+        let (mut rng, mut service_locator, mut hittable_index_list, light_index_list) = Self::empty_cornell_box_scene_prebuild(config);
+
+        let albedo_texture_index: usize = service_locator.get_texture_service_mut().add_texture(TextureEnum::SolidColor(SolidColor::from_color(&ColorRGB::new(0.9, 0.9, 0.9))));
+
+        // From left to right: back scattering, a forward and a backward lobe, and Rayleigh
+        let phase_function_indices: [usize; 3] = [
+            service_locator.get_material_service_mut().add_material(MaterialEnum::HenyeyGreenstein(HenyeyGreenstein::new(albedo_texture_index, -0.6))),
+            service_locator.get_material_service_mut().add_material(MaterialEnum::HenyeyGreenstein(HenyeyGreenstein::double_lobe(albedo_texture_index, 0.8, -0.3, 0.7))),
+            service_locator.get_material_service_mut().add_material(MaterialEnum::Rayleigh(Rayleigh::new(albedo_texture_index))),
+        ];
+
+        let invisible_material_index: usize = service_locator.get_material_service_mut().add_material(MaterialEnum::DefaultMaterial(DefaultMaterial{}));
+        for (sphere_index, phase_function_index) in phase_function_indices.iter().enumerate() {
+            let center: Vec3 = Vec3::new(420.0 - 145.0 * sphere_index as f32, 200.0, 278.0);
+            let boundary_index: usize = service_locator.get_hittable_service_mut().add_hittable(HittableEnum::Sphere(Sphere::new(center, 65.0, invisible_material_index)));
+            hittable_index_list.push(service_locator.get_hittable_service_mut().add_hittable(HittableEnum::ConstantMedium(ConstantMedium::new(boundary_index, *phase_function_index, 0.03))));
+        }
+
+        build_acceleration_structures(&mut rng, &mut service_locator, hittable_index_list, light_index_list);
+
+        service_locator
+    }
+
+    fn cornell_box_emissive_volumes_scene(config: &RenderConfig) -> ServiceLocator {
+        let (mut rng, mut service_locator, mut hittable_index_list, light_index_list) = Self::empty_cornell_box_scene_prebuild(config);
+        let invisible_material_index: usize = service_locator.get_material_service_mut().add_material(MaterialEnum::DefaultMaterial(DefaultMaterial{}));
+
+        // A fireball, sooty smoke whose temperature follows another noise
+        let fire_center: Vec3 = Vec3::new(400.0, 200.0, 300.0);
+        let fire_radius: f32 = 110.0;
+        let fire_minimum: Vec3 = fire_center - Vec3::broadcast(fire_radius);
+        let fire_maximum: Vec3 = fire_center + Vec3::broadcast(fire_radius);
+
+        let smoke_noise_index: usize = service_locator.get_texture_service_mut().add_texture(TextureEnum::Fbm(Fbm::new(NoiseBasis::Simplex(Simplex::new(rng.gen())), 0.015, 5, 2.0, 0.5)));
+        let smoke_density_index: usize = service_locator.get_texture_service_mut().add_texture(TextureEnum::Remap(Remap::new(smoke_noise_index, 0.4, 0.7, 0.0, 1.0)));
+        let smoke: VolumeGrid = VolumeGrid::bake(service_locator.get_texture_service(), smoke_density_index, [48, 48, 48], fire_minimum, fire_maximum);
+
+        let heat_noise_index: usize = service_locator.get_texture_service_mut().add_texture(TextureEnum::Fbm(Fbm::new(NoiseBasis::Simplex(Simplex::new(rng.gen())), 0.01, 4, 2.0, 0.5)));
+        let heat_index: usize = service_locator.get_texture_service_mut().add_texture(TextureEnum::Remap(Remap::new(heat_noise_index, 0.3, 0.75, 800.0, 2400.0)));
+        let temperature: VolumeGrid = VolumeGrid::bake(service_locator.get_texture_service(), heat_index, [32, 32, 32], fire_minimum, fire_maximum);
+
+        let soot_texture_index: usize = service_locator.get_texture_service_mut().add_texture(TextureEnum::SolidColor(SolidColor::from_color(&ColorRGB::new(0.3, 0.3, 0.3))));
+        let soot_phase_function_index: usize = service_locator.get_material_service_mut().add_material(MaterialEnum::Isotropic(Isotropic::new(soot_texture_index)));
+        let fire_material_index: usize = service_locator.get_material_service_mut().add_material(MaterialEnum::EmissiveVolume(EmissiveVolume::new(soot_phase_function_index, VolumeEmission::Blackbody(BlackbodyEmission::new(temperature, 8.0)))));
+
+        let fire_boundary_index: usize = service_locator.get_hittable_service_mut().add_hittable(HittableEnum::Sphere(Sphere::new(fire_center, fire_radius, invisible_material_index)));
+        hittable_index_list.push(service_locator.get_hittable_service_mut().add_hittable(HittableEnum::HeterogeneousMedium(HeterogeneousMedium::new(fire_boundary_index, fire_material_index, smoke, 0.1))));
+
+        // Evenly glowing green gas in a box
+        let gas_texture_index: usize = service_locator.get_texture_service_mut().add_texture(TextureEnum::SolidColor(SolidColor::from_color(&ColorRGB::new(0.8, 0.8, 0.8))));
+        let gas_phase_function_index: usize = service_locator.get_material_service_mut().add_material(MaterialEnum::HenyeyGreenstein(HenyeyGreenstein::new(gas_texture_index, 0.5)));
+        let gas_material_index: usize = service_locator.get_material_service_mut().add_material(MaterialEnum::EmissiveVolume(EmissiveVolume::new(gas_phase_function_index, VolumeEmission::Constant(ColorRGB::new(0.1, 0.8, 0.3)))));
+
+        let gas_box: BoxHittable = BoxHittable::new(&mut rng, service_locator.get_hittable_service_mut(), Vec3::new(60.0, 0.0, 300.0), Vec3::new(200.0, 140.0, 440.0), invisible_material_index);
+        let gas_box_index: usize = service_locator.get_hittable_service_mut().add_hittable(HittableEnum::BoxHittable(gas_box));
+        hittable_index_list.push(service_locator.get_hittable_service_mut().add_hittable(HittableEnum::ConstantMedium(ConstantMedium::new(gas_box_index, gas_material_index, 0.02))));
+
+        // Blue plasma glowing along the edges of Worley cells
+        let cells_index: usize = service_locator.get_texture_service_mut().add_texture(TextureEnum::Worley(Worley::new(rng.gen(), 0.03, WorleyPattern::F2MinusF1)));
+        let edges_index: usize = service_locator.get_texture_service_mut().add_texture(TextureEnum::Remap(Remap::new(cells_index, 0.0, 0.15, 1.0, 0.0)));
+        let plasma_color_index: usize = service_locator.get_texture_service_mut().add_texture(TextureEnum::SolidColor(SolidColor::from_color(&ColorRGB::new(0.4, 0.6, 3.0))));
+        let plasma_emission_index: usize = service_locator.get_texture_service_mut().add_texture(TextureEnum::Arithmetic(Arithmetic::new(edges_index, plasma_color_index, Operation::Multiply)));
+        let plasma_material_index: usize = service_locator.get_material_service_mut().add_material(MaterialEnum::EmissiveVolume(EmissiveVolume::new(gas_phase_function_index, VolumeEmission::Texture(plasma_emission_index))));
+
+        let plasma_boundary_index: usize = service_locator.get_hittable_service_mut().add_hittable(HittableEnum::Sphere(Sphere::new(Vec3::new(150.0, 380.0, 350.0), 80.0, invisible_material_index)));
+        hittable_index_list.push(service_locator.get_hittable_service_mut().add_hittable(HittableEnum::ConstantMedium(ConstantMedium::new(plasma_boundary_index, plasma_material_index, 0.03))));
+
+        build_acceleration_structures(&mut rng, &mut service_locator, hittable_index_list, light_index_list);
+
+        service_locator
+    }
+
+    fn foggy_valley_scene(config: &RenderConfig) -> ServiceLocator {
+        // Camera
+        let look_from: Vec3 = Vec3::new(0.0, 1.5, -6.0);
+        let look_at: Vec3 = Vec3::new(0.0, 1.0, 10.0);
+        let v_up: Vec3 = Vec3::new(0.0, 1.0, 0.0);
+        let dist_to_focus: f32 = 10.0;
+        let aperture: f32 = 0.0;
+        let time_0: f32 = 0.0;
+        let time_1: f32 = 1.0;
+        let camera: Camera = Camera::new(look_from, look_at, v_up, 40.0, config.aspect_ratio, aperture, dist_to_focus, time_0, time_1);
+
+        let background: ColorRGB = ColorRGB::new(0.5, 0.6, 0.75);
+
+        let (mut rng, mut service_locator, mut hittable_index_list, mut light_index_list) = init_build_resources(config, camera, background);
+
+        // Ground fog, thick in the valley and thinning out with height, scattering mostly forward
+        let fog_texture_index: usize = service_locator.get_texture_service_mut().add_texture(TextureEnum::SolidColor(SolidColor::from_color(&ColorRGB::new(0.9, 0.9, 0.9))));
+        let fog_phase_function_index: usize = service_locator.get_material_service_mut().add_material(MaterialEnum::HenyeyGreenstein(HenyeyGreenstein::new(fog_texture_index, 0.6)));
+        service_locator.get_scene_service_mut().set_atmosphere(Atmosphere::new(0.15, fog_phase_function_index).with_height_falloff(0.0, 1.2));
+
+        let ground_texture_index: usize = service_locator.get_texture_service_mut().add_texture(TextureEnum::SolidColor(SolidColor::from_color(&ColorRGB::new(0.3, 0.4, 0.2))));
+        let ground_material_index: usize = service_locator.get_material_service_mut().add_material(MaterialEnum::Lambertian(Lambertian::new(ground_texture_index)));
+        hittable_index_list.push(service_locator.get_hittable_service_mut().add_hittable(HittableEnum::XZRect(XZRect::new(-100.0, 100.0, -100.0, 100.0, 0.0, ground_material_index))));
+
+        // Two rows of trunks receding into the fog
+        let trunk_texture_index: usize = service_locator.get_texture_service_mut().add_texture(TextureEnum::SolidColor(SolidColor::from_color(&ColorRGB::new(0.35, 0.25, 0.15))));
+        let trunk_material_index: usize = service_locator.get_material_service_mut().add_material(MaterialEnum::Lambertian(Lambertian::new(trunk_texture_index)));
+        for row in 0..8 {
+            for side in [-1.0, 1.0] {
+                let trunk: BoxHittable = BoxHittable::new(&mut rng, service_locator.get_hittable_service_mut(), Vec3::new(-0.3, 0.0, -0.3), Vec3::new(0.3, 5.0, 0.3), trunk_material_index);
+                let trunk_index: usize = service_locator.get_hittable_service_mut().add_hittable(HittableEnum::BoxHittable(trunk));
+                let position: Vec3 = Vec3::new(side * 2.5, 0.0, 4.0 * row as f32);
+                hittable_index_list.push(service_locator.get_hittable_service_mut().add_hittable(HittableEnum::Translate(Translate::new(position, trunk_index))));
+            }
+        }
+
+        let sun_texture_index: usize = service_locator.get_texture_service_mut().add_texture(TextureEnum::SolidColor(SolidColor::from_color(&ColorRGB::new(40.0, 36.0, 30.0))));
+        let sun_material_index: usize = service_locator.get_material_service_mut().add_material(MaterialEnum::DiffuseLight(DiffuseLight::new(sun_texture_index)));
+        let sun_index: usize = service_locator.get_hittable_service_mut().add_hittable(HittableEnum::Sphere(Sphere::new(Vec3::new(0.0, 12.0, 40.0), 4.0, sun_material_index)));
+        hittable_index_list.push(sun_index);
+        light_index_list.push(sun_index);
+
+        build_acceleration_structures(&mut rng, &mut service_locator, hittable_index_list, light_index_list);
+
+        service_locator
+    }
+
+    fn delta_lights_scene(config: &RenderConfig) -> ServiceLocator {
+        // Camera
+        let look_from: Vec3 = Vec3::new(0.0, 4.0, -12.0);
+        let look_at: Vec3 = Vec3::new(0.0, 1.0, 0.0);
+        let v_up: Vec3 = Vec3::new(0.0, 1.0, 0.0);
+        let dist_to_focus: f32 = 12.0;
+        let aperture: f32 = 0.0;
+        let time_0: f32 = 0.0;
+        let time_1: f32 = 1.0;
+        let camera: Camera = Camera::new(look_from, look_at, v_up, 35.0, config.aspect_ratio, aperture, dist_to_focus, time_0, time_1);
+
+        let background: ColorRGB = ColorRGB::new(0.02, 0.03, 0.05);
+
+        let (mut rng, mut service_locator, mut hittable_index_list, light_index_list) = init_build_resources(config, camera, background);
+
+        let checker_odd_texture_index: usize = service_locator.get_texture_service_mut().add_texture(TextureEnum::SolidColor(SolidColor::from_color(&ColorRGB::new(0.2, 0.2, 0.2))));
+        let checker_even_texture_index: usize = service_locator.get_texture_service_mut().add_texture(TextureEnum::SolidColor(SolidColor::from_color(&ColorRGB::new(0.7, 0.7, 0.7))));
+        let checker_texture_index: usize = service_locator.get_texture_service_mut().add_texture(TextureEnum::Checker(Checker::new(checker_odd_texture_index, checker_even_texture_index)));
+        let ground_material_index: usize = service_locator.get_material_service_mut().add_material(MaterialEnum::Lambertian(Lambertian::new(checker_texture_index)));
+        hittable_index_list.push(service_locator.get_hittable_service_mut().add_hittable(HittableEnum::XZRect(XZRect::new(-50.0, 50.0, -50.0, 50.0, 0.0, ground_material_index))));
+
+        let white_texture_index: usize = service_locator.get_texture_service_mut().add_texture(TextureEnum::SolidColor(SolidColor::from_color(&ColorRGB::new(0.8, 0.8, 0.8))));
+        let white_material_index: usize = service_locator.get_material_service_mut().add_material(MaterialEnum::Lambertian(Lambertian::new(white_texture_index)));
+        let gold_material_index: usize = service_locator.get_material_service_mut().add_material(MaterialEnum::Conductor(Conductor::preset("gold", 0.3).unwrap()));
+
+        hittable_index_list.push(service_locator.get_hittable_service_mut().add_hittable(HittableEnum::Sphere(Sphere::new(Vec3::new(-3.0, 1.0, 0.0), 1.0, white_material_index))));
+        hittable_index_list.push(service_locator.get_hittable_service_mut().add_hittable(HittableEnum::Sphere(Sphere::new(Vec3::new(0.0, 1.0, 1.0), 1.0, gold_material_index))));
+        let pillar: BoxHittable = BoxHittable::new(&mut rng, service_locator.get_hittable_service_mut(), Vec3::new(2.2, 0.0, -0.8), Vec3::new(3.8, 3.0, 0.8), white_material_index);
+        hittable_index_list.push(service_locator.get_hittable_service_mut().add_hittable(HittableEnum::BoxHittable(pillar)));
+
+        // A low evening sun with soft shadows, a warm spot on the pillar and a blue point light behind the spheres
+        let light_service = service_locator.get_light_service_mut();
+        light_service.add_light(LightEnum::Directional(DirectionalLight::new(Vec3::new(1.0, -0.5, 0.6), ColorRGB::new(1.2, 0.9, 0.6), 3.0)));
+        light_service.add_light(LightEnum::Spot(SpotLight::new(Vec3::new(0.0, 6.0, -4.0), Vec3::new(3.0, 1.5, 0.0), ColorRGB::new(60.0, 45.0, 20.0), 15.0, 10.0)));
+        light_service.add_light(LightEnum::Point(PointLight::new(Vec3::new(-1.5, 2.5, 3.0), ColorRGB::new(4.0, 8.0, 20.0))));
+
+        build_acceleration_structures(&mut rng, &mut service_locator, hittable_index_list, light_index_list);
+
+        service_locator
+    }
+
+    fn environment_map_scene(config: &RenderConfig) -> ServiceLocator {
+        // Camera
+        let look_from: Vec3 = Vec3::new(13.0, 2.0, 3.0);
+        let look_at: Vec3 = Vec3::new( 0.0, 1.0, 0.0);
+        let v_up: Vec3 = Vec3::new(0.0, 1.0, 0.0);
+        let dist_to_focus: f32 = 10.0;
+        let aperture: f32 = 0.0;
+        let time_0: f32 = 0.0;
+        let time_1: f32 = 1.0;
+        let camera: Camera = Camera::new(look_from, look_at, v_up, 25.0, config.aspect_ratio, aperture, dist_to_focus, time_0, time_1);
+
+        let background: ColorRGB = ColorRGB::black();
+
+        let (mut rng, mut service_locator, mut hittable_index_list, mut light_index_list) = init_build_resources(config, camera, background);
+
+        // Like the dome scene, but the sunset is infinitely far away and its sun is sampled directly
+        let environment_map: EnvironmentMap = EnvironmentMap::new("sunset.pfm").with_rotation(90.0).with_intensity(1.5);
+        light_index_list.push(service_locator.get_hittable_service_mut().add_hittable(HittableEnum::EnvironmentLight(EnvironmentLight::new(&environment_map))));
+        service_locator.get_scene_service_mut().set_background(BackgroundEnum::EnvironmentMap(environment_map));
+
+        let ground_texture_index: usize = service_locator.get_texture_service_mut().add_texture(TextureEnum::SolidColor(SolidColor::from_color(&ColorRGB::new(0.4, 0.4, 0.4))));
+        let ground_material_index: usize = service_locator.get_material_service_mut().add_material(MaterialEnum::Lambertian(Lambertian::new(ground_texture_index)));
+        hittable_index_list.push(service_locator.get_hittable_service_mut().add_hittable(HittableEnum::Sphere(Sphere::new(Vec3::new( 0.0, -1000.0, 0.0), 1000.0, ground_material_index))));
+
+        let diffuse_texture_index: usize = service_locator.get_texture_service_mut().add_texture(TextureEnum::SolidColor(SolidColor::from_color(&ColorRGB::new(0.8, 0.8, 0.8))));
+        let diffuse_material_index: usize = service_locator.get_material_service_mut().add_material(MaterialEnum::Lambertian(Lambertian::new(diffuse_texture_index)));
+        hittable_index_list.push(service_locator.get_hittable_service_mut().add_hittable(HittableEnum::Sphere(Sphere::new(Vec3::new( 0.0, 1.0, 1.2), 1.0, diffuse_material_index))));
+
+        let metal_material_index: usize = service_locator.get_material_service_mut().add_material(MaterialEnum::Metal(Metal::new(ColorRGB::new(0.9, 0.9, 0.9), 0.0)));
+        hittable_index_list.push(service_locator.get_hittable_service_mut().add_hittable(HittableEnum::Sphere(Sphere::new(Vec3::new( 0.0, 1.0, -1.2), 1.0, metal_material_index))));
+
+        build_acceleration_structures(&mut rng, &mut service_locator, hittable_index_list, light_index_list);
+
+        service_locator
+    }
+
+    fn physical_sky_scene(config: &RenderConfig) -> ServiceLocator {
+        // Camera
+        let look_from: Vec3 = Vec3::new(-30.0, 6.0, -40.0);
+        let look_at: Vec3 = Vec3::new(0.0, 8.0, 0.0);
+        let v_up: Vec3 = Vec3::new(0.0, 1.0, 0.0);
+        let dist_to_focus: f32 = 50.0;
+        let aperture: f32 = 0.0;
+        let time_0: f32 = 0.0;
+        let time_1: f32 = 1.0;
+        let camera: Camera = Camera::new(look_from, look_at, v_up, 50.0, config.aspect_ratio, aperture, dist_to_focus, time_0, time_1);
+
+        let background: ColorRGB = ColorRGB::black();
+
+        let (mut rng, mut service_locator, mut hittable_index_list, light_index_list) = init_build_resources(config, camera, background);
+
+        // A midsummer afternoon in Copenhagen
+        let sky: PreethamSky = PreethamSky::new(sun_direction_at(172, 17.0, 55.7), 3.0, 0.2);
+        service_locator.get_light_service_mut().add_light(LightEnum::Directional(sky.sun_light(3.0)));
+        service_locator.get_scene_service_mut().set_background(BackgroundEnum::Sky(sky));
+
+        let ground_texture_index: usize = service_locator.get_texture_service_mut().add_texture(TextureEnum::SolidColor(SolidColor::from_color(&ColorRGB::new(0.35, 0.35, 0.33))));
+        let ground_material_index: usize = service_locator.get_material_service_mut().add_material(MaterialEnum::Lambertian(Lambertian::new(ground_texture_index)));
+        hittable_index_list.push(service_locator.get_hittable_service_mut().add_hittable(HittableEnum::XZRect(XZRect::new(-500.0, 500.0, -500.0, 500.0, 0.0, ground_material_index))));
+
+        // A block of buildings of varying heights
+        let facade_texture_index: usize = service_locator.get_texture_service_mut().add_texture(TextureEnum::SolidColor(SolidColor::from_color(&ColorRGB::new(0.75, 0.72, 0.68))));
+        let facade_material_index: usize = service_locator.get_material_service_mut().add_material(MaterialEnum::Lambertian(Lambertian::new(facade_texture_index)));
+        let buildings: [(f32, f32, f32, f32); 5] = [(-12.0, 0.0, 8.0, 14.0), (0.0, -4.0, 10.0, 24.0), (12.0, 2.0, 8.0, 10.0), (-4.0, 14.0, 12.0, 18.0), (14.0, 16.0, 10.0, 30.0)];
+        for (x, z, width, height) in buildings {
+            let building: BoxHittable = BoxHittable::new(&mut rng, service_locator.get_hittable_service_mut(), Vec3::new(x, 0.0, z), Vec3::new(x + width, height, z + width), facade_material_index);
+            hittable_index_list.push(service_locator.get_hittable_service_mut().add_hittable(HittableEnum::BoxHittable(building)));
+        }
+
+        build_acceleration_structures(&mut rng, &mut service_locator, hittable_index_list, light_index_list);
+
+        service_locator
+    }
+
+    fn city_at_night_scene(config: &RenderConfig) -> ServiceLocator {
+        // Camera
+        let look_from: Vec3 = Vec3::new(-60.0, 35.0, -75.0);
+        let look_at: Vec3 = Vec3::new(0.0, 2.0, 0.0);
+        let v_up: Vec3 = Vec3::new(0.0, 1.0, 0.0);
+        let dist_to_focus: f32 = 100.0;
+        let aperture: f32 = 0.0;
+        let time_0: f32 = 0.0;
+        let time_1: f32 = 1.0;
+        let camera: Camera = Camera::new(look_from, look_at, v_up, 40.0, config.aspect_ratio, aperture, dist_to_focus, time_0, time_1);
+
+        let background: ColorRGB = ColorRGB::new(0.002, 0.003, 0.008);
+
+        let (mut rng, mut service_locator, mut hittable_index_list, mut light_index_list) = init_build_resources(config, camera, background);
+
+        let ground_texture_index: usize = service_locator.get_texture_service_mut().add_texture(TextureEnum::SolidColor(SolidColor::from_color(&ColorRGB::new(0.3, 0.3, 0.3))));
+        let ground_material_index: usize = service_locator.get_material_service_mut().add_material(MaterialEnum::Lambertian(Lambertian::new(ground_texture_index)));
+        hittable_index_list.push(service_locator.get_hittable_service_mut().add_hittable(HittableEnum::XZRect(XZRect::new(-200.0, 200.0, -200.0, 200.0, 0.0, ground_material_index))));
+
+        let building_texture_index: usize = service_locator.get_texture_service_mut().add_texture(TextureEnum::SolidColor(SolidColor::from_color(&ColorRGB::new(0.6, 0.6, 0.65))));
+        let building_material_index: usize = service_locator.get_material_service_mut().add_material(MaterialEnum::Lambertian(Lambertian::new(building_texture_index)));
+
+        let street_lamp_texture_index: usize = service_locator.get_texture_service_mut().add_texture(TextureEnum::SolidColor(SolidColor::from_color(&ColorRGB::new(30.0, 20.0, 8.0))));
+        let street_lamp_material_index: usize = service_locator.get_material_service_mut().add_material(MaterialEnum::DiffuseLight(DiffuseLight::new(street_lamp_texture_index)));
+        let beacon_texture_index: usize = service_locator.get_texture_service_mut().add_texture(TextureEnum::SolidColor(SolidColor::from_color(&ColorRGB::new(400.0, 60.0, 40.0))));
+        let beacon_material_index: usize = service_locator.get_material_service_mut().add_material(MaterialEnum::DiffuseLight(DiffuseLight::new(beacon_texture_index)));
+
+        // A grid of blocks, with a dim lamp at every crossing and a few bright beacons on the tallest buildings.
+        // With uniform selection almost every light sample would go to a lamp, selecting by power
+        // sends most of them to the beacons, which light most of the city.
+        let block_count: i32 = 5;
+        let block_size: f32 = 10.0;
+        for a in -block_count..=block_count {
+            for b in -block_count..=block_count {
+                let center: Vec3 = Vec3::new(a as f32 * block_size, 0.0, b as f32 * block_size);
+                let height: f32 = 4.0 + 16.0 * rng.gen::<f32>() * rng.gen::<f32>();
+                let building: BoxHittable = BoxHittable::new(&mut rng, service_locator.get_hittable_service_mut(), center + Vec3::new(-3.0, 0.0, -3.0), center + Vec3::new(3.0, height, 3.0), building_material_index);
+                hittable_index_list.push(service_locator.get_hittable_service_mut().add_hittable(HittableEnum::BoxHittable(building)));
+
+                if 14.0 < height {
+                    let beacon_index: usize = service_locator.get_hittable_service_mut().add_hittable(HittableEnum::Sphere(Sphere::new(center + Vec3::new(0.0, height + 0.6, 0.0), 0.6, beacon_material_index)));
+                    hittable_index_list.push(beacon_index);
+                    light_index_list.push(beacon_index);
+                }
+
+                let lamp_index: usize = service_locator.get_hittable_service_mut().add_hittable(HittableEnum::Sphere(Sphere::new(center + Vec3::new(0.5 * block_size, 3.0, 0.5 * block_size), 0.15, street_lamp_material_index)));
+                hittable_index_list.push(lamp_index);
+                light_index_list.push(lamp_index);
+            }
+        }
+
+        build_acceleration_structures(&mut rng, &mut service_locator, hittable_index_list, light_index_list);
+
+        service_locator
+    }
+
+    fn photometric_room_scene(config: &RenderConfig) -> ServiceLocator {
+        // Camera
+        let look_from: Vec3 = Vec3::new(2.0, 1.4, 0.2);
+        let look_at: Vec3 = Vec3::new(2.0, 1.0, 3.0);
+        let v_up: Vec3 = Vec3::new(0.0, 1.0, 0.0);
+        let dist_to_focus: f32 = 3.0;
+        let aperture: f32 = 0.0;
+        let time_0: f32 = 0.0;
+        let time_1: f32 = 1.0;
+        let camera: Camera = Camera::new(look_from, look_at, v_up, 60.0, config.aspect_ratio, aperture, dist_to_focus, time_0, time_1);
+
+        let background: ColorRGB = ColorRGB::black();
+
+        let (mut rng, mut service_locator, mut hittable_index_list, mut light_index_list) = init_build_resources(config, camera, background);
+
+        // A 4 by 4 meter room, lit by what could be found in a shop, and exposed so 50 nits is white
+        let units: PhotometricUnits = PhotometricUnits::new(1.0, 50.0);
+
+        let white_texture_index: usize = service_locator.get_texture_service_mut().add_texture(TextureEnum::SolidColor(SolidColor::from_color(&ColorRGB::new(0.73, 0.73, 0.73))));
+        let white_material_index: usize = service_locator.get_material_service_mut().add_material(MaterialEnum::Lambertian(Lambertian::new(white_texture_index)));
+        let wood_texture_index: usize = service_locator.get_texture_service_mut().add_texture(TextureEnum::SolidColor(SolidColor::from_color(&ColorRGB::new(0.4, 0.25, 0.12))));
+        let wood_material_index: usize = service_locator.get_material_service_mut().add_material(MaterialEnum::Lambertian(Lambertian::new(wood_texture_index)));
+
+        hittable_index_list.push(service_locator.get_hittable_service_mut().add_hittable(HittableEnum::XZRect(XZRect::new(0.0, 4.0, 0.0, 4.0, 0.0, wood_material_index))));
+        let ceiling_index: usize = service_locator.get_hittable_service_mut().add_hittable(HittableEnum::XZRect(XZRect::new(0.0, 4.0, 0.0, 4.0, 2.7, white_material_index)));
+        hittable_index_list.push(service_locator.get_hittable_service_mut().add_hittable(HittableEnum::FlipFace(FlipFace::new(ceiling_index))));
+        hittable_index_list.push(service_locator.get_hittable_service_mut().add_hittable(HittableEnum::YZRect(YZRect::new(0.0, 2.7, 0.0, 4.0, 0.0, white_material_index))));
+        let right_wall_index: usize = service_locator.get_hittable_service_mut().add_hittable(HittableEnum::YZRect(YZRect::new(0.0, 2.7, 0.0, 4.0, 4.0, white_material_index)));
+        hittable_index_list.push(service_locator.get_hittable_service_mut().add_hittable(HittableEnum::FlipFace(FlipFace::new(right_wall_index))));
+        hittable_index_list.push(service_locator.get_hittable_service_mut().add_hittable(HittableEnum::XYRect(XYRect::new(0.0, 4.0, 0.0, 2.7, 0.0, white_material_index))));
+        let back_wall_index: usize = service_locator.get_hittable_service_mut().add_hittable(HittableEnum::XYRect(XYRect::new(0.0, 4.0, 0.0, 2.7, 4.0, white_material_index)));
+        hittable_index_list.push(service_locator.get_hittable_service_mut().add_hittable(HittableEnum::FlipFace(FlipFace::new(back_wall_index))));
+
+        let table: BoxHittable = BoxHittable::new(&mut rng, service_locator.get_hittable_service_mut(), Vec3::new(1.2, 0.0, 2.4), Vec3::new(2.8, 0.75, 3.4), wood_material_index);
+        hittable_index_list.push(service_locator.get_hittable_service_mut().add_hittable(HittableEnum::BoxHittable(table)));
+        hittable_index_list.push(service_locator.get_hittable_service_mut().add_hittable(HittableEnum::Sphere(Sphere::new(Vec3::new(0.9, 0.3, 1.8), 0.3, white_material_index))));
+
+        // A 60 by 60 centimeter 15 watt LED panel in the ceiling
+        let panel_radiance: ColorRGB = units.area_radiance(&ColorRGB::blackbody(4000.0), LuminousFlux::Watts { watts: 15.0, luminous_efficacy: LED_EFFICACY }, 0.6 * 0.6);
+        let panel_texture_index: usize = service_locator.get_texture_service_mut().add_texture(TextureEnum::SolidColor(SolidColor::from_color(&panel_radiance)));
+        let panel_material_index: usize = service_locator.get_material_service_mut().add_material(MaterialEnum::DiffuseLight(DiffuseLight::new(panel_texture_index)));
+        let unflipped_panel_index: usize = service_locator.get_hittable_service_mut().add_hittable(HittableEnum::XZRect(XZRect::new(1.7, 2.3, 1.7, 2.3, 2.69, panel_material_index)));
+        hittable_index_list.push(service_locator.get_hittable_service_mut().add_hittable(HittableEnum::FlipFace(FlipFace::new(unflipped_panel_index))));
+        light_index_list.push(unflipped_panel_index);
+
+        // A ball of iron on the table, glowing red hot at 1250 Kelvin
+        let iron_texture_index: usize = service_locator.get_texture_service_mut().add_texture(TextureEnum::SolidColor(SolidColor::from_color(&units.blackbody_radiance(1250.0))));
+        let iron_material_index: usize = service_locator.get_material_service_mut().add_material(MaterialEnum::DiffuseLight(DiffuseLight::new(iron_texture_index)));
+        let iron_index: usize = service_locator.get_hittable_service_mut().add_hittable(HittableEnum::Sphere(Sphere::new(Vec3::new(2.3, 0.87, 2.9), 0.12, iron_material_index)));
+        hittable_index_list.push(iron_index);
+        light_index_list.push(iron_index);
+
+        // A 35 watt halogen spot grazing the back wall and a candle on the table
+        let light_service = service_locator.get_light_service_mut();
+        light_service.add_light(LightEnum::Spot(SpotLight::new(Vec3::new(3.3, 2.6, 3.5), Vec3::new(3.3, 0.8, 4.0), units.spot_intensity(&ColorRGB::blackbody(2900.0), LuminousFlux::Watts { watts: 35.0, luminous_efficacy: HALOGEN_EFFICACY }, 35.0, 25.0), 35.0, 25.0)));
+        light_service.add_light(LightEnum::Point(PointLight::new(Vec3::new(1.6, 0.95, 2.9), units.point_intensity(&ColorRGB::blackbody(1850.0), LuminousFlux::Lumens(12.0)))));
+
+        build_acceleration_structures(&mut rng, &mut service_locator, hittable_index_list, light_index_list);
+
+        service_locator
+    }
+
+    fn ies_gallery_scene(config: &RenderConfig) -> ServiceLocator {
+        // Camera
+        let look_from: Vec3 = Vec3::new(3.0, 1.5, -1.5);
+        let look_at: Vec3 = Vec3::new(3.0, 1.4, 4.0);
+        let v_up: Vec3 = Vec3::new(0.0, 1.0, 0.0);
+        let dist_to_focus: f32 = 5.5;
+        let aperture: f32 = 0.0;
+        let time_0: f32 = 0.0;
+        let time_1: f32 = 1.0;
+        let camera: Camera = Camera::new(look_from, look_at, v_up, 55.0, config.aspect_ratio, aperture, dist_to_focus, time_0, time_1);
+
+        let background: ColorRGB = ColorRGB::black();
+
+        let (mut rng, mut service_locator, mut hittable_index_list, mut light_index_list) = init_build_resources(config, camera, background);
+
+        let units: PhotometricUnits = PhotometricUnits::new(1.0, 200.0);
+
+        let white_texture_index: usize = service_locator.get_texture_service_mut().add_texture(TextureEnum::SolidColor(SolidColor::from_color(&ColorRGB::new(0.73, 0.73, 0.73))));
+        let white_material_index: usize = service_locator.get_material_service_mut().add_material(MaterialEnum::Lambertian(Lambertian::new(white_texture_index)));
+        let floor_texture_index: usize = service_locator.get_texture_service_mut().add_texture(TextureEnum::SolidColor(SolidColor::from_color(&ColorRGB::new(0.25, 0.25, 0.25))));
+        let floor_material_index: usize = service_locator.get_material_service_mut().add_material(MaterialEnum::Lambertian(Lambertian::new(floor_texture_index)));
+
+        // A 6 by 4 meter gallery, with the camera looking at the far wall through the open front
+        hittable_index_list.push(service_locator.get_hittable_service_mut().add_hittable(HittableEnum::XZRect(XZRect::new(0.0, 6.0, -2.0, 4.0, 0.0, floor_material_index))));
+        let ceiling_index: usize = service_locator.get_hittable_service_mut().add_hittable(HittableEnum::XZRect(XZRect::new(0.0, 6.0, -2.0, 4.0, 3.0, white_material_index)));
+        hittable_index_list.push(service_locator.get_hittable_service_mut().add_hittable(HittableEnum::FlipFace(FlipFace::new(ceiling_index))));
+        hittable_index_list.push(service_locator.get_hittable_service_mut().add_hittable(HittableEnum::YZRect(YZRect::new(0.0, 3.0, -2.0, 4.0, 0.0, white_material_index))));
+        let right_wall_index: usize = service_locator.get_hittable_service_mut().add_hittable(HittableEnum::YZRect(YZRect::new(0.0, 3.0, -2.0, 4.0, 6.0, white_material_index)));
+        hittable_index_list.push(service_locator.get_hittable_service_mut().add_hittable(HittableEnum::FlipFace(FlipFace::new(right_wall_index))));
+        let back_wall_index: usize = service_locator.get_hittable_service_mut().add_hittable(HittableEnum::XYRect(XYRect::new(0.0, 6.0, 0.0, 3.0, 4.0, white_material_index)));
+        hittable_index_list.push(service_locator.get_hittable_service_mut().add_hittable(HittableEnum::FlipFace(FlipFace::new(back_wall_index))));
+
+        let pedestal: BoxHittable = BoxHittable::new(&mut rng, service_locator.get_hittable_service_mut(), Vec3::new(2.6, 0.0, 2.1), Vec3::new(3.4, 0.9, 2.9), white_material_index);
+        hittable_index_list.push(service_locator.get_hittable_service_mut().add_hittable(HittableEnum::BoxHittable(pedestal)));
+        hittable_index_list.push(service_locator.get_hittable_service_mut().add_hittable(HittableEnum::Sphere(Sphere::new(Vec3::new(3.0, 1.2, 2.5), 0.3, white_material_index))));
+
+        // Two downlights close to the wall, throwing the usual scallops, and a spot with the same profile aimed at the pedestal
+        match IesProfile::from_file("downlight.ies") {
+            Ok(downlight) => {
+                let warm_white: ColorRGB = ColorRGB::blackbody(3000.0);
+                let downlight_intensity: ColorRGB = units.intensity(&warm_white, downlight.max_candela());
+                let light_service = service_locator.get_light_service_mut();
+                for x in [1.0, 5.0] {
+                    let position: Vec3 = Vec3::new(x, 2.95, 3.6);
+                    light_service.add_light(LightEnum::Point(PointLight::new(position, downlight_intensity).with_profile(downlight.clone(), position - Vec3::new(0.0, 1.0, 0.0), Vec3::new(1.0, 0.0, 0.0))));
+                }
+                light_service.add_light(LightEnum::Spot(SpotLight::new(Vec3::new(3.0, 2.95, 1.0), Vec3::new(3.0, 1.2, 2.5), downlight_intensity, 40.0, 30.0).with_profile(downlight, Vec3::new(1.0, 0.0, 0.0))));
+            },
+            Err(error) => println!("Skipping the downlights, {}", error),
+        }
+
+        // A wall washer in the ceiling lighting up the middle of the wall, its length along x puts its throw towards +z
+        match IesProfile::from_file("wallwash.ies") {
+            Ok(wall_washer) => {
+                let washer_area: f32 = 1.2 * 0.2;
+                let washer_material_index: usize = service_locator.get_material_service_mut().add_material(MaterialEnum::ProfiledLight(ProfiledLight::new(wall_washer, units.area_radiance_per_candela(&ColorRGB::blackbody(4000.0), washer_area))));
+                let unflipped_washer_index: usize = service_locator.get_hittable_service_mut().add_hittable(HittableEnum::XZRect(XZRect::new(2.4, 3.6, 3.0, 3.2, 2.99, washer_material_index)));
+                hittable_index_list.push(service_locator.get_hittable_service_mut().add_hittable(HittableEnum::FlipFace(FlipFace::new(unflipped_washer_index))));
+                light_index_list.push(unflipped_washer_index);
+            },
+            Err(error) => println!("Skipping the wall washer, {}", error),
+        }
+
+        build_acceleration_structures(&mut rng, &mut service_locator, hittable_index_list, light_index_list);
+
+        service_locator
+    }
+}
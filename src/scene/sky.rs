@@ -0,0 +1,154 @@
+use std::f32::consts::PI;
+
+use ultraviolet::Vec3;
+
+use crate::{core::color_rgb::ColorRGB, lights::directional_light::DirectionalLight};
+
+// Angular diameter of the sun in degrees
+const SUN_ANGULAR_DIAMETER: f32 = 0.53;
+
+// Direction towards the sun from its elevation above the horizon and its azimuth in degrees.
+// The azimuth is measured clockwise from north, +z, towards east, +x.
+pub fn sun_direction(elevation: f32, azimuth: f32) -> Vec3 {
+    let elevation = elevation.to_radians();
+    let azimuth = azimuth.to_radians();
+
+    Vec3::new(elevation.cos() * azimuth.sin(), elevation.sin(), elevation.cos() * azimuth.cos())
+}
+
+// Direction towards the sun on a day of the year, at an hour of local solar time, so noon is 12.0,
+// at a latitude in degrees. Ignores the equation of time, which is off by a quarter of an hour at most.
+pub fn sun_direction_at(day_of_year: u32, hour: f32, latitude: f32) -> Vec3 {
+    let declination = (23.44 * (2.0 * PI * (284.0 + day_of_year as f32) / 365.0).sin()).to_radians();
+    let hour_angle = (15.0 * (hour - 12.0)).to_radians();
+    let latitude = latitude.to_radians();
+
+    let sin_elevation = latitude.sin() * declination.sin() + latitude.cos() * declination.cos() * hour_angle.cos();
+    let elevation = sin_elevation.clamp(-1.0, 1.0).asin();
+
+    let cos_azimuth = ((declination.sin() - sin_elevation * latitude.sin()) / (elevation.cos() * latitude.cos()).max(1.0e-6)).clamp(-1.0, 1.0);
+    let azimuth = if 0.0 < hour_angle { 2.0 * PI - cos_azimuth.acos() } else { cos_azimuth.acos() };
+
+    sun_direction(elevation.to_degrees(), azimuth.to_degrees())
+}
+
+// Perez et al. luminance distribution, relative to the luminance at the zenith
+#[derive(Clone, Copy)]
+struct PerezCoefficients {
+    a: f32,
+    b: f32,
+    c: f32,
+    d: f32,
+    e: f32,
+}
+
+impl PerezCoefficients {
+    #[inline]
+    fn evaluate(&self, cos_theta: f32, gamma: f32, cos_gamma: f32) -> f32 {
+        (1.0 + self.a * (self.b / cos_theta).exp()) * (1.0 + self.c * (self.d * gamma).exp() + self.e * cos_gamma * cos_gamma)
+    }
+}
+
+#[inline]
+fn zenith_chromaticity(turbidity: f32, theta: f32, coefficients: [[f32; 4]; 3]) -> f32 {
+    let thetas = [theta * theta * theta, theta * theta, theta, 1.0];
+    let row = |index: usize| -> f32 { coefficients[index].iter().zip(thetas.iter()).map(|(c, t)| c * t).sum() };
+
+    turbidity * turbidity * row(0) + turbidity * row(1) + row(2)
+}
+
+// Preetham, Shirley and Smits analytic daylight model. Turbidity goes from about 2 for a clear sky to 10 for haze.
+// Radiance is scaled so the zenith has a luminance of intensity. The sun disk itself is not part of the sky,
+// it is lit by the directional light from sun_light, which keeps it from being counted twice.
+pub struct PreethamSky {
+    sun_direction: Vec3,
+    turbidity: f32,
+    intensity: f32,
+    zenith: [f32; 3], // Yxy, Y relative to the zenith
+    perez: [PerezCoefficients; 3],
+    normalization: [f32; 3], // The distributions at the zenith
+}
+
+impl PreethamSky {
+    pub fn new(sun_direction: Vec3, turbidity: f32, intensity: f32) -> PreethamSky {
+        let turbidity = turbidity.clamp(1.7, 10.0);
+        let sun_direction = sun_direction.normalized();
+        // The model breaks down with the sun below the horizon
+        let theta_sun = sun_direction.y.clamp(-1.0, 1.0).acos().min(0.5 * PI - 0.01);
+
+        let perez = [
+            PerezCoefficients { a: 0.1787 * turbidity - 1.4630, b: -0.3554 * turbidity + 0.4275, c: -0.0227 * turbidity + 5.3251, d: 0.1206 * turbidity - 2.5771, e: -0.0670 * turbidity + 0.3703 },
+            PerezCoefficients { a: -0.0193 * turbidity - 0.2592, b: -0.0665 * turbidity + 0.0008, c: -0.0004 * turbidity + 0.2125, d: -0.0641 * turbidity - 0.8989, e: -0.0033 * turbidity + 0.0452 },
+            PerezCoefficients { a: -0.0167 * turbidity - 0.2608, b: -0.0950 * turbidity + 0.0092, c: -0.0079 * turbidity + 0.2102, d: -0.0441 * turbidity - 1.6537, e: -0.0109 * turbidity + 0.0529 },
+        ];
+
+        let zenith_x = zenith_chromaticity(turbidity, theta_sun, [
+            [0.00166, -0.00375, 0.00209, 0.0],
+            [-0.02903, 0.06377, -0.03202, 0.00394],
+            [0.11693, -0.21196, 0.06052, 0.25886],
+        ]);
+        let zenith_y = zenith_chromaticity(turbidity, theta_sun, [
+            [0.00275, -0.00610, 0.00317, 0.0],
+            [-0.04214, 0.08970, -0.04153, 0.00516],
+            [0.15346, -0.26756, 0.06670, 0.26688],
+        ]);
+
+        let mut normalization = [0.0; 3];
+        for (value, coefficients) in normalization.iter_mut().zip(perez.iter()) {
+            *value = coefficients.evaluate(1.0, theta_sun, theta_sun.cos());
+        }
+
+        let azimuth = sun_direction.x.atan2(sun_direction.z);
+
+        PreethamSky { 
+            sun_direction: Vec3::new(theta_sun.sin() * azimuth.sin(), theta_sun.cos(), theta_sun.sin() * azimuth.cos()), 
+            turbidity, 
+            intensity, 
+            zenith: [1.0, zenith_x, zenith_y], 
+            perez, 
+            normalization 
+        }
+    }
+
+    // Sunlight dimmed and reddened by Rayleigh and aerosol scattering along its path through the atmosphere,
+    // giving irradiance when the sun is at the zenith of a perfectly clear sky
+    pub fn sun_light(&self, irradiance: f32) -> DirectionalLight {
+        let theta = self.sun_direction.y.clamp(-1.0, 1.0).acos();
+        let relative_air_mass = 1.0 / (theta.cos() + 0.15 * (93.885 - theta.to_degrees()).max(0.01).powf(-1.253));
+        let beta = 0.04608 * self.turbidity - 0.04586;
+
+        // Red, green and blue wavelengths in micrometers
+        let transmittance = |wavelength: f32| -> f32 {
+            let rayleigh = (-0.008735 * wavelength.powf(-4.08) * relative_air_mass).exp();
+            let aerosol = (-beta * wavelength.powf(-1.3) * relative_air_mass).exp();
+            rayleigh * aerosol
+        };
+
+        let color = ColorRGB::new(transmittance(0.68), transmittance(0.55), transmittance(0.44));
+        DirectionalLight::new(-self.sun_direction, color * irradiance, SUN_ANGULAR_DIAMETER)
+    }
+
+    pub fn value(&self, direction: &Vec3) -> ColorRGB {
+        let direction = direction.normalized();
+        // Below the horizon the sky continues as it looks at the horizon
+        let cos_theta = direction.y.max(0.01);
+        let cos_gamma = direction.dot(self.sun_direction).clamp(-1.0, 1.0);
+        let gamma = cos_gamma.acos();
+
+        let relative = |index: usize| -> f32 { self.zenith[index] * self.perez[index].evaluate(cos_theta, gamma, cos_gamma) / self.normalization[index] };
+        let (luminance, x, y) = (relative(0), relative(1), relative(2));
+        let luminance = luminance * self.intensity;
+        if y <= 0.0 {
+            return ColorRGB::black();
+        }
+
+        let big_x = x / y * luminance;
+        let big_z = (1.0 - x - y) / y * luminance;
+
+        ColorRGB::new(
+            ( 3.2406 * big_x - 1.5372 * luminance - 0.4986 * big_z).max(0.0),
+            (-0.9689 * big_x + 1.8758 * luminance + 0.0415 * big_z).max(0.0),
+            ( 0.0557 * big_x - 0.2040 * luminance + 1.0570 * big_z).max(0.0),
+        )
+    }
+}
\ No newline at end of file
@@ -6,6 +6,8 @@ use crate::hittables::default_hittable::DefaultHittable;
 use crate::hittables::hit_record::HitRecord;
 use crate::core::ray::Ray;
 use crate::hittables::hittable_enum::HittableEnum;
+use crate::services::material_service::MaterialService;
+use crate::services::texture_service::TextureService;
 
 // Introduce a build step
 // All elements are added with add hittable
@@ -72,4 +74,9 @@ impl HittableService {
     pub fn random(&self, index:usize, rng: &mut ThreadRng, origin: &Vec3) -> Vec3 {
         self.hittables[index].random(self, rng, origin)
     }
+
+    #[inline]
+    pub fn emitted_power(&self, index: usize, material_service: &MaterialService, texture_service: &TextureService, flipped: bool) -> f32 {
+        self.hittables[index].emitted_power(self, material_service, texture_service, flipped)
+    }
 }
@@ -0,0 +1,32 @@
+use ultraviolet::Vec3;
+use rand::rngs::ThreadRng;
+
+use crate::lights::{light_enum::LightEnum, light::{Light, LightSample}};
+
+// Delta lights, every one of them is sampled at every non-specular scattering event.
+// Emissive geometry is still sampled through the lights list of the HittableService.
+pub struct LightService {
+    lights: Vec<LightEnum>,
+}
+
+impl LightService {
+    pub fn new() -> LightService {
+        LightService{ lights: Vec::new() }
+    }
+
+    pub fn add_light(&mut self, new_light: LightEnum) -> usize {
+        self.lights.push(new_light);
+
+        self.lights.len() - 1
+    }
+
+    #[inline]
+    pub fn light_count(&self) -> usize {
+        self.lights.len()
+    }
+
+    #[inline]
+    pub fn sample(&self, light_index: usize, rng: &mut ThreadRng, point: &Vec3, sample_out: &mut LightSample) -> bool {
+        self.lights[light_index].sample(rng, point, sample_out)
+    }
+}
\ No newline at end of file
@@ -28,19 +28,46 @@ impl MaterialService {
         self.materials.len() - 1
     }
 
+    #[inline]
+    pub fn perturb_normal(&self, texture_service: &TextureService, ray: &Ray, hit: &mut HitRecord) {
+        self.perturb_normal_by_index(hit.material, texture_service, ray, hit);
+    }
+
+    #[inline]
+    pub fn perturb_normal_by_index(&self, index: usize, texture_service: &TextureService, ray: &Ray, hit: &mut HitRecord) {
+        self.materials[index].perturb_normal(self, texture_service, ray, hit);
+    }
+
     #[inline]
     pub fn emitted(&self, texture_service: &TextureService, ray: &Ray, hit: &HitRecord, emitted_out: &mut ColorRGB) {
-        self.materials[hit.material].emitted(texture_service, ray, hit, emitted_out);
+        self.emitted_by_index(hit.material, texture_service, ray, hit, emitted_out);
+    }
+
+    #[inline]
+    pub fn emitted_by_index(&self, index: usize, texture_service: &TextureService, ray: &Ray, hit: &HitRecord, emitted_out: &mut ColorRGB) {
+        self.materials[index].emitted(self, texture_service, ray, hit, emitted_out);
     }
 
     #[inline]
     pub fn scatter(&self, rng: &mut ThreadRng, texture_service: &TextureService, ray: &Ray, hit: &HitRecord, scatter_out: &mut ScatterRecord) -> bool {
-        self.materials[hit.material].scatter(rng, texture_service, ray, hit, scatter_out)
+        self.scatter_by_index(hit.material, rng, texture_service, ray, hit, scatter_out)
+    }
+
+    #[inline]
+    pub fn scatter_by_index(&self, index: usize, rng: &mut ThreadRng, texture_service: &TextureService, ray: &Ray, hit: &HitRecord, scatter_out: &mut ScatterRecord) -> bool {
+        scatter_out.material = index;
+        self.materials[index].scatter(rng, self, texture_service, ray, hit, scatter_out)
+    }
+
+    // Evaluated for the material recorded by scatter, which is not necessarily hit.material
+    #[inline]
+    pub fn scattering_pdf(&self, rng: &mut ThreadRng, texture_service: &TextureService, ray: &Ray, hit: &HitRecord, scatter_record: &ScatterRecord, scattered_ray:&Ray) -> f32 {
+        self.materials[scatter_record.material].scattering_pdf(rng, self, texture_service, ray, hit, scattered_ray)
     }
 
     #[inline]
-    pub fn scattering_pdf(&self, rng: &mut ThreadRng, ray: &Ray, hit: &HitRecord, scattered_ray:&Ray) -> f32 {
-        self.materials[hit.material].scattering_pdf(rng, ray, hit, scattered_ray)
+    pub fn scattering_tint(&self, rng: &mut ThreadRng, texture_service: &TextureService, ray: &Ray, hit: &HitRecord, scatter_record: &ScatterRecord, scattered_ray:&Ray) -> ColorRGB {
+        self.materials[scatter_record.material].scattering_tint(rng, self, texture_service, ray, hit, scattered_ray)
     }
 
 }
@@ -1,4 +1,5 @@
 pub mod hittable_service;
+pub mod light_service;
 pub mod material_service;
 pub mod scene_service;
 pub mod service_locator;
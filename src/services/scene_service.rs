@@ -1,22 +1,35 @@
-use crate::{scene::camera::Camera, core::color_rgb::ColorRGB};
+use crate::{scene::{camera::Camera, atmosphere::Atmosphere, background::BackgroundEnum}, core::color_rgb::ColorRGB};
 
 pub struct SceneService {
     camera: Camera,
-    background: ColorRGB,
+    background: BackgroundEnum,
+    atmosphere: Option<Atmosphere>,
     // config loaded from config file goes here
     // dynamic global state goes here
 }
 
 impl SceneService {
     pub fn new(camera: Camera, background: ColorRGB) -> SceneService {
-        SceneService{ camera, background }
+        SceneService{ camera, background: BackgroundEnum::SolidColor(background), atmosphere: None }
     }
 
     pub fn get_camera(&self) -> &Camera {
         &self.camera
     }
 
-    pub fn get_background(&self) -> &ColorRGB {
+    pub fn get_background(&self) -> &BackgroundEnum {
         &self.background
     }
-}
+
+    pub fn set_background(&mut self, background: BackgroundEnum) {
+        self.background = background;
+    }
+
+    pub fn get_atmosphere(&self) -> Option<&Atmosphere> {
+        self.atmosphere.as_ref()
+    }
+
+    pub fn set_atmosphere(&mut self, atmosphere: Atmosphere) {
+        self.atmosphere = Some(atmosphere);
+    }
+}
\ No newline at end of file
@@ -1,9 +1,10 @@
-use crate::services::{material_service::MaterialService, hittable_service::HittableService, texture_service::TextureService, scene_service::SceneService};
+use crate::services::{material_service::MaterialService, hittable_service::HittableService, light_service::LightService, texture_service::TextureService, scene_service::SceneService};
 
 pub struct ServiceLocator {
     hittable_service: HittableService,
     material_service: MaterialService,
     texture_service: TextureService,
+    light_service: LightService,
     scene_service: SceneService
 }
 
@@ -12,8 +13,9 @@ impl ServiceLocator {
         let hittable_service = HittableService::new();
         let material_service = MaterialService::new();
         let texture_service = TextureService::new();
+        let light_service = LightService::new();
 
-        ServiceLocator{ hittable_service, material_service, texture_service, scene_service }
+        ServiceLocator{ hittable_service, material_service, texture_service, light_service, scene_service }
     }
 
     pub fn get_hittable_service(&self) -> &HittableService {
@@ -40,11 +42,19 @@ impl ServiceLocator {
         &mut self.texture_service
     }
 
+    pub fn get_light_service(&self) -> &LightService {
+        &self.light_service
+    }
+
+    pub fn get_light_service_mut(&mut self) -> &mut LightService {
+        &mut self.light_service
+    }
+
     pub fn get_scene_service(&self) -> &SceneService {
         &self.scene_service
     }
 
-    pub fn _get_scene_service_mut(&mut self) -> &mut SceneService {
+    pub fn get_scene_service_mut(&mut self) -> &mut SceneService {
         &mut self.scene_service
     }
 
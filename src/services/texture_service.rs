@@ -1,4 +1,4 @@
-use crate::{textures::{texture_enum::TextureEnum, default::Default, texture::Texture}, core::color_rgb::ColorRGB};
+use crate::{textures::{texture_enum::TextureEnum, default::Default, texture::Texture}, core::color_rgb::ColorRGB, hittables::hit_record::HitRecord};
 use ultraviolet::Vec3;
 
 pub struct TextureService {
@@ -30,4 +30,14 @@ impl TextureService {
         self.textures[texture_index].value(self, u, v, p, color_out)
     }
 
+    #[inline]
+    pub fn value_at_hit(&self, texture_index: usize, hit: &HitRecord, color_out: &mut ColorRGB) -> bool {
+        self.textures[texture_index].value_at_hit(self, hit, color_out)
+    }
+
+    #[inline]
+    pub fn alpha(&self, texture_index: usize, u: f32, v: f32, p: &Vec3) -> f32 {
+        self.textures[texture_index].alpha(self, u, v, p)
+    }
+
 }
@@ -0,0 +1,51 @@
+use ultraviolet::Vec3;
+
+use crate::{services::texture_service::TextureService, core::color_rgb::ColorRGB, hittables::hit_record::HitRecord};
+
+use super::texture::Texture;
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Operation {
+    Add,
+    Subtract,
+    Multiply,
+}
+
+// Combines two textures channel by channel, a multiplied with a solid color tints a texture
+pub struct Arithmetic {
+    a: usize,
+    b: usize,
+    operation: Operation,
+}
+
+impl Arithmetic {
+    pub fn new(a: usize, b: usize, operation: Operation) -> Self {
+        Arithmetic { a, b, operation }
+    }
+
+    fn combine(&self, lookup: impl Fn(usize, &mut ColorRGB) -> bool, color_out: &mut ColorRGB) -> bool {
+        let mut a: ColorRGB = ColorRGB::black();
+        lookup(self.a, &mut a);
+
+        let mut b: ColorRGB = ColorRGB::black();
+        lookup(self.b, &mut b);
+
+        *color_out = match self.operation {
+            Operation::Add => a + b,
+            Operation::Subtract => a - b,
+            Operation::Multiply => a * b,
+        };
+
+        true
+    }
+}
+
+impl Texture for Arithmetic {
+    fn value(&self, texture_service: &TextureService, u: f32, v: f32, p: &Vec3, color_out: &mut ColorRGB) -> bool {
+        self.combine(|index, color| texture_service.value(index, u, v, p, color), color_out)
+    }
+
+    fn value_at_hit(&self, texture_service: &TextureService, hit: &HitRecord, color_out: &mut ColorRGB) -> bool {
+        self.combine(|index, color| texture_service.value_at_hit(index, hit, color), color_out)
+    }
+}
\ No newline at end of file
@@ -0,0 +1,59 @@
+use ultraviolet::Vec3;
+
+use crate::{services::texture_service::TextureService, core::color_rgb::ColorRGB, hittables::hit_record::HitRecord};
+
+use super::texture::Texture;
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Channel {
+    Red,
+    Green,
+    Blue,
+    Alpha,
+    Luminance,
+}
+
+// Spreads a single channel of a texture to grayscale,
+// for packed maps with for example roughness in one channel and height in another
+pub struct ChannelExtract {
+    input: usize,
+    channel: Channel,
+}
+
+impl ChannelExtract {
+    pub fn new(input: usize, channel: Channel) -> Self {
+        ChannelExtract { input, channel }
+    }
+
+    fn extract(&self, input: &ColorRGB, alpha: impl Fn() -> f32, color_out: &mut ColorRGB) -> bool {
+        let value = match self.channel {
+            Channel::Red => input.r,
+            Channel::Green => input.g,
+            Channel::Blue => input.b,
+            Channel::Alpha => alpha(),
+            Channel::Luminance => input.luminance(),
+        };
+
+        color_out.r = value;
+        color_out.g = value;
+        color_out.b = value;
+
+        true
+    }
+}
+
+impl Texture for ChannelExtract {
+    fn value(&self, texture_service: &TextureService, u: f32, v: f32, p: &Vec3, color_out: &mut ColorRGB) -> bool {
+        let mut input: ColorRGB = ColorRGB::black();
+        texture_service.value(self.input, u, v, p, &mut input);
+
+        self.extract(&input, || texture_service.alpha(self.input, u, v, p), color_out)
+    }
+
+    fn value_at_hit(&self, texture_service: &TextureService, hit: &HitRecord, color_out: &mut ColorRGB) -> bool {
+        let mut input: ColorRGB = ColorRGB::black();
+        texture_service.value_at_hit(self.input, hit, &mut input);
+
+        self.extract(&input, || texture_service.alpha(self.input, hit.u, hit.v, &hit.position), color_out)
+    }
+}
\ No newline at end of file
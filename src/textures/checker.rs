@@ -1,6 +1,6 @@
 use ultraviolet::Vec3;
 
-use crate::{services::texture_service::TextureService, core::color_rgb::ColorRGB};
+use crate::{services::texture_service::TextureService, core::color_rgb::ColorRGB, hittables::hit_record::HitRecord};
 
 use super::texture::Texture;
 
@@ -14,15 +14,20 @@ impl Checker {
         Checker{odd, even}
     }
 
+    #[inline]
+    fn pick(&self, p: &Vec3) -> usize {
+        let sines = (10.0 * p.x).sin() * (10.0 * p.y).sin() * (10.0 * p.z).sin();
+        if sines < 0.0 { self.odd } else { self.even }
+    }
+
 }
 
 impl Texture for Checker {
     fn value(&self, texture_service: &TextureService, u: f32, v: f32, p: &Vec3, color_out: &mut ColorRGB) -> bool {
-        let sines = (10.0 * p.x).sin() * (10.0 * p.y).sin() * (10.0 * p.z).sin();
-        if sines < 0.0 {
-            texture_service.value(self.odd, u, v, p, color_out)
-        } else {
-            texture_service.value(self.even, u, v, p, color_out)
-        }
+        texture_service.value(self.pick(p), u, v, p, color_out)
+    }
+
+    fn value_at_hit(&self, texture_service: &TextureService, hit: &HitRecord, color_out: &mut ColorRGB) -> bool {
+        texture_service.value_at_hit(self.pick(&hit.position), hit, color_out)
     }
 }
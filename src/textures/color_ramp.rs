@@ -0,0 +1,52 @@
+use ultraviolet::Vec3;
+
+use crate::{services::texture_service::TextureService, core::color_rgb::ColorRGB, hittables::hit_record::HitRecord};
+
+use super::texture::Texture;
+
+// Maps the luminance of another texture to a color, interpolating linearly between the stops.
+// Values outside of the stops get the color of the closest stop.
+pub struct ColorRamp {
+    input_texture_index: usize,
+    stops: Vec<(f32, ColorRGB)>,
+}
+
+impl ColorRamp {
+    pub fn new(input_texture_index: usize, mut stops: Vec<(f32, ColorRGB)>) -> Self {
+        assert!(!stops.is_empty(), "a color ramp needs at least one stop");
+        stops.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+        ColorRamp { input_texture_index, stops }
+    }
+
+    fn ramp(&self, lookup: impl Fn(usize, &mut ColorRGB) -> bool, color_out: &mut ColorRGB) -> bool {
+        let mut input: ColorRGB = ColorRGB::black();
+        lookup(self.input_texture_index, &mut input);
+        let value = input.luminance();
+
+        let next = self.stops.iter().position(|(position, _)| value < *position);
+        *color_out = match next {
+            Some(0) => self.stops[0].1,
+            None => self.stops[self.stops.len() - 1].1,
+            Some(index) => {
+                let (start, start_color) = self.stops[index - 1];
+                let (end, end_color) = self.stops[index];
+                let weight = (value - start) / (end - start);
+
+                start_color * (1.0 - weight) + end_color * weight
+            },
+        };
+
+        true
+    }
+}
+
+impl Texture for ColorRamp {
+    fn value(&self, texture_service: &TextureService, u: f32, v: f32, p: &Vec3, color_out: &mut ColorRGB) -> bool {
+        self.ramp(|index, color| texture_service.value(index, u, v, p, color), color_out)
+    }
+
+    fn value_at_hit(&self, texture_service: &TextureService, hit: &HitRecord, color_out: &mut ColorRGB) -> bool {
+        self.ramp(|index, color| texture_service.value_at_hit(index, hit, color), color_out)
+    }
+}
\ No newline at end of file
@@ -0,0 +1,60 @@
+use ultraviolet::Vec3;
+
+use crate::{services::texture_service::TextureService, noise::{perlin::Perlin, simplex::Simplex}, core::color_rgb::ColorRGB};
+
+use super::texture::Texture;
+
+pub enum NoiseBasis {
+    Perlin(Perlin),
+    Simplex(Simplex),
+}
+
+impl NoiseBasis {
+    #[inline]
+    fn noise(&self, point: &Vec3) -> f32 {
+        match self {
+            NoiseBasis::Perlin(perlin) => perlin.noise(point),
+            NoiseBasis::Simplex(simplex) => simplex.noise(point),
+        }
+    }
+}
+
+// Fractal brownian motion, sums octaves of noise where every octave has its frequency multiplied
+// by lacunarity and its amplitude multiplied by gain. The result is grayscale in [0, 1].
+// A single octave of simplex noise gives plain simplex noise.
+pub struct Fbm {
+    basis: NoiseBasis,
+    scale: f32,
+    octaves: u32,
+    lacunarity: f32,
+    gain: f32,
+}
+
+impl Fbm {
+    pub fn new(basis: NoiseBasis, scale: f32, octaves: u32, lacunarity: f32, gain: f32) -> Self {
+        Fbm { basis, scale, octaves: octaves.max(1), lacunarity, gain }
+    }
+}
+
+impl Texture for Fbm {
+    fn value(&self, _texture_service: &TextureService, _u: f32, _v: f32, point: &Vec3, color_out: &mut ColorRGB) -> bool {
+        let mut sum: f32 = 0.0;
+        let mut amplitude_sum: f32 = 0.0;
+        let mut amplitude: f32 = 1.0;
+        let mut frequency: f32 = self.scale;
+
+        for _octave in 0..self.octaves {
+            sum += amplitude * self.basis.noise(&(*point * frequency));
+            amplitude_sum += amplitude;
+            amplitude *= self.gain;
+            frequency *= self.lacunarity;
+        }
+
+        let value = (0.5 + 0.5 * sum / amplitude_sum).clamp(0.0, 1.0);
+        color_out.r = value;
+        color_out.g = value;
+        color_out.b = value;
+
+        true
+    }
+}
\ No newline at end of file
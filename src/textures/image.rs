@@ -1,55 +1,480 @@
 use ultraviolet::Vec3;
 
-use crate::{services::texture_service::TextureService, core::color_rgb::ColorRGB};
+use crate::{services::texture_service::TextureService, core::color_rgb::ColorRGB, hittables::hit_record::HitRecord};
 
-use super::texture::Texture;
-pub struct Image {
-    data: Vec<u8>,
+use super::{texture::Texture, uv_transform::UVTransform, image_loaders::load_pfm};
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum ImageFilter {
+    Nearest,
+    Bilinear,
+    Trilinear, // Bilinear on the two mip levels around the footprint of the lookup
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum WrapMode {
+    Clamp,
+    Repeat,
+    Mirror,
+}
+
+impl WrapMode {
+    #[inline]
+    fn texel(&self, coordinate: i64, size: usize) -> usize {
+        let size = size as i64;
+        match self {
+            WrapMode::Clamp => coordinate.clamp(0, size - 1) as usize,
+            WrapMode::Repeat => coordinate.rem_euclid(size) as usize,
+            WrapMode::Mirror => {
+                let coordinate = coordinate.rem_euclid(2 * size);
+                (if size <= coordinate { 2 * size - 1 - coordinate } else { coordinate }) as usize
+            }
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum ColorSpace {
+    Srgb,
+    Linear,
+}
+
+#[inline]
+fn srgb_to_linear(value: f32) -> f32 {
+    if value <= 0.04045 {
+        value / 12.92
+    } else {
+        ((value + 0.055) / 1.055).powf(2.4)
+    }
+}
+
+fn file_extension(path: &str) -> String {
+    std::path::Path::new(path).extension().map(|extension| extension.to_string_lossy().to_lowercase()).unwrap_or_default()
+}
+
+// Color images in 8-bit formats are assumed to be sRGB encoded, .hdr, .exr and .pfm files to be linear
+#[inline]
+pub fn default_color_space(path: &str) -> ColorSpace {
+    if matches!(file_extension(path).as_str(), "hdr" | "exr" | "pfm") { ColorSpace::Linear } else { ColorSpace::Srgb }
+}
+
+// RGBA pixels with the bottom row first, as linear values.
+// 8-bit formats are scaled to [0, 1], float formats keep their full range.
+// sRGB encoded colors are decoded at load time, alpha is always linear.
+pub fn load_image_data(path: &str, color_space: ColorSpace) -> (Vec<f32>, usize, usize) {
+    let (mut data, width, height) = match file_extension(path).as_str() {
+        "pfm" => load_pfm(path),
+        _ => {
+            let image = image::open(path).expect("image not found").flipv().to_rgba32f();
+            let (width, height) = image.dimensions();
+            (image.into_raw(), width as usize, height as usize)
+        }
+    };
+
+    if color_space == ColorSpace::Srgb {
+        for pixel in data.chunks_exact_mut(4) {
+            for value in pixel.iter_mut().take(3) {
+                *value = srgb_to_linear(*value);
+            }
+        }
+    }
+
+    (data, width, height)
+}
+
+// For every texel of a level downsampled from source_size to target_size,
+// the source texels under it and how much of the box each of them covers
+fn box_filter_weights(source_size: usize, target_size: usize) -> Vec<Vec<(usize, f32)>> {
+    let box_width = source_size as f32 / target_size as f32;
+
+    (0..target_size).map(|target| {
+        let start = target as f32 * box_width;
+        let end = start + box_width;
+
+        (start.floor() as usize..(end.ceil() as usize).min(source_size))
+            .map(|source| {
+                let overlap = end.min(source as f32 + 1.0) - start.max(source as f32);
+                (source, overlap / box_width)
+            })
+            .filter(|(_, weight)| 0.0 < *weight)
+            .collect()
+    }).collect()
+}
+
+// One level of the mip chain, RGBA
+struct ImageLevel {
+    data: Vec<f32>,
     width: usize,
     height: usize,
-    bytes_per_pixel: usize,
-    bytes_per_scanline: usize,
+}
+
+impl ImageLevel {
+    #[inline]
+    fn texel(&self, x: usize, y: usize) -> [f32; 4] {
+        let index = (y * self.width + x) * 4;
+        [self.data[index], self.data[index + 1], self.data[index + 2], self.data[index + 3]]
+    }
+
+    // Box filters the level down to half its size. Odd sizes are covered by boxes slightly
+    // wider than 2 texels, the texels straddling a box edge are split between both boxes by their overlap.
+    fn downsample(&self) -> ImageLevel {
+        let width = (self.width / 2).max(1);
+        let height = (self.height / 2).max(1);
+        let column_weights = box_filter_weights(self.width, width);
+        let row_weights = box_filter_weights(self.height, height);
+        let mut data: Vec<f32> = Vec::with_capacity(width * height * 4);
+
+        for row in &row_weights {
+            for column in &column_weights {
+                let mut result = [0.0; 4];
+                for &(y, y_weight) in row {
+                    for &(x, x_weight) in column {
+                        let texel = self.texel(x, y);
+                        for channel in 0..4 {
+                            result[channel] += x_weight * y_weight * texel[channel];
+                        }
+                    }
+                }
+                data.extend_from_slice(&result);
+            }
+        }
+
+        ImageLevel { data, width, height }
+    }
+}
+
+// Trilinear filtering picks the mip level per hit, from the extent of the pixel footprint at the hit
+// measured in texels of the full resolution image, along whichever of u and v covers more texels. Distant and grazing lookups read coarser levels,
+// which keeps tiled textures from aliasing while close ones stay sharp.
+// Lookups without a hit use the full resolution.
+// Level 0 is the full resolution image, every level halves the resolution.
+pub struct Image {
+    levels: Vec<ImageLevel>,
+    filter: ImageFilter,
+    wrap_mode: WrapMode,
+    uv_transform: UVTransform,
 }
 
 impl Image {
+    // For color textures, like albedo and emission
     pub fn new(path: &str) -> Self {
-        let bytes_per_pixel: usize = 3;
+        Image::with_color_space(path, default_color_space(path))
+    }
+
+    // For non-color data, like normal, roughness and height maps, which are used as stored
+    pub fn new_data(path: &str) -> Self {
+        Image::with_color_space(path, ColorSpace::Linear)
+    }
 
-        let image = image::open(path).expect("image not found").flipv().to_rgb8();
-        let (width, height) = image.dimensions();
-        let data = image.into_raw();
+    pub fn with_color_space(path: &str, color_space: ColorSpace) -> Self {
+        let (data, width, height) = load_image_data(path, color_space);
 
-        Image{data, width: width as usize, height: height as usize, bytes_per_pixel, bytes_per_scanline: bytes_per_pixel * width as usize}
+        Image{
+            levels: vec![ImageLevel{ data, width, height }], 
+            filter: ImageFilter::Nearest, 
+            wrap_mode: WrapMode::Clamp, 
+            uv_transform: UVTransform::identity()
+        }
     }
-}
 
-impl Texture for Image {
-    fn value(&self, _texture_service: &TextureService, u: f32, v: f32, _point: &Vec3, color_out: &mut ColorRGB) -> bool {
-        if self.data.is_empty() {
+    pub fn with_filter(mut self, filter: ImageFilter) -> Self {
+        if filter == ImageFilter::Trilinear && self.levels.len() == 1 {
+            self.build_mip_chain();
+        }
+
+        self.filter = filter;
+        self
+    }
+
+    pub fn with_wrap_mode(mut self, wrap_mode: WrapMode) -> Self {
+        self.wrap_mode = wrap_mode;
+        self
+    }
+
+    pub fn with_uv_transform(mut self, uv_transform: UVTransform) -> Self {
+        self.uv_transform = uv_transform;
+        self
+    }
+
+    fn build_mip_chain(&mut self) {
+        while let Some(last) = self.levels.last() {
+            if last.width == 1 && last.height == 1 {
+                break;
+            }
+
+            let next = last.downsample();
+            self.levels.push(next);
+        }
+    }
+
+    #[inline]
+    fn nearest(&self, level: &ImageLevel, u: f32, v: f32) -> [f32; 4] {
+        let x = self.wrap_mode.texel((u * level.width as f32).floor() as i64, level.width);
+        let y = self.wrap_mode.texel((v * level.height as f32).floor() as i64, level.height);
+
+        level.texel(x, y)
+    }
+
+    #[inline]
+    fn bilinear(&self, level: &ImageLevel, u: f32, v: f32) -> [f32; 4] {
+        let x = u * level.width as f32 - 0.5;
+        let y = v * level.height as f32 - 0.5;
+        let x_floor = x.floor();
+        let y_floor = y.floor();
+        let x_weight = x - x_floor;
+        let y_weight = y - y_floor;
+
+        let x0 = self.wrap_mode.texel(x_floor as i64, level.width);
+        let x1 = self.wrap_mode.texel(x_floor as i64 + 1, level.width);
+        let y0 = self.wrap_mode.texel(y_floor as i64, level.height);
+        let y1 = self.wrap_mode.texel(y_floor as i64 + 1, level.height);
+
+        let texels = [level.texel(x0, y0), level.texel(x1, y0), level.texel(x0, y1), level.texel(x1, y1)];
+        let weights = [(1.0 - x_weight) * (1.0 - y_weight), x_weight * (1.0 - y_weight), (1.0 - x_weight) * y_weight, x_weight * y_weight];
+
+        let mut result = [0.0; 4];
+        for (texel, weight) in texels.iter().zip(weights.iter()) {
+            for channel in 0..4 {
+                result[channel] += weight * texel[channel];
+            }
+        }
+
+        result
+    }
+
+    // Mip level of a lookup at a hit, 0 if the hit has no footprint or no uv parameterization
+    fn level_of_detail(&self, hit: &HitRecord) -> f32 {
+        let (u_scale, v_scale) = self.uv_transform.apply_to_uv_scale(hit.uv_scale);
+        if hit.footprint.0 <= 0.0 || hit.footprint.1 <= 0.0 || u_scale <= 0.0 || v_scale <= 0.0 {
+            return 0.0;
+        }
+
+        let texels_u = hit.footprint.0 / u_scale * self.levels[0].width as f32;
+        let texels_v = hit.footprint.1 / v_scale * self.levels[0].height as f32;
+
+        texels_u.max(texels_v).log2().max(0.0)
+    }
+
+    fn sample(&self, u: f32, v: f32, level_of_detail: f32) -> [f32; 4] {
+        let (u, v) = self.uv_transform.apply(u, v);
+
+        match self.filter {
+            ImageFilter::Nearest => self.nearest(&self.levels[0], u, v),
+            ImageFilter::Bilinear => self.bilinear(&self.levels[0], u, v),
+            ImageFilter::Trilinear => {
+                let level = level_of_detail.clamp(0.0, (self.levels.len() - 1) as f32);
+                let level_index = level.floor() as usize;
+                let level_weight = level - level_index as f32;
+                let fine = self.bilinear(&self.levels[level_index], u, v);
+                if level_weight <= 0.0 || self.levels.len() <= level_index + 1 {
+                    return fine;
+                }
+
+                let coarse = self.bilinear(&self.levels[level_index + 1], u, v);
+                let mut result = [0.0; 4];
+                for channel in 0..4 {
+                    result[channel] = (1.0 - level_weight) * fine[channel] + level_weight * coarse[channel];
+                }
+
+                result
+            }
+        }
+    }
+
+    fn color(&self, u: f32, v: f32, level_of_detail: f32, color_out: &mut ColorRGB) -> bool {
+        if self.levels[0].data.is_empty() {
             color_out.r = 0.0;
             color_out.g = 1.0;
             color_out.b = 1.0;
-            
+
             return false;
         }
 
-        let u: f32 = u.clamp(0.0, 1.0);
-        let v: f32 = v.clamp(0.0, 1.0);
+        let texel = self.sample(u, v, level_of_detail);
 
-        let mut i: usize = ( u * self.width as f32 ) as usize;
-        let mut j: usize = ( v * self.height as f32 ) as usize;
+        color_out.r = texel[0];
+        color_out.g = texel[1];
+        color_out.b = texel[2];
 
-        if self.width <= i { i = self.width - 1; }
-        if self.height <= j { j = self.height - 1; } 
 
-        let color_scale: f32 = 1.0 / 255.0;
-        let pixel_index: usize = j * self.bytes_per_scanline + i * self.bytes_per_pixel;
+        true
+    }
+}
 
-        color_out.r = color_scale * self.data[pixel_index    ] as f32;
-        color_out.g = color_scale * self.data[pixel_index + 1] as f32;
-        color_out.b = color_scale * self.data[pixel_index + 2] as f32;
+impl Texture for Image {
+    fn value(&self, _texture_service: &TextureService, u: f32, v: f32, _point: &Vec3, color_out: &mut ColorRGB) -> bool {
+        self.color(u, v, 0.0, color_out)
+    }
 
+    fn value_at_hit(&self, _texture_service: &TextureService, hit: &HitRecord, color_out: &mut ColorRGB) -> bool {
+        self.color(hit.u, hit.v, self.level_of_detail(hit), color_out)
+    }
 
-        true
+    fn alpha(&self, _texture_service: &TextureService, u: f32, v: f32, _point: &Vec3) -> f32 {
+        if self.levels[0].data.is_empty() {
+            return 1.0;
+        }
+
+        self.sample(u, v, 0.0)[3]
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::ray::Ray;
+
+    use super::*;
+
+    #[test]
+    fn wrap_modes_handle_negative_coordinates() {
+        let texels = |wrap_mode: WrapMode| (-5..9).map(|coordinate| wrap_mode.texel(coordinate, 4)).collect::<Vec<usize>>();
+
+        assert_eq!(texels(WrapMode::Clamp), vec![0, 0, 0, 0, 0, 0, 1, 2, 3, 3, 3, 3, 3, 3]);
+        assert_eq!(texels(WrapMode::Repeat), vec![3, 0, 1, 2, 3, 0, 1, 2, 3, 0, 1, 2, 3, 0]);
+        assert_eq!(texels(WrapMode::Mirror), vec![3, 3, 2, 1, 0, 0, 1, 2, 3, 3, 2, 1, 0, 0]);
+    }
+
+    // Every texel holds its own x in red and y in green
+    fn coordinate_level(width: usize, height: usize) -> ImageLevel {
+        let mut data: Vec<f32> = Vec::with_capacity(width * height * 4);
+        for y in 0..height {
+            for x in 0..width {
+                data.extend_from_slice(&[x as f32, y as f32, 0.0, 1.0]);
+            }
+        }
+
+        ImageLevel { data, width, height }
+    }
+
+    fn assert_texel_close(actual: [f32; 4], expected: [f32; 4]) {
+        for channel in 0..4 {
+            assert!((actual[channel] - expected[channel]).abs() < 1.0e-5, "got {:?}, expected {:?}", actual, expected);
+        }
+    }
+
+    #[test]
+    fn downsample_odd_sizes() {
+        let level = coordinate_level(5, 3).downsample();
+        assert_eq!((level.width, level.height), (2, 1));
+
+        // Boxes 2.5 texels wide, the middle column is split between both, every row counts a third
+        assert_texel_close(level.texel(0, 0), [0.8, 1.0, 0.0, 1.0]);
+        assert_texel_close(level.texel(1, 0), [3.2, 1.0, 0.0, 1.0]);
+
+        // Single texel wide levels keep their width of 1
+        let level = coordinate_level(1, 3).downsample();
+        assert_eq!((level.width, level.height), (1, 1));
+        assert_texel_close(level.texel(0, 0), [0.0, 1.0, 0.0, 1.0]);
+    }
+
+    #[test]
+    fn downsample_keeps_the_mean() {
+        for (width, height) in [(4, 4), (5, 3), (7, 2), (9, 9)] {
+            let source = coordinate_level(width, height);
+            let level = source.downsample();
+
+            let mean = |level: &ImageLevel| level.data.chunks_exact(4).map(|texel| texel[0] + texel[1]).sum::<f32>() / (level.width * level.height) as f32;
+            assert!((mean(&source) - mean(&level)).abs() < 1.0e-4, "{}x{}", width, height);
+        }
+    }
+
+    #[test]
+    fn mip_chain_ends_at_one_texel() {
+        let mut image = Image { 
+            levels: vec![coordinate_level(7, 3)], 
+            filter: ImageFilter::Nearest, 
+            wrap_mode: WrapMode::Clamp, 
+            uv_transform: UVTransform::identity()
+        };
+        image.build_mip_chain();
+
+        let sizes: Vec<(usize, usize)> = image.levels.iter().map(|level| (level.width, level.height)).collect();
+        assert_eq!(sizes, vec![(7, 3), (3, 1), (1, 1)]);
+    }
+
+    // A 64x64 texture on a 4x4 patch of floor, looked at straight down with a cone spreading 1/1000 per unit of distance
+    fn floor_hit(image: &Image, distance: f32) -> f32 {
+        let ray = Ray::new(Vec3::new(0.0, distance, 0.0), Vec3::new(0.0, -1.0, 0.0), 0.0).with_cone(0.0, 1.0e-3);
+        let mut hit = HitRecord::new(&ray, distance, 0.25, 0.25, &Vec3::zero(), &Vec3::new(0.0, 1.0, 0.0), 0);
+        hit.set_tangents(&Vec3::new(1.0, 0.0, 0.0), &Vec3::new(0.0, 0.0, 1.0));
+        hit.uv_scale = (4.0, 4.0);
+        hit.set_footprint(&ray);
+
+        image.level_of_detail(&hit)
+    }
+
+    fn trilinear_image(uv_transform: UVTransform) -> Image {
+        Image { 
+            levels: vec![coordinate_level(64, 64)], 
+            filter: ImageFilter::Nearest, 
+            wrap_mode: WrapMode::Repeat, 
+            uv_transform
+        }.with_filter(ImageFilter::Trilinear)
+    }
+
+    #[test]
+    fn level_of_detail_grows_with_distance() {
+        let image = trilinear_image(UVTransform::identity());
+
+        // The footprint covers 0.016 texels per unit of distance, one texel at a distance of 62.5
+        assert_eq!(floor_hit(&image, 10.0), 0.0);
+        let levels: Vec<f32> = [125.0, 250.0, 500.0, 1000.0].iter().map(|distance| floor_hit(&image, *distance)).collect();
+        for (level, expected) in levels.iter().zip([1.0, 2.0, 3.0, 4.0]) {
+            assert!((level - expected).abs() < 1.0e-4, "{:?}", levels);
+        }
+
+        // Tiling the texture 4 times packs 4 times the texels into the same footprint
+        let tiled = trilinear_image(UVTransform::new(4.0, 4.0, 0.0, 0.0, 0.0));
+        assert!((floor_hit(&tiled, 125.0) - 3.0).abs() < 1.0e-4);
+    }
+
+    #[test]
+    fn grazing_lookups_stretch_the_footprint_along_the_view() {
+        // Looking along v at 60 degrees from the normal, the footprint is twice as long along v
+        let ray = Ray::new(Vec3::new(0.0, 100.0, -100.0 * 3.0_f32.sqrt()), Vec3::new(0.0, -0.5, 0.5 * 3.0_f32.sqrt()), 0.0).with_cone(0.0, 1.0e-3);
+        let mut hit = HitRecord::new(&ray, 200.0, 0.25, 0.25, &Vec3::zero(), &Vec3::new(0.0, 1.0, 0.0), 0);
+        hit.set_tangents(&Vec3::new(1.0, 0.0, 0.0), &Vec3::new(0.0, 0.0, 1.0));
+        hit.set_footprint(&ray);
+        assert!((hit.footprint.0 - 0.2).abs() < 1.0e-4 && (hit.footprint.1 - 0.4).abs() < 1.0e-4);
+
+        // So the level follows the 6.4 texels along v, until u is tiled densely enough to cover more
+        hit.uv_scale = (4.0, 4.0);
+        assert!((trilinear_image(UVTransform::identity()).level_of_detail(&hit) - 6.4_f32.log2()).abs() < 1.0e-4);
+        assert!((trilinear_image(UVTransform::new(4.0, 1.0, 0.0, 0.0, 0.0)).level_of_detail(&hit) - 12.8_f32.log2()).abs() < 1.0e-4);
+    }
+
+    #[test]
+    fn distant_lookups_read_the_average_color() {
+        let image = trilinear_image(UVTransform::identity());
+        let service = TextureService::new();
+
+        let ray = Ray::new(Vec3::new(0.0, 1.0e5, 0.0), Vec3::new(0.0, -1.0, 0.0), 0.0).with_cone(0.0, 1.0e-3);
+        let mut hit = HitRecord::new(&ray, 1.0e5, 0.3, 0.7, &Vec3::zero(), &Vec3::new(0.0, 1.0, 0.0), 0);
+        hit.set_tangents(&Vec3::new(1.0, 0.0, 0.0), &Vec3::new(0.0, 0.0, 1.0));
+        hit.uv_scale = (4.0, 4.0);
+        hit.set_footprint(&ray);
+
+        let mut color = ColorRGB::black();
+        image.value_at_hit(&service, &hit, &mut color);
+        assert!((color.r - 31.5).abs() < 1.0e-3 && (color.g - 31.5).abs() < 1.0e-3);
+
+        // Without a footprint, the full resolution texel
+        let mut color = ColorRGB::black();
+        image.value(&service, 0.3, 0.7, &Vec3::zero(), &mut color);
+        assert!((color.r - 19.0).abs() < 1.0 && (color.g - 44.0).abs() < 1.0);
+    }
+
+    #[test]
+    fn loads_exr_as_linear_with_the_bottom_row_first() {
+        // Top row 2.0 and 3.0, bottom row 4.0 and 5.0, values above 1 have to survive
+        let pixels: Vec<f32> = vec![2.0, 2.0, 2.0, 1.0, 3.0, 3.0, 3.0, 1.0, 4.0, 4.0, 4.0, 1.0, 5.0, 5.0, 5.0, 0.5];
+        let path = std::env::temp_dir().join("raytracing_in_rust_test.exr");
+        image::Rgba32FImage::from_raw(2, 2, pixels).unwrap().save(&path).unwrap();
+
+        let path = path.to_string_lossy().to_string();
+        assert!(default_color_space(&path) == ColorSpace::Linear);
+        let (data, width, height) = load_image_data(&path, ColorSpace::Linear);
+        assert_eq!((width, height), (2, 2));
+        assert_eq!(data, vec![4.0, 4.0, 4.0, 1.0, 5.0, 5.0, 5.0, 0.5, 2.0, 2.0, 2.0, 1.0, 3.0, 3.0, 3.0, 1.0]);
+    }
+}
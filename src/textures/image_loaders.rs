@@ -0,0 +1,106 @@
+// Loaders for the float formats the image crate can't handle.
+// All of them return RGBA pixels with the bottom row first, like Image expects,
+// along with the width and the height. Missing alpha is filled in as 1.
+
+// Portable float map, either "PF" for RGB or "Pf" for grayscale.
+// A negative scale in the header marks little endian data, rows are stored bottom to top.
+pub fn load_pfm(path: &str) -> (Vec<f32>, usize, usize) {
+    let bytes = std::fs::read(path).expect("image not found");
+
+    // The header is three whitespace separated tokens after the identifier,
+    // followed by a single whitespace character and the data
+    let mut tokens: Vec<String> = Vec::new();
+    let mut position: usize = 0;
+    while tokens.len() < 4 {
+        while position < bytes.len() && bytes[position].is_ascii_whitespace() {
+            position += 1;
+        }
+
+        let start = position;
+        while position < bytes.len() && !bytes[position].is_ascii_whitespace() {
+            position += 1;
+        }
+
+        assert!(start < position, "pfm header is truncated");
+        tokens.push(String::from_utf8_lossy(&bytes[start..position]).to_string());
+    }
+    position += 1;
+
+    let channel_count: usize = match tokens[0].as_str() {
+        "PF" => 3,
+        "Pf" => 1,
+        _ => panic!("{} is not a pfm file", path),
+    };
+    let width: usize = tokens[1].parse().expect("pfm width is not a number");
+    let height: usize = tokens[2].parse().expect("pfm height is not a number");
+    let scale: f32 = tokens[3].parse().expect("pfm scale is not a number");
+    let is_little_endian = scale < 0.0;
+
+    let sample_count = width * height * channel_count;
+    assert!(position + sample_count * 4 <= bytes.len(), "pfm data is truncated");
+
+    let samples: Vec<f32> = bytes[position..position + sample_count * 4]
+        .chunks_exact(4)
+        .map(|chunk| {
+            let chunk: [u8; 4] = chunk.try_into().unwrap();
+            if is_little_endian { f32::from_le_bytes(chunk) } else { f32::from_be_bytes(chunk) }
+        })
+        .collect();
+
+    let mut data: Vec<f32> = Vec::with_capacity(width * height * 4);
+    for pixel in samples.chunks_exact(channel_count) {
+        if channel_count == 3 {
+            data.extend_from_slice(&[pixel[0], pixel[1], pixel[2], 1.0]);
+        } else {
+            data.extend_from_slice(&[pixel[0], pixel[0], pixel[0], 1.0]);
+        }
+    }
+
+    (data, width, height)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // The fixtures are 2 by 3 pixels, written bottom row first,
+    // red is x + 10 y with y counted from the bottom, green and blue are offset by 100 and 200
+    fn assert_rgb(path: &str) {
+        let (data, width, height) = load_pfm(path);
+        assert_eq!((width, height), (2, 3));
+
+        for y in 0..height {
+            for x in 0..width {
+                let value = (x + 10 * y) as f32;
+                let index = (y * width + x) * 4;
+                assert_eq!(data[index..index + 4], [value, 100.0 + value, 200.0 + value, 1.0], "pixel {} {} of {}", x, y, path);
+            }
+        }
+    }
+
+    // Grayscale is x + 10 y + 0.5, copied to all three color channels
+    fn assert_gray(path: &str) {
+        let (data, width, height) = load_pfm(path);
+        assert_eq!((width, height), (2, 3));
+
+        for y in 0..height {
+            for x in 0..width {
+                let value = (x + 10 * y) as f32 + 0.5;
+                let index = (y * width + x) * 4;
+                assert_eq!(data[index..index + 4], [value, value, value, 1.0], "pixel {} {} of {}", x, y, path);
+            }
+        }
+    }
+
+    #[test]
+    fn loads_rgb_pfm_in_both_endiannesses() {
+        assert_rgb("fixtures/rgb_le.pfm");
+        assert_rgb("fixtures/rgb_be.pfm");
+    }
+
+    #[test]
+    fn loads_grayscale_pfm_in_both_endiannesses() {
+        assert_gray("fixtures/gray_le.pfm");
+        assert_gray("fixtures/gray_be.pfm");
+    }
+}
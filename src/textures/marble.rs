@@ -0,0 +1,32 @@
+use ultraviolet::Vec3;
+
+use crate::{services::texture_service::TextureService, noise::perlin::Perlin, core::color_rgb::ColorRGB};
+
+use super::texture::Texture;
+
+// Veins along the z axis, bent by turbulence. The result is grayscale in [0, 1].
+// Noise is the same pattern with a turbulence of 10 and 7 octaves.
+pub struct Marble {
+    perlin: Perlin,
+    scale: f32,
+    turbulence: f32,
+    octaves: i32,
+}
+
+impl Marble {
+    pub fn new(seed: u64, point_count: u32, scale: f32, turbulence: f32, octaves: i32) -> Self {
+        Marble { perlin: Perlin::new(seed, point_count), scale, turbulence, octaves }
+    }
+}
+
+impl Texture for Marble {
+    fn value(&self, _texture_service: &TextureService, _u: f32, _v: f32, point: &Vec3, color_out: &mut ColorRGB) -> bool {
+        let value = 0.5 * (1.0 + (self.scale * point.z + self.turbulence * self.perlin.turbulence(point, self.octaves)).sin());
+
+        color_out.r = value;
+        color_out.g = value;
+        color_out.b = value;
+
+        true
+    }
+}
\ No newline at end of file
@@ -0,0 +1,45 @@
+use ultraviolet::Vec3;
+
+use crate::{services::texture_service::TextureService, core::color_rgb::ColorRGB, hittables::hit_record::HitRecord};
+
+use super::texture::Texture;
+
+// Blends two textures by the luminance of a mask texture, 0 gives a and 1 gives b
+pub struct Mix {
+    a: usize,
+    b: usize,
+    mask: usize,
+}
+
+impl Mix {
+    pub fn new(a: usize, b: usize, mask: usize) -> Self {
+        Mix { a, b, mask }
+    }
+
+    // The inputs are looked up through lookup, so lookups at a hit stay at the hit
+    fn blend(&self, lookup: impl Fn(usize, &mut ColorRGB) -> bool, color_out: &mut ColorRGB) -> bool {
+        let mut mask: ColorRGB = ColorRGB::black();
+        lookup(self.mask, &mut mask);
+        let weight = mask.luminance().clamp(0.0, 1.0);
+
+        let mut a: ColorRGB = ColorRGB::black();
+        lookup(self.a, &mut a);
+
+        let mut b: ColorRGB = ColorRGB::black();
+        lookup(self.b, &mut b);
+
+        *color_out = a * (1.0 - weight) + b * weight;
+
+        true
+    }
+}
+
+impl Texture for Mix {
+    fn value(&self, texture_service: &TextureService, u: f32, v: f32, p: &Vec3, color_out: &mut ColorRGB) -> bool {
+        self.blend(|index, color| texture_service.value(index, u, v, p, color), color_out)
+    }
+
+    fn value_at_hit(&self, texture_service: &TextureService, hit: &HitRecord, color_out: &mut ColorRGB) -> bool {
+        self.blend(|index, color| texture_service.value_at_hit(index, hit, color), color_out)
+    }
+}
\ No newline at end of file
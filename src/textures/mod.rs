@@ -2,6 +2,20 @@ pub mod texture;
 pub mod checker;
 pub mod default;
 pub mod image;
+pub mod image_loaders;
 pub mod noise;
+pub mod fbm;
+pub mod worley;
+pub mod marble;
+pub mod wood;
+pub mod color_ramp;
+pub mod mix;
+pub mod arithmetic;
+pub mod remap;
+pub mod channel_extract;
+pub mod uv_transform_node;
+pub mod triplanar;
+pub mod volume_grid;
 pub mod solid_color;
+pub mod uv_transform;
 pub mod texture_enum;
\ No newline at end of file
@@ -1,5 +1,4 @@
 use ultraviolet::{Vec3};
-use rand::rngs::ThreadRng;
 
 use crate::{services::texture_service::TextureService, noise::perlin::Perlin, core::color_rgb::ColorRGB};
 
@@ -11,8 +10,8 @@ pub struct Noise {
 }
 
 impl Noise {
-    pub fn new(rng: &mut ThreadRng, point_count: u32, scale: f32) -> Self {
-        Noise{perlin: Perlin::new(rng, point_count), scale}
+    pub fn new(seed: u64, point_count: u32, scale: f32) -> Self {
+        Noise{perlin: Perlin::new(seed, point_count), scale}
     }
 }
 
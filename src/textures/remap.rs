@@ -0,0 +1,53 @@
+use ultraviolet::Vec3;
+
+use crate::{services::texture_service::TextureService, core::color_rgb::ColorRGB, hittables::hit_record::HitRecord};
+
+use super::texture::Texture;
+
+// Linearly maps every channel from [from_minimum, from_maximum] to [to_minimum, to_maximum], clamped to the new range.
+// Mapping [0, 1] to [1, 0] inverts a texture.
+pub struct Remap {
+    input: usize,
+    from_minimum: f32,
+    from_maximum: f32,
+    to_minimum: f32,
+    to_maximum: f32,
+}
+
+impl Remap {
+    pub fn new(input: usize, from_minimum: f32, from_maximum: f32, to_minimum: f32, to_maximum: f32) -> Self {
+        Remap { input, from_minimum, from_maximum, to_minimum, to_maximum }
+    }
+
+    pub fn invert(input: usize) -> Self {
+        Remap::new(input, 0.0, 1.0, 1.0, 0.0)
+    }
+
+    #[inline]
+    fn remap(&self, value: f32) -> f32 {
+        let range = self.from_maximum - self.from_minimum;
+        let weight = if range.abs() < f32::EPSILON { 0.0 } else { ((value - self.from_minimum) / range).clamp(0.0, 1.0) };
+
+        self.to_minimum + weight * (self.to_maximum - self.to_minimum)
+    }
+
+    fn remap_input(&self, lookup: impl Fn(usize, &mut ColorRGB) -> bool, color_out: &mut ColorRGB) -> bool {
+        lookup(self.input, color_out);
+
+        color_out.r = self.remap(color_out.r);
+        color_out.g = self.remap(color_out.g);
+        color_out.b = self.remap(color_out.b);
+
+        true
+    }
+}
+
+impl Texture for Remap {
+    fn value(&self, texture_service: &TextureService, u: f32, v: f32, p: &Vec3, color_out: &mut ColorRGB) -> bool {
+        self.remap_input(|index, color| texture_service.value(index, u, v, p, color), color_out)
+    }
+
+    fn value_at_hit(&self, texture_service: &TextureService, hit: &HitRecord, color_out: &mut ColorRGB) -> bool {
+        self.remap_input(|index, color| texture_service.value_at_hit(index, hit, color), color_out)
+    }
+}
\ No newline at end of file
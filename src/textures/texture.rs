@@ -1,6 +1,15 @@
 use ultraviolet::Vec3;
-use crate::{services::texture_service::TextureService, core::color_rgb::ColorRGB};
+use crate::{services::texture_service::TextureService, core::color_rgb::ColorRGB, hittables::hit_record::HitRecord};
 
 pub trait Texture : Sync + Send {
     fn value(&self, texture_service: &TextureService, u: f32, v: f32, p: &Vec3, color_out: &mut ColorRGB) -> bool;
+
+    // Lookups on a surface, where textures can also use the footprint and normal of the hit
+    fn value_at_hit(&self, texture_service: &TextureService, hit: &HitRecord, color_out: &mut ColorRGB) -> bool {
+        self.value(texture_service, hit.u, hit.v, &hit.position, color_out)
+    }
+
+    fn alpha(&self, _texture_service: &TextureService, _u: f32, _v: f32, _p: &Vec3) -> f32 {
+        1.0
+    }
 }
\ No newline at end of file
@@ -1,7 +1,7 @@
-use crate::{services::texture_service::TextureService, core::color_rgb::ColorRGB};
+use crate::{services::texture_service::TextureService, core::color_rgb::ColorRGB, hittables::hit_record::HitRecord};
 use ultraviolet::Vec3;
 
-use super::{default::Default, solid_color::SolidColor, checker::Checker, noise::Noise, image::Image, texture::Texture};
+use super::{default::Default, solid_color::SolidColor, checker::Checker, noise::Noise, image::Image, fbm::Fbm, worley::Worley, marble::Marble, wood::Wood, color_ramp::ColorRamp, mix::Mix, arithmetic::Arithmetic, remap::Remap, channel_extract::ChannelExtract, uv_transform_node::UVTransformNode, triplanar::Triplanar, volume_grid::VolumeGrid, texture::Texture};
 
 pub enum TextureEnum {
     Default(Default),
@@ -9,6 +9,18 @@ pub enum TextureEnum {
     Checker(Checker),
     Noise(Noise),
     Image(Image),
+    Fbm(Fbm),
+    Worley(Worley),
+    Marble(Marble),
+    Wood(Wood),
+    ColorRamp(ColorRamp),
+    Mix(Mix),
+    Arithmetic(Arithmetic),
+    Remap(Remap),
+    ChannelExtract(ChannelExtract),
+    UVTransformNode(UVTransformNode),
+    Triplanar(Triplanar),
+    VolumeGrid(VolumeGrid),
 }
 
 impl Texture for TextureEnum {
@@ -20,6 +32,64 @@ impl Texture for TextureEnum {
             TextureEnum::Checker(checker) => checker.value(texture_service, u, v, p, color_out),
             TextureEnum::Noise(noise) => noise.value(texture_service, u, v, p, color_out),
             TextureEnum::Image(image) => image.value(texture_service, u, v, p, color_out),
+            TextureEnum::Fbm(fbm) => fbm.value(texture_service, u, v, p, color_out),
+            TextureEnum::Worley(worley) => worley.value(texture_service, u, v, p, color_out),
+            TextureEnum::Marble(marble) => marble.value(texture_service, u, v, p, color_out),
+            TextureEnum::Wood(wood) => wood.value(texture_service, u, v, p, color_out),
+            TextureEnum::ColorRamp(color_ramp) => color_ramp.value(texture_service, u, v, p, color_out),
+            TextureEnum::Mix(mix) => mix.value(texture_service, u, v, p, color_out),
+            TextureEnum::Arithmetic(arithmetic) => arithmetic.value(texture_service, u, v, p, color_out),
+            TextureEnum::Remap(remap) => remap.value(texture_service, u, v, p, color_out),
+            TextureEnum::ChannelExtract(channel_extract) => channel_extract.value(texture_service, u, v, p, color_out),
+            TextureEnum::UVTransformNode(uv_transform_node) => uv_transform_node.value(texture_service, u, v, p, color_out),
+            TextureEnum::Triplanar(triplanar) => triplanar.value(texture_service, u, v, p, color_out),
+            TextureEnum::VolumeGrid(volume_grid) => volume_grid.value(texture_service, u, v, p, color_out),
+        }
+    }
+
+    #[inline]
+    fn value_at_hit(&self, texture_service: &TextureService, hit: &HitRecord, color_out: &mut ColorRGB) -> bool {
+        match self {
+            TextureEnum::Default(default) => default.value_at_hit(texture_service, hit, color_out),
+            TextureEnum::SolidColor(solid_color) => solid_color.value_at_hit(texture_service, hit, color_out),
+            TextureEnum::Checker(checker) => checker.value_at_hit(texture_service, hit, color_out),
+            TextureEnum::Noise(noise) => noise.value_at_hit(texture_service, hit, color_out),
+            TextureEnum::Image(image) => image.value_at_hit(texture_service, hit, color_out),
+            TextureEnum::Fbm(fbm) => fbm.value_at_hit(texture_service, hit, color_out),
+            TextureEnum::Worley(worley) => worley.value_at_hit(texture_service, hit, color_out),
+            TextureEnum::Marble(marble) => marble.value_at_hit(texture_service, hit, color_out),
+            TextureEnum::Wood(wood) => wood.value_at_hit(texture_service, hit, color_out),
+            TextureEnum::ColorRamp(color_ramp) => color_ramp.value_at_hit(texture_service, hit, color_out),
+            TextureEnum::Mix(mix) => mix.value_at_hit(texture_service, hit, color_out),
+            TextureEnum::Arithmetic(arithmetic) => arithmetic.value_at_hit(texture_service, hit, color_out),
+            TextureEnum::Remap(remap) => remap.value_at_hit(texture_service, hit, color_out),
+            TextureEnum::ChannelExtract(channel_extract) => channel_extract.value_at_hit(texture_service, hit, color_out),
+            TextureEnum::UVTransformNode(uv_transform_node) => uv_transform_node.value_at_hit(texture_service, hit, color_out),
+            TextureEnum::Triplanar(triplanar) => triplanar.value_at_hit(texture_service, hit, color_out),
+            TextureEnum::VolumeGrid(volume_grid) => volume_grid.value_at_hit(texture_service, hit, color_out),
+        }
+    }
+
+    #[inline]
+    fn alpha(&self, texture_service: &TextureService, u: f32, v: f32, p: &Vec3) -> f32 {
+        match self {
+            TextureEnum::Default(default) => default.alpha(texture_service, u, v, p),
+            TextureEnum::SolidColor(solid_color) => solid_color.alpha(texture_service, u, v, p),
+            TextureEnum::Checker(checker) => checker.alpha(texture_service, u, v, p),
+            TextureEnum::Noise(noise) => noise.alpha(texture_service, u, v, p),
+            TextureEnum::Image(image) => image.alpha(texture_service, u, v, p),
+            TextureEnum::Fbm(fbm) => fbm.alpha(texture_service, u, v, p),
+            TextureEnum::Worley(worley) => worley.alpha(texture_service, u, v, p),
+            TextureEnum::Marble(marble) => marble.alpha(texture_service, u, v, p),
+            TextureEnum::Wood(wood) => wood.alpha(texture_service, u, v, p),
+            TextureEnum::ColorRamp(color_ramp) => color_ramp.alpha(texture_service, u, v, p),
+            TextureEnum::Mix(mix) => mix.alpha(texture_service, u, v, p),
+            TextureEnum::Arithmetic(arithmetic) => arithmetic.alpha(texture_service, u, v, p),
+            TextureEnum::Remap(remap) => remap.alpha(texture_service, u, v, p),
+            TextureEnum::ChannelExtract(channel_extract) => channel_extract.alpha(texture_service, u, v, p),
+            TextureEnum::UVTransformNode(uv_transform_node) => uv_transform_node.alpha(texture_service, u, v, p),
+            TextureEnum::Triplanar(triplanar) => triplanar.alpha(texture_service, u, v, p),
+            TextureEnum::VolumeGrid(volume_grid) => volume_grid.alpha(texture_service, u, v, p),
         }
     }
 }
\ No newline at end of file
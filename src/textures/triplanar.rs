@@ -0,0 +1,114 @@
+use ultraviolet::Vec3;
+
+use crate::{services::texture_service::TextureService, core::color_rgb::ColorRGB, hittables::hit_record::HitRecord};
+
+use super::texture::Texture;
+
+// Projects a uv mapped texture along the three axes and blends the projections by the shading normal,
+// for hittables without usable texture coordinates. Each projection is weighted by the matching
+// component of the normal to the power of sharpness, higher sharpness gives narrower seams.
+// Lookups without a hit have no normal and blend the three projections equally.
+// The projected coordinates are wrapped to [0, 1), so the input tiles whatever its wrap mode is,
+// images should still use WrapMode::Repeat to filter across the tile borders.
+pub struct Triplanar {
+    input: usize,
+    scale: f32,
+    sharpness: f32,
+}
+
+impl Triplanar {
+    pub fn new(input: usize, scale: f32, sharpness: f32) -> Self {
+        Triplanar { input, scale, sharpness }
+    }
+
+    fn weights(&self, normal: &Vec3) -> Vec3 {
+        let weights = Vec3::new(normal.x.abs().powf(self.sharpness), normal.y.abs().powf(self.sharpness), normal.z.abs().powf(self.sharpness));
+        let weight_sum = weights.x + weights.y + weights.z;
+        if weight_sum <= 0.0 {
+            return Vec3::broadcast(1.0 / 3.0);
+        }
+
+        weights / weight_sum
+    }
+
+    // The inputs are looked up with the projected uv through lookup, so lookups at a hit stay at the hit
+    fn blend(&self, p: &Vec3, weights: &Vec3, lookup: impl Fn(f32, f32, &mut ColorRGB) -> bool, color_out: &mut ColorRGB) -> bool {
+        let point = *p * self.scale;
+        let point = Vec3::new(point.x.rem_euclid(1.0), point.y.rem_euclid(1.0), point.z.rem_euclid(1.0));
+        let projections = [(point.y, point.z, weights.x), (point.x, point.z, weights.y), (point.x, point.y, weights.z)];
+
+        *color_out = ColorRGB::black();
+        let mut projected: ColorRGB = ColorRGB::black();
+        for (u, v, weight) in projections {
+            if 0.0 < weight {
+                lookup(u, v, &mut projected);
+                *color_out += projected * weight;
+            }
+        }
+
+        true
+    }
+}
+
+impl Texture for Triplanar {
+    fn value(&self, texture_service: &TextureService, _u: f32, _v: f32, p: &Vec3, color_out: &mut ColorRGB) -> bool {
+        self.blend(p, &Vec3::broadcast(1.0 / 3.0), |u, v, color| texture_service.value(self.input, u, v, p, color), color_out)
+    }
+
+    // The projections don't line up with the tangents of the hit, so the wider extent of the footprint
+    // is used along both projected axes
+    fn value_at_hit(&self, texture_service: &TextureService, hit: &HitRecord, color_out: &mut ColorRGB) -> bool {
+        let footprint = hit.footprint.0.max(hit.footprint.1);
+        let projected = HitRecord { uv_scale: (1.0 / self.scale, 1.0 / self.scale), footprint: (footprint, footprint), ..*hit };
+
+        self.blend(&hit.position, &self.weights(&hit.normal), |u, v, color| texture_service.value_at_hit(self.input, &HitRecord { u, v, ..projected }, color), color_out)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{textures::{texture_enum::TextureEnum, image::Image}, core::ray::Ray};
+
+    use super::*;
+
+    fn hit_with_normal(position: Vec3, normal: Vec3) -> HitRecord {
+        let ray = Ray::new(position + normal, -normal, 0.0);
+        HitRecord::new(&ray, 1.0, 0.0, 0.0, &position, &normal, 0)
+    }
+
+    #[test]
+    fn weights_follow_the_normal() {
+        let triplanar = Triplanar::new(0, 1.0, 4.0);
+
+        let up = triplanar.weights(&Vec3::new(0.0, -1.0, 0.0));
+        assert_eq!((up.x, up.y, up.z), (0.0, 1.0, 0.0));
+
+        let tilted = triplanar.weights(&Vec3::new(1.0, 1.0, 0.0).normalized());
+        assert!((tilted.x - 0.5).abs() < 1.0e-6 && (tilted.y - 0.5).abs() < 1.0e-6 && tilted.z == 0.0);
+
+        let skewed = triplanar.weights(&Vec3::new(0.3, -0.5, 0.8).normalized());
+        assert!((skewed.x + skewed.y + skewed.z - 1.0).abs() < 1.0e-6);
+        assert!(skewed.x < skewed.y && skewed.y < skewed.z);
+    }
+
+    #[test]
+    fn flat_faces_read_the_projection_along_their_normal() {
+        // 2 by 3 texels, red is x + 10 y
+        let mut texture_service = TextureService::new();
+        let input = texture_service.add_texture(TextureEnum::Image(Image::new_data("fixtures/rgb_le.pfm")));
+        let triplanar = Triplanar::new(input, 1.0, 4.0);
+
+        // The same spot on faces of boxes anywhere in the scene, wrapped to (0.75, 0.1, 0.5)
+        let red = |position: Vec3, normal: Vec3| {
+            let mut color = ColorRGB::black();
+            triplanar.value_at_hit(&texture_service, &hit_with_normal(position, normal), &mut color);
+            color.r
+        };
+        for offset in [Vec3::zero(), Vec3::new(30.0, 2.0, -7.0), Vec3::new(-5.0, -40.0, 12.0)] {
+            let position = Vec3::new(0.75, 0.1, 0.5) + offset;
+            assert_eq!(red(position, Vec3::new(1.0, 0.0, 0.0)), 10.0); // y and z, texel (0, 1)
+            assert_eq!(red(position, Vec3::new(0.0, 1.0, 0.0)), 11.0); // x and z, texel (1, 1)
+            assert_eq!(red(position, Vec3::new(0.0, 0.0, -1.0)), 1.0); // x and y, texel (1, 0)
+        }
+    }
+}
@@ -0,0 +1,39 @@
+// Scales, then rotates (in radians, around the origin) and then offsets texture coordinates.
+// A scale of 4 repeats a texture four times across a surface, given a repeating wrap mode.
+#[derive(Clone, Copy)]
+pub struct UVTransform {
+    pub scale_u: f32,
+    pub scale_v: f32,
+    pub offset_u: f32,
+    pub offset_v: f32,
+    cos_rotation: f32,
+    sin_rotation: f32,
+}
+
+impl UVTransform {
+    pub fn new(scale_u: f32, scale_v: f32, offset_u: f32, offset_v: f32, rotation: f32) -> Self {
+        UVTransform { scale_u, scale_v, offset_u, offset_v, cos_rotation: rotation.cos(), sin_rotation: rotation.sin() }
+    }
+
+    pub fn identity() -> Self {
+        UVTransform::new(1.0, 1.0, 0.0, 0.0, 0.0)
+    }
+
+    // World distance covered by one unit of the transformed uv, for picking mip levels.
+    // Scaling up packs more of the texture into the same distance, the rotation is ignored.
+    #[inline]
+    pub fn apply_to_uv_scale(&self, uv_scale: (f32, f32)) -> (f32, f32) {
+        (uv_scale.0 / self.scale_u.abs().max(f32::EPSILON), uv_scale.1 / self.scale_v.abs().max(f32::EPSILON))
+    }
+
+    #[inline]
+    pub fn apply(&self, u: f32, v: f32) -> (f32, f32) {
+        let u = u * self.scale_u;
+        let v = v * self.scale_v;
+
+        (
+            self.cos_rotation * u - self.sin_rotation * v + self.offset_u,
+            self.sin_rotation * u + self.cos_rotation * v + self.offset_v,
+        )
+    }
+}
\ No newline at end of file
@@ -0,0 +1,37 @@
+use ultraviolet::Vec3;
+
+use crate::{services::texture_service::TextureService, core::color_rgb::ColorRGB, hittables::hit_record::HitRecord};
+
+use super::{texture::Texture, uv_transform::UVTransform};
+
+// Texture node applying a UVTransform to the coordinates of another texture, alpha included.
+// Image applies its own UVTransform without a node, this is for everything else.
+pub struct UVTransformNode {
+    input: usize,
+    transform: UVTransform,
+}
+
+impl UVTransformNode {
+    pub fn new(input: usize, transform: UVTransform) -> Self {
+        UVTransformNode { input, transform }
+    }
+}
+
+impl Texture for UVTransformNode {
+    fn value(&self, texture_service: &TextureService, u: f32, v: f32, p: &Vec3, color_out: &mut ColorRGB) -> bool {
+        let (u, v) = self.transform.apply(u, v);
+        texture_service.value(self.input, u, v, p, color_out)
+    }
+
+    fn value_at_hit(&self, texture_service: &TextureService, hit: &HitRecord, color_out: &mut ColorRGB) -> bool {
+        let mut transformed = *hit;
+        (transformed.u, transformed.v) = self.transform.apply(hit.u, hit.v);
+        transformed.uv_scale = self.transform.apply_to_uv_scale(hit.uv_scale);
+        texture_service.value_at_hit(self.input, &transformed, color_out)
+    }
+
+    fn alpha(&self, texture_service: &TextureService, u: f32, v: f32, p: &Vec3) -> f32 {
+        let (u, v) = self.transform.apply(u, v);
+        texture_service.alpha(self.input, u, v, p)
+    }
+}
\ No newline at end of file
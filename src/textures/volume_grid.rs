@@ -0,0 +1,193 @@
+use ultraviolet::Vec3;
+
+use crate::{services::texture_service::TextureService, core::color_rgb::ColorRGB};
+
+use super::texture::Texture;
+
+// Scalar values on a regular 3D grid spanning the box from minimum to maximum,
+// like density or temperature. Lookups are trilinear and zero outside of the box.
+#[derive(Clone)]
+pub struct VolumeGrid {
+    data: Vec<f32>, // x varies fastest, then y, then z
+    resolution: [usize; 3],
+    minimum: Vec3,
+    maximum: Vec3,
+    max_value: f32,
+}
+
+impl VolumeGrid {
+    pub fn new(data: Vec<f32>, resolution: [usize; 3], minimum: Vec3, maximum: Vec3) -> Self {
+        assert!(data.len() == resolution[0] * resolution[1] * resolution[2], "volume grid data does not match its resolution");
+        let max_value = data.iter().fold(0.0f32, |max, value| max.max(*value));
+
+        VolumeGrid { data, resolution, minimum, maximum, max_value }
+    }
+
+    // Mitsuba .vol files with float32 data. Only the first channel is used,
+    // the bounding box is taken from the file.
+    pub fn from_vol(path: &str) -> Self {
+        let bytes = std::fs::read(path).expect("volume not found");
+        assert!(48 <= bytes.len() && &bytes[0..3] == b"VOL" && bytes[3] == 3, "{} is not a version 3 vol file", path);
+
+        let read_i32 = |offset: usize| i32::from_le_bytes(bytes[offset..offset + 4].try_into().unwrap());
+        let read_f32 = |offset: usize| f32::from_le_bytes(bytes[offset..offset + 4].try_into().unwrap());
+
+        assert!(read_i32(4) == 1, "only float32 vol files are supported");
+        let resolution: [usize; 3] = [read_i32(8) as usize, read_i32(12) as usize, read_i32(16) as usize];
+        let channel_count = read_i32(20) as usize;
+        let minimum = Vec3::new(read_f32(24), read_f32(28), read_f32(32));
+        let maximum = Vec3::new(read_f32(36), read_f32(40), read_f32(44));
+
+        let data: Vec<f32> = bytes[48..]
+            .chunks_exact(4 * channel_count)
+            .map(|voxel| f32::from_le_bytes(voxel[0..4].try_into().unwrap()))
+            .collect();
+
+        VolumeGrid::new(data, resolution, minimum, maximum)
+    }
+
+    // Samples the red channel of any texture at the voxel centers, turning a procedural texture into a grid
+    pub fn bake(texture_service: &TextureService, texture_index: usize, resolution: [usize; 3], minimum: Vec3, maximum: Vec3) -> Self {
+        let extent = maximum - minimum;
+        let mut data: Vec<f32> = Vec::with_capacity(resolution[0] * resolution[1] * resolution[2]);
+        let mut color: ColorRGB = ColorRGB::black();
+
+        for z in 0..resolution[2] {
+            for y in 0..resolution[1] {
+                for x in 0..resolution[0] {
+                    let point = minimum + extent * Vec3::new(
+                        (x as f32 + 0.5) / resolution[0] as f32, 
+                        (y as f32 + 0.5) / resolution[1] as f32, 
+                        (z as f32 + 0.5) / resolution[2] as f32
+                    );
+                    texture_service.value(texture_index, 0.0, 0.0, &point, &mut color);
+                    data.push(color.r.max(0.0));
+                }
+            }
+        }
+
+        VolumeGrid::new(data, resolution, minimum, maximum)
+    }
+
+    #[inline]
+    pub fn max_value(&self) -> f32 {
+        self.max_value
+    }
+
+    #[inline]
+    fn voxel(&self, x: usize, y: usize, z: usize) -> f32 {
+        self.data[(z * self.resolution[1] + y) * self.resolution[0] + x]
+    }
+
+    pub fn lookup(&self, point: &Vec3) -> f32 {
+        let local = (*point - self.minimum) / (self.maximum - self.minimum);
+        if local.x < 0.0 || local.y < 0.0 || local.z < 0.0 || 1.0 < local.x || 1.0 < local.y || 1.0 < local.z {
+            return 0.0;
+        }
+
+        // Voxel centers sit at half integer grid coordinates
+        let grid = Vec3::new(
+            local.x * self.resolution[0] as f32 - 0.5, 
+            local.y * self.resolution[1] as f32 - 0.5, 
+            local.z * self.resolution[2] as f32 - 0.5
+        );
+        let clamp = |value: f32, axis: usize| (value.max(0.0) as usize).min(self.resolution[axis] - 1);
+
+        let x0 = clamp(grid.x.floor(), 0);
+        let y0 = clamp(grid.y.floor(), 1);
+        let z0 = clamp(grid.z.floor(), 2);
+        let x1 = clamp(grid.x.floor() + 1.0, 0);
+        let y1 = clamp(grid.y.floor() + 1.0, 1);
+        let z1 = clamp(grid.z.floor() + 1.0, 2);
+
+        let weight_x = (grid.x - grid.x.floor()).clamp(0.0, 1.0);
+        let weight_y = (grid.y - grid.y.floor()).clamp(0.0, 1.0);
+        let weight_z = (grid.z - grid.z.floor()).clamp(0.0, 1.0);
+
+        let lerp = |a: f32, b: f32, weight: f32| a + (b - a) * weight;
+        let front = lerp(
+            lerp(self.voxel(x0, y0, z0), self.voxel(x1, y0, z0), weight_x),
+            lerp(self.voxel(x0, y1, z0), self.voxel(x1, y1, z0), weight_x),
+            weight_y
+        );
+        let back = lerp(
+            lerp(self.voxel(x0, y0, z1), self.voxel(x1, y0, z1), weight_x),
+            lerp(self.voxel(x0, y1, z1), self.voxel(x1, y1, z1), weight_x),
+            weight_y
+        );
+
+        lerp(front, back, weight_z)
+    }
+}
+
+impl Texture for VolumeGrid {
+    fn value(&self, _texture_service: &TextureService, _u: f32, _v: f32, point: &Vec3, color_out: &mut ColorRGB) -> bool {
+        let value = self.lookup(point);
+
+        color_out.r = value;
+        color_out.g = value;
+        color_out.b = value;
+
+        true
+    }
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // A 2x3x2 grid with two channels, the first holding x + 2y + 4z, which trilinear lookups reproduce exactly
+    fn write_vol(path: &std::path::Path) {
+        let resolution: [i32; 3] = [2, 3, 2];
+        let mut bytes: Vec<u8> = b"VOL".to_vec();
+        bytes.push(3);
+        for value in [1, resolution[0], resolution[1], resolution[2], 2] {
+            bytes.extend_from_slice(&value.to_le_bytes());
+        }
+        for value in [1.0_f32, 2.0, 3.0, 3.0, 5.0, 5.0] {
+            bytes.extend_from_slice(&value.to_le_bytes());
+        }
+        for z in 0..resolution[2] {
+            for y in 0..resolution[1] {
+                for x in 0..resolution[0] {
+                    bytes.extend_from_slice(&((x + 2 * y + 4 * z) as f32).to_le_bytes());
+                    bytes.extend_from_slice(&(-1.0_f32).to_le_bytes());
+                }
+            }
+        }
+
+        std::fs::write(path, bytes).unwrap();
+    }
+
+    #[test]
+    fn reads_vol_header_and_first_channel() {
+        let path = std::env::temp_dir().join("raytracing_in_rust_test.vol");
+        write_vol(&path);
+        let grid = VolumeGrid::from_vol(&path.to_string_lossy());
+
+        assert_eq!(grid.resolution, [2, 3, 2]);
+        assert_eq!((grid.minimum.x, grid.minimum.y, grid.minimum.z), (1.0, 2.0, 3.0));
+        assert_eq!((grid.maximum.x, grid.maximum.y, grid.maximum.z), (3.0, 5.0, 5.0));
+        assert_eq!(grid.data.len(), 12);
+        assert_eq!(grid.max_value(), 1.0 + 2.0 * 2.0 + 4.0);
+    }
+
+    #[test]
+    fn lookups_are_trilinear_and_zero_outside() {
+        let path = std::env::temp_dir().join("raytracing_in_rust_test_lookup.vol");
+        write_vol(&path);
+        let grid = VolumeGrid::from_vol(&path.to_string_lossy());
+
+        // Voxels are one unit wide, their centers are half a unit in from the minimum
+        let at_grid = |x: f32, y: f32, z: f32| grid.lookup(&Vec3::new(1.5 + x, 2.5 + y, 3.5 + z));
+        assert!((at_grid(0.0, 0.0, 0.0) - 0.0).abs() < 1.0e-5);
+        assert!((at_grid(1.0, 2.0, 1.0) - 9.0).abs() < 1.0e-5);
+        assert!((at_grid(0.5, 0.5, 0.5) - 3.5).abs() < 1.0e-5);
+        assert!((at_grid(0.25, 1.5, 0.75) - (0.25 + 3.0 + 3.0)).abs() < 1.0e-5);
+
+        // Between the outermost centers and the box the values are held
+        assert!((grid.lookup(&Vec3::new(1.1, 2.1, 3.1)) - 0.0).abs() < 1.0e-5);
+
+        assert_eq!(grid.lookup(&Vec3::new(0.9, 3.0, 4.0)), 0.0);
+        assert_eq!(grid.lookup(&Vec3::new(2.0, 5.1, 4.0)), 0.0);
+    }
+}
@@ -0,0 +1,34 @@
+use ultraviolet::Vec3;
+
+use crate::{services::texture_service::TextureService, noise::perlin::Perlin, core::color_rgb::ColorRGB};
+
+use super::texture::Texture;
+
+// Growth rings around the y axis through the origin, so place the log with the position of the object in mind.
+// Rings are ring_frequency per unit of distance, and wobble by distortion times the turbulence.
+// The result is grayscale, 0 in early wood and rising sharply towards 1 in the dark late wood.
+pub struct Wood {
+    perlin: Perlin,
+    ring_frequency: f32,
+    distortion: f32,
+}
+
+impl Wood {
+    pub fn new(seed: u64, point_count: u32, ring_frequency: f32, distortion: f32) -> Self {
+        Wood { perlin: Perlin::new(seed, point_count), ring_frequency, distortion }
+    }
+}
+
+impl Texture for Wood {
+    fn value(&self, _texture_service: &TextureService, _u: f32, _v: f32, point: &Vec3, color_out: &mut ColorRGB) -> bool {
+        let radius = (point.x * point.x + point.z * point.z).sqrt();
+        let rings = radius * self.ring_frequency + self.distortion * self.perlin.turbulence(&(*point * 0.05), 4);
+        let value = rings.fract().powi(3);
+
+        color_out.r = value;
+        color_out.g = value;
+        color_out.b = value;
+
+        true
+    }
+}
\ No newline at end of file
@@ -0,0 +1,82 @@
+use ultraviolet::Vec3;
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha20Rng;
+
+use crate::{services::texture_service::TextureService, core::color_rgb::ColorRGB};
+
+use super::texture::Texture;
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum WorleyPattern {
+    F1, // Distance to the closest feature point, round cells
+    F2MinusF1, // Distance between the two closest feature points, dark cell borders
+}
+
+// Cellular noise with one randomly placed feature point per unit cell.
+// The result is grayscale, distances are clamped to 1.
+pub struct Worley {
+    permutation: Vec<usize>, // Doubled, so lookups never have to wrap
+    feature_points: Vec<Vec3>,
+    scale: f32,
+    pattern: WorleyPattern,
+}
+
+impl Worley {
+    pub fn new(seed: u64, scale: f32, pattern: WorleyPattern) -> Self {
+        let mut rng: ChaCha20Rng = ChaCha20Rng::seed_from_u64(seed);
+        let mut permutation: Vec<usize> = (0..256).collect();
+        for index in (1..256).rev() {
+            let target = rng.gen_range(0..=index);
+            permutation.swap(index, target);
+        }
+        let permutation: Vec<usize> = permutation.iter().chain(permutation.iter()).copied().collect();
+
+        let feature_points: Vec<Vec3> = (0..256).map(|_| Vec3::new(rng.gen::<f32>(), rng.gen::<f32>(), rng.gen::<f32>())).collect();
+
+        Worley { permutation, feature_points, scale, pattern }
+    }
+
+    #[inline]
+    fn feature_point(&self, x: i64, y: i64, z: i64) -> Vec3 {
+        let p = &self.permutation;
+        let hash = p[(x & 255) as usize + p[(y & 255) as usize + p[(z & 255) as usize]]];
+
+        Vec3::new(x as f32, y as f32, z as f32) + self.feature_points[hash]
+    }
+}
+
+impl Texture for Worley {
+    fn value(&self, _texture_service: &TextureService, _u: f32, _v: f32, point: &Vec3, color_out: &mut ColorRGB) -> bool {
+        let point = *point * self.scale;
+        let cell_x = point.x.floor() as i64;
+        let cell_y = point.y.floor() as i64;
+        let cell_z = point.z.floor() as i64;
+
+        let mut closest: f32 = f32::MAX;
+        let mut second_closest: f32 = f32::MAX;
+        for offset_x in -1..=1 {
+            for offset_y in -1..=1 {
+                for offset_z in -1..=1 {
+                    let distance = (self.feature_point(cell_x + offset_x, cell_y + offset_y, cell_z + offset_z) - point).mag();
+                    if distance < closest {
+                        second_closest = closest;
+                        closest = distance;
+                    } else if distance < second_closest {
+                        second_closest = distance;
+                    }
+                }
+            }
+        }
+
+        let value = match self.pattern {
+            WorleyPattern::F1 => closest,
+            WorleyPattern::F2MinusF1 => second_closest - closest,
+        }.min(1.0);
+
+        color_out.r = value;
+        color_out.g = value;
+        color_out.b = value;
+
+        true
+    }
+}
\ No newline at end of file